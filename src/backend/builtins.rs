@@ -0,0 +1,283 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::{
+    diagnostics::{Diagnostic, DiagnosticKind},
+    span::Spanned,
+    types::{PrimitiveType, Value},
+};
+
+/// A relation whose contents are computed natively instead of stored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Builtin {
+    /// `Add(a, b, c)` holds when `a + b = c`.
+    Add,
+
+    /// `Sub(a, b, c)` holds when `a - b = c`.
+    Sub,
+
+    /// `Mul(a, b, c)` holds when `a * b = c`.
+    Mul,
+
+    /// `Div(a, b, c)` holds when `a / b = c`, rounding towards zero.
+    Div,
+
+    /// `Mod(a, b, c)` holds when `a % b = c`.
+    Mod,
+
+    /// `Eq(a, b)` holds when `a = b`.
+    Eq,
+
+    /// `Ne(a, b)` holds when `a != b`.
+    Ne,
+
+    /// `Lt(a, b)` holds when `a < b`.
+    Lt,
+
+    /// `Le(a, b)` holds when `a <= b`.
+    Le,
+
+    /// `Gt(a, b)` holds when `a > b`.
+    Gt,
+
+    /// `Ge(a, b)` holds when `a >= b`.
+    Ge,
+
+    /// `Range(start, end, x)` holds when `start <= x < end`.
+    Range,
+}
+
+impl Builtin {
+    /// The most values that [Builtin::Range] enumerates for an unbound term.
+    pub const RANGE_LIMIT: i64 = 1 << 20;
+
+    /// Every built-in relation.
+    pub const ALL: &'static [Builtin] = &[
+        Builtin::Add,
+        Builtin::Sub,
+        Builtin::Mul,
+        Builtin::Div,
+        Builtin::Mod,
+        Builtin::Eq,
+        Builtin::Ne,
+        Builtin::Lt,
+        Builtin::Le,
+        Builtin::Gt,
+        Builtin::Ge,
+        Builtin::Range,
+    ];
+
+    /// Looks up a built-in relation by its name within a standard library module.
+    pub fn lookup(module: &[&str], name: &str) -> Option<Self> {
+        if module != ["Math"] {
+            return None;
+        }
+
+        Self::ALL
+            .iter()
+            .find(|builtin| builtin.to_string() == name)
+            .copied()
+    }
+
+    /// Looks up the built-in relation that an arithmetic or comparison operator desugars to.
+    pub fn from_operator(op: &str) -> Option<Self> {
+        match op {
+            "+" => Some(Builtin::Add),
+            "-" => Some(Builtin::Sub),
+            "*" => Some(Builtin::Mul),
+            "/" => Some(Builtin::Div),
+            "%" => Some(Builtin::Mod),
+            "=" => Some(Builtin::Eq),
+            "!=" => Some(Builtin::Ne),
+            "<" => Some(Builtin::Lt),
+            "<=" => Some(Builtin::Le),
+            ">" => Some(Builtin::Gt),
+            ">=" => Some(Builtin::Ge),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the given path names a standard library module.
+    pub fn is_module(module: &[&str]) -> bool {
+        module == ["Math"]
+    }
+
+    /// The types of each term of this relation.
+    pub fn signature(&self) -> &'static [PrimitiveType] {
+        use Builtin::*;
+        use PrimitiveType::Integer;
+        match self {
+            Add | Sub | Mul | Div | Mod | Range => &[Integer, Integer, Integer],
+            Eq | Ne | Lt | Le | Gt | Ge => &[Integer, Integer],
+        }
+    }
+
+    /// Tests if this relation can be evaluated given which of its terms are bound.
+    pub fn can_eval(&self, bound: &[bool]) -> bool {
+        use Builtin::*;
+        match (self, bound) {
+            (_, [true, true, true]) | (_, [true, true]) => true,
+            (Add | Sub | Mul, [a, b, c]) => [a, b, c].into_iter().filter(|el| **el).count() >= 2,
+            (Div | Mod | Range, [true, true, _]) => true,
+            (Eq, [a, b]) => *a || *b,
+            _ => false,
+        }
+    }
+
+    /// Finds how many tuples evaluating this relation with the given bound
+    /// terms would enumerate, if that is more than [Builtin::RANGE_LIMIT].
+    pub fn oversized(&self, args: &[Option<Value>]) -> Option<i64> {
+        let len = match (self, args) {
+            (Builtin::Range, [Some(Value::Integer(start)), Some(Value::Integer(end)), None]) => {
+                end.saturating_sub(*start)
+            }
+            _ => return None,
+        };
+
+        (len > Self::RANGE_LIMIT).then_some(len)
+    }
+
+    /// Reports if evaluating this relation with the given bound terms would
+    /// enumerate more tuples than [Builtin::RANGE_LIMIT].
+    pub fn limit_diagnostic<S: Clone>(
+        &self,
+        span: S,
+        args: &[Option<Value>],
+    ) -> Option<Diagnostic<S>> {
+        self.oversized(args)
+            .map(|len| Self::oversized_diagnostic(span, len))
+    }
+
+    /// Reports a range of `len` values that exceeds [Builtin::RANGE_LIMIT].
+    pub fn oversized_diagnostic<S: Clone>(span: S, len: i64) -> Diagnostic<S> {
+        Diagnostic {
+            span: span.clone(),
+            kind: DiagnosticKind::Error,
+            message: format!(
+                "Range of {len} values exceeds the limit of {}",
+                Self::RANGE_LIMIT
+            ),
+            labels: vec![Spanned {
+                span,
+                inner: "Narrow this range.".to_string(),
+            }],
+        }
+    }
+
+    /// Evaluates all complete tuples of this relation matching the bound terms.
+    ///
+    /// Callers must first check that the bound terms satisfy [Builtin::can_eval].
+    /// Ranges beyond [Builtin::RANGE_LIMIT] evaluate to nothing, and should be
+    /// reported with [Builtin::oversized] instead.
+    pub fn eval(&self, args: &[Option<Value>]) -> Vec<Vec<i64>> {
+        let ints: Option<Vec<Option<i64>>> = args
+            .iter()
+            .map(|arg| match arg {
+                None => Some(None),
+                Some(Value::Integer(val)) => Some(Some(*val)),
+                Some(_) => None,
+            })
+            .collect();
+
+        // arguments of other types are rejected by type checking
+        let Some(ints) = ints else {
+            return vec![];
+        };
+
+        use Builtin::*;
+        match (self, ints.as_slice()) {
+            (Add, [a, b, c]) => Self::invert(*a, *b, *c, i64::checked_add, i64::checked_sub),
+            (Sub, [a, b, c]) => Self::invert(*c, *b, *a, i64::checked_add, i64::checked_sub)
+                .into_iter()
+                .map(|abc| vec![abc[2], abc[1], abc[0]])
+                .collect(),
+            (Mul, [a, b, c]) => Self::invert(*a, *b, *c, i64::checked_mul, |c, x| {
+                (x != 0 && c % x == 0).then(|| c / x)
+            }),
+            (Div, [Some(a), Some(b), c]) => Self::forward(*a, *b, *c, i64::checked_div),
+            (Mod, [Some(a), Some(b), c]) => Self::forward(*a, *b, *c, i64::checked_rem),
+            (Range, [Some(start), Some(end), x]) => match x {
+                Some(x) => (start <= x && x < end)
+                    .then(|| vec![*start, *end, *x])
+                    .into_iter()
+                    .collect(),
+                None if end.saturating_sub(*start) > Self::RANGE_LIMIT => vec![],
+                None => (*start..*end).map(|x| vec![*start, *end, x]).collect(),
+            },
+            (Eq, [Some(a), Some(b)]) => Self::test(*a, *b, a == b),
+            (Eq, [Some(a), None]) | (Eq, [None, Some(a)]) => vec![vec![*a, *a]],
+            (Ne, [Some(a), Some(b)]) => Self::test(*a, *b, a != b),
+            (Lt, [Some(a), Some(b)]) => Self::test(*a, *b, a < b),
+            (Le, [Some(a), Some(b)]) => Self::test(*a, *b, a <= b),
+            (Gt, [Some(a), Some(b)]) => Self::test(*a, *b, a > b),
+            (Ge, [Some(a), Some(b)]) => Self::test(*a, *b, a >= b),
+            _ => vec![],
+        }
+    }
+
+    /// Evaluates `op(a, b) = c`, solving for any one unbound term with `inv(c, a) = b`.
+    fn invert(
+        a: Option<i64>,
+        b: Option<i64>,
+        c: Option<i64>,
+        op: impl Fn(i64, i64) -> Option<i64>,
+        inv: impl Fn(i64, i64) -> Option<i64>,
+    ) -> Vec<Vec<i64>> {
+        let solution = match (a, b, c) {
+            (Some(a), Some(b), Some(c)) => (op(a, b) == Some(c)).then_some([a, b, c]),
+            (Some(a), Some(b), None) => op(a, b).map(|c| [a, b, c]),
+            (Some(a), None, Some(c)) => inv(c, a).map(|b| [a, b, c]),
+            (None, Some(b), Some(c)) => inv(c, b).map(|a| [a, b, c]),
+            _ => None,
+        };
+
+        solution.into_iter().map(Vec::from).collect()
+    }
+
+    /// Evaluates `op(a, b) = c` where only `c` may be unbound.
+    fn forward(
+        a: i64,
+        b: i64,
+        c: Option<i64>,
+        op: impl Fn(i64, i64) -> Option<i64>,
+    ) -> Vec<Vec<i64>> {
+        match (op(a, b), c) {
+            (Some(result), Some(c)) if result == c => vec![vec![a, b, c]],
+            (Some(result), None) => vec![vec![a, b, result]],
+            _ => vec![],
+        }
+    }
+
+    fn test(a: i64, b: i64, result: bool) -> Vec<Vec<i64>> {
+        if result {
+            vec![vec![a, b]]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
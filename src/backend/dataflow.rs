@@ -18,30 +18,66 @@
 
 use std::sync::Arc;
 
-use differential_dataflow::operators::{iterate::Variable, Join, Threshold};
-use timely::{communication::Allocator, dataflow::Scope, order::Product, worker::Worker};
-
-use crate::{
-    backend::types::*,
-    utils::{map_value, swap, value, InputSession, Key},
+use differential_dataflow::{
+    lattice::Lattice,
+    operators::{arrange::ArrangeBySelf, iterate::Variable, Join, Reduce, Threshold},
+    Collection,
+};
+use timely::{
+    communication::Allocate,
+    dataflow::{operators::Probe, ProbeHandle, Scope},
+    order::Product,
+    worker::Worker,
 };
 
-pub fn backend(worker: &mut Worker<Allocator>) {
-    let mut facts = InputSession::new();
-    let mut nodes = InputSession::new();
+use crate::{backend::types::*, utils::*};
+
+pub fn backend<G: Scope>(inputs: BackendInputs<G>) -> BackendOutputs<G>
+where
+    G::Timestamp: Lattice,
+{
+    let mut scope = inputs.facts.scope();
+    let nodes = inputs.nodes.map(Key::pair);
+    let facts = inputs.facts;
 
-    worker.dataflow(|scope| {
-        let nodes = nodes.to_collection(scope).map(Key::pair);
-        let facts = facts.to_collection(scope);
+    let (relations, oversized) = scope.iterative::<u32, _, _>(|scope| {
+        // import context
+        let facts = facts.enter(scope);
+        let nodes = nodes.enter(scope);
 
-        let relations = scope.iterative::<u32, _, _>(|scope| {
+        // the complete node contents of the previous stratum
+        let step = Product::new(Default::default(), 1);
+        let complete: Variable<_, (Key<Node>, Tuple), Diff> = Variable::new(scope, step);
+
+        // aggregate the complete contents of each reduced node by group
+        let reduce = nodes
+            .flat_map(map_value(Node::reduce))
+            .map(|(dst, (src, (num, reducer)))| (src, (dst, num, reducer)))
+            .join(&complete)
+            .map(|(_src, ((dst, num, reducer), tuple))| {
+                let group = Tuple::from(&tuple[..num]);
+                ((dst, group, reducer), tuple)
+            })
+            .reduce(|(_dst, _group, reducer), input, output| {
+                let tuples = input.iter().map(|(tuple, _diff)| &tuple[..]);
+                output.extend(reducer.reduce(tuples).map(|result| (result, 1)));
+            })
+            .map(|((dst, group, _reducer), result)| {
+                let tuple = group.iter().cloned().chain([result]).collect();
+                (dst, tuple)
+            });
+
+        // evaluate all nodes to a fixed point, negating only complete contents
+        let (relations, tuples, oversized) = scope.iterative::<u32, _, _>(|scope| {
             // import context
             let facts = facts.enter(scope);
             let nodes = nodes.enter(scope);
+            let complete = complete.enter(scope);
+            let reduce = reduce.enter(scope);
 
             // initialize iterative variables and state
             let step = Product::new(Default::default(), 1);
-            let relations = Variable::new_from(facts, step);
+            let relations = Variable::new_from(facts, step.clone());
             let tuples = Variable::new(scope, step);
 
             // extract left and right sides of join operation
@@ -62,6 +98,27 @@ pub fn backend(worker: &mut Worker<Allocator>) {
             // select and merge sides of join source nodes
             let join = join_lhs.join(&join_rhs).map(join);
 
+            // extract left side of antijoin operation
+            let antijoin_lhs = nodes
+                .flat_map(map_value(Node::antijoin_lhs))
+                .map(|(dst, (src, num))| (src, (dst, num)))
+                .join(&tuples)
+                .map(value)
+                .map(join_slice);
+
+            // extract the complete right side of antijoin operation
+            let antijoin_rhs = nodes
+                .flat_map(map_value(Node::antijoin_rhs))
+                .map(|(dst, (src, num))| (src, (dst, num)))
+                .join(&complete)
+                .map(value)
+                .map(join_slice)
+                .map(key)
+                .distinct();
+
+            // remove matching left-hand tuples
+            let antijoin = antijoin_lhs.antijoin(&antijoin_rhs).map(unslice);
+
             // project operation
             let project_src = nodes.flat_map(map_value(Node::project_src)).map(swap);
             let project_map = nodes.flat_map(map_value(Node::project_map));
@@ -73,6 +130,43 @@ pub fn backend(worker: &mut Worker<Allocator>) {
                 .join(&project_map)
                 .map(project);
 
+            // filter operation
+            let filter_src = nodes.flat_map(map_value(Node::filter_src)).map(swap);
+            let filter_conditions = nodes.flat_map(map_value(Node::filter_conditions));
+
+            // join together filter targets and test their conditions
+            let filter = filter_src
+                .join(&tuples)
+                .map(value)
+                .join(&filter_conditions)
+                .flat_map(filter);
+
+            // native operation
+            let native_src = nodes.flat_map(map_value(Node::native_src)).map(swap);
+            let native_call = nodes.flat_map(map_value(Node::native_call));
+
+            // join together native targets and evaluate their built-in relations
+            let native_args = native_src.join(&tuples).map(value).join(&native_call);
+
+            let native = native_args.flat_map(native);
+
+            // find the native evaluations too large to enumerate
+            let oversized = native_args.flat_map(oversized);
+
+            // extend operation
+            let extend_src = nodes.flat_map(map_value(Node::extend_src)).map(swap);
+            let extend_values = nodes.flat_map(map_value(Node::extend_values));
+
+            // join together extend targets and append their constants
+            let extend = extend_src
+                .join(&tuples)
+                .map(value)
+                .join(&extend_values)
+                .map(extend);
+
+            // unit operation
+            let unit = nodes.flat_map(map_value(Node::unit));
+
             // load relation operation
             let load_relation = nodes
                 .flat_map(map_value(Node::load_relation))
@@ -81,8 +175,18 @@ pub fn backend(worker: &mut Worker<Allocator>) {
                 .map(value);
 
             // combine all operations into new tuples
-            let new_tuples = join.concat(&project).concat(&load_relation).distinct();
-            tuples.set_concat(&new_tuples);
+            let new_tuples = join
+                .concat(&antijoin)
+                .concat(&project)
+                .concat(&filter)
+                .concat(&native)
+                .concat(&reduce)
+                .concat(&extend)
+                .concat(&unit)
+                .concat(&load_relation)
+                .distinct();
+
+            let tuples = tuples.set_concat(&new_tuples);
 
             // store new tuples to corresponding relations
             let stored = nodes
@@ -93,9 +197,145 @@ pub fn backend(worker: &mut Worker<Allocator>) {
                 .map(store)
                 .distinct();
 
-            relations.set_concat(&stored).leave()
+            (
+                relations.set_concat(&stored).leave(),
+                tuples.leave(),
+                oversized.leave(),
+            )
         });
+
+        // feed the completed stratum forward to the next
+        complete.set(&tuples);
+        (relations.leave(), oversized.leave())
     });
+
+    BackendOutputs {
+        relations: relations.distinct(),
+        oversized: oversized.distinct(),
+    }
+}
+
+pub fn backend_worker<A: Allocate>(
+    worker: &mut Worker<A>,
+) -> (BackendWorkerInput, BackendWorkerOutput) {
+    let mut input = BackendWorkerInput {
+        facts: InputSession::new(),
+        nodes: InputSession::new(),
+    };
+
+    let output = worker.dataflow(|scope| {
+        // the same fact or node may be lowered from more than one item
+        let inputs = BackendInputs {
+            facts: input.facts.to_collection(scope).distinct(),
+            nodes: input.nodes.to_collection(scope).distinct(),
+        };
+
+        let outputs = backend(inputs);
+
+        let relations = outputs.relations.arrange_by_self();
+        let oversized = outputs.oversized.arrange_by_self();
+
+        BackendWorkerOutput {
+            probes: vec![relations.stream.probe(), oversized.stream.probe()],
+            relations: Box::new(TraceMap::new(relations.trace)),
+            oversized: Box::new(TraceMap::new(oversized.trace)),
+        }
+    });
+
+    (input, output)
+}
+
+pub struct BackendWorkerInput {
+    pub facts: InputSession<(ResourceId, Tuple)>,
+    pub nodes: InputSession<Node>,
+}
+
+impl WorkerInput for BackendWorkerInput {
+    type Update = BackendUpdate;
+
+    fn advance_to(&mut self, time: Time) {
+        self.facts.advance_to(time);
+        self.nodes.advance_to(time);
+    }
+
+    fn on_update(&mut self, update: Self::Update) {
+        let diff = |add| if add { 1 } else { -1 };
+
+        use BackendUpdate::*;
+        match update {
+            Fact(relation, tuple, add) => self.facts.update((relation, tuple), diff(add)),
+            Node(node, add) => self.nodes.update(node, diff(add)),
+        }
+    }
+
+    fn flush(&mut self) {
+        self.facts.flush();
+        self.nodes.flush();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BackendUpdate {
+    Fact(ResourceId, Tuple, bool),
+    Node(Node, bool),
+}
+
+pub struct BackendWorkerOutput {
+    pub probes: Vec<ProbeHandle<Time>>,
+    pub relations: Box<dyn DynTraceMap<(ResourceId, Tuple), ()>>,
+    pub oversized: Box<dyn DynTraceMap<(Key<Node>, i64), ()>>,
+}
+
+impl WorkerOutput for BackendWorkerOutput {
+    type Result = BackendResult;
+
+    fn advance_to(&mut self, time: Time) {
+        self.relations.advance_to(time);
+        self.oversized.advance_to(time);
+    }
+
+    fn pending(&self, time: &Time) -> bool {
+        self.probes.iter().any(|probe| probe.less_than(time))
+    }
+
+    fn results(&mut self) -> Vec<Self::Result> {
+        self.relations.update();
+        self.oversized.update();
+
+        let relations = self
+            .relations
+            .distinct_keys()
+            .into_iter()
+            .map(|(relation, tuple)| BackendResult::Tuple(relation, tuple));
+
+        let oversized = self
+            .oversized
+            .distinct_keys()
+            .into_iter()
+            .map(|(node, len)| BackendResult::Oversized(node, len));
+
+        relations.chain(oversized).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BackendResult {
+    /// A tuple of an evaluated relation.
+    Tuple(ResourceId, Tuple),
+
+    /// A native node that was not evaluated because it would enumerate this
+    /// many tuples, beyond [Builtin::RANGE_LIMIT].
+    Oversized(Key<Node>, i64),
+}
+
+pub struct BackendInputs<G: Scope> {
+    pub facts: Collection<G, (ResourceId, Tuple)>,
+    pub nodes: Collection<G, Node>,
+}
+
+pub struct BackendOutputs<G: Scope> {
+    pub relations: Collection<G, (ResourceId, Tuple)>,
+    pub oversized: Collection<G, (Key<Node>, i64)>,
 }
 
 pub fn join_slice(((dst, num), vals): ((Key<Node>, usize), Tuple)) -> ((Key<Node>, Tuple), Tuple) {
@@ -117,10 +357,66 @@ pub fn join(
     (dst, result)
 }
 
+pub fn unslice(((dst, prefix), trail): ((Key<Node>, Tuple), Tuple)) -> (Key<Node>, Tuple) {
+    (dst, prefix.iter().chain(trail.iter()).cloned().collect())
+}
+
 pub fn project((dst, (src, map)): (Key<Node>, (Tuple, Arc<[usize]>))) -> (Key<Node>, Tuple) {
     (dst, map.iter().map(|idx| src[*idx].clone()).collect())
 }
 
+pub fn filter(
+    (dst, (src, conditions)): (Key<Node>, (Tuple, Arc<[Condition]>)),
+) -> Option<(Key<Node>, Tuple)> {
+    if conditions.iter().all(|condition| condition.test(&src)) {
+        Some((dst, src))
+    } else {
+        None
+    }
+}
+
+pub fn extend((dst, (src, values)): (Key<Node>, (Tuple, Tuple))) -> (Key<Node>, Tuple) {
+    (dst, src.iter().chain(values.iter()).cloned().collect())
+}
+
+/// Binds the arguments of a native node to the terms of a source tuple.
+fn native_bound(src: &Tuple, args: &[Argument]) -> Vec<Option<Value>> {
+    args.iter()
+        .map(|arg| match arg {
+            Argument::Term(idx) => Some(src[*idx].clone()),
+            Argument::Constant(val) => Some(val.clone()),
+            Argument::Free => None,
+        })
+        .collect()
+}
+
+pub fn native(
+    (dst, (src, (builtin, args))): (Key<Node>, (Tuple, (Builtin, Arc<[Argument]>))),
+) -> Vec<(Key<Node>, Tuple)> {
+    let bound = native_bound(&src, &args);
+
+    builtin
+        .eval(&bound)
+        .into_iter()
+        .map(|solution| {
+            let free = args
+                .iter()
+                .zip(solution)
+                .filter(|(arg, _val)| **arg == Argument::Free)
+                .map(|(_arg, val)| Value::Integer(val));
+
+            (dst, src.iter().cloned().chain(free).collect())
+        })
+        .collect()
+}
+
+pub fn oversized(
+    (dst, (src, (builtin, args))): (Key<Node>, (Tuple, (Builtin, Arc<[Argument]>))),
+) -> Option<(Key<Node>, i64)> {
+    let len = builtin.oversized(&native_bound(&src, &args))?;
+    Some((dst, len))
+}
+
 pub fn store(((dst, map), vals): ((ResourceId, IndexList), Tuple)) -> (ResourceId, Tuple) {
     let vals = map.iter().map(|idx| vals[*idx].clone()).collect();
     (dst, vals)
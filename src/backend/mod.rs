@@ -16,5 +16,6 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod builtins;
 pub mod dataflow;
 pub mod types;
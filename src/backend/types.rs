@@ -20,7 +20,7 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
-pub use crate::frontend::types::{ResourceId, Value};
+pub use crate::frontend::types::{Builtin, ResourceId, Value};
 use crate::utils::Key;
 
 pub type Tuple = Arc<[Value]>;
@@ -41,6 +41,21 @@ pub enum Node {
         num: usize,
     },
 
+    /// Removes the tuples of a node that match the tuples of another node.
+    ///
+    /// The right-hand node's contents are taken from the complete evaluation of
+    /// the previous stratum, so that negated relations are never read partially.
+    Antijoin {
+        /// The node to remove tuples from.
+        lhs: Key<Node>,
+
+        /// The node whose matching tuples are removed.
+        rhs: Key<Node>,
+
+        /// The number of terms starting from the front of each node to match.
+        num: usize,
+    },
+
     /// Projects (rearranges) a node's terms.
     ///
     /// Can also unload nodes.
@@ -52,6 +67,57 @@ pub enum Node {
         map: Arc<[usize]>,
     },
 
+    /// Filters a node's tuples by a list of conditions on their terms.
+    Filter {
+        /// The node to filter.
+        src: Key<Node>,
+
+        /// The conditions that every passing tuple must meet.
+        conditions: Arc<[Condition]>,
+    },
+
+    /// Evaluates a built-in relation against each tuple of a node.
+    ///
+    /// Each output tuple is the source tuple followed by the values of the free arguments.
+    Native {
+        /// The node to load bound terms from.
+        src: Key<Node>,
+
+        /// The built-in relation to evaluate.
+        builtin: Builtin,
+
+        /// The arguments to the built-in relation.
+        args: Arc<[Argument]>,
+    },
+
+    /// Aggregates the tuples of a node that share the same leading terms.
+    ///
+    /// Each output tuple is the shared terms followed by the aggregate. Like
+    /// [Node::Antijoin], the source node's contents are taken from the
+    /// complete evaluation of the previous stratum.
+    Reduce {
+        /// The node to aggregate.
+        src: Key<Node>,
+
+        /// The number of terms starting from the front of the node to group by.
+        num: usize,
+
+        /// The aggregate to compute over each group.
+        reducer: Reducer,
+    },
+
+    /// Appends constant values to each tuple of a node.
+    Extend {
+        /// The node to extend.
+        src: Key<Node>,
+
+        /// The values to append to every tuple.
+        values: Tuple,
+    },
+
+    /// Produces a single empty tuple.
+    Unit,
+
     /// Loads node contents from a relation.
     LoadRelation {
         /// The key of the relation (given by [ResourceId]) to load.
@@ -71,6 +137,75 @@ pub enum Node {
     },
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Condition {
+    /// The term at the given index equals a constant value.
+    Constant(usize, Value),
+
+    /// The terms at the given indices equal each other.
+    Equal(usize, usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Argument {
+    /// Bound to the term of the source tuple at the given index.
+    Term(usize),
+
+    /// Bound to a constant value.
+    Constant(Value),
+
+    /// Unbound, to be produced by the built-in relation.
+    Free,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Reducer {
+    /// The number of tuples in the group.
+    Count,
+
+    /// The sum of the integer terms at the given index, if it fits in an `i64`.
+    Sum(usize),
+
+    /// The least term at the given index.
+    Min(usize),
+
+    /// The greatest term at the given index.
+    Max(usize),
+}
+
+impl Reducer {
+    /// Aggregates a group of tuples, returning nothing for empty groups.
+    ///
+    /// Sums that overflow also return nothing, like the arithmetic built-ins,
+    /// so that their group is left out instead of wrapping around.
+    pub fn reduce<'a>(&self, tuples: impl Iterator<Item = &'a [Value]>) -> Option<Value> {
+        let mut tuples = tuples.peekable();
+        tuples.peek()?;
+
+        match self {
+            Reducer::Count => Some(Value::Integer(tuples.count() as i64)),
+            Reducer::Sum(idx) => tuples
+                .filter_map(|tuple| match tuple[*idx] {
+                    Value::Integer(val) => Some(val),
+                    _ => None,
+                })
+                .try_fold(0, i64::checked_add)
+                .map(Value::Integer),
+            Reducer::Min(idx) => tuples.map(|tuple| tuple[*idx].clone()).min(),
+            Reducer::Max(idx) => tuples.map(|tuple| tuple[*idx].clone()).max(),
+        }
+    }
+}
+
+impl Condition {
+    pub fn test(&self, tuple: &[Value]) -> bool {
+        match self {
+            Condition::Constant(idx, val) => tuple[*idx] == *val,
+            Condition::Equal(lhs, rhs) => tuple[*lhs] == tuple[*rhs],
+        }
+    }
+}
+
 impl Node {
     pub fn join_lhs(self) -> Option<(Key<Node>, usize)> {
         match self {
@@ -86,6 +221,20 @@ impl Node {
         }
     }
 
+    pub fn antijoin_lhs(self) -> Option<(Key<Node>, usize)> {
+        match self {
+            Node::Antijoin { lhs, num, .. } => Some((lhs, num)),
+            _ => None,
+        }
+    }
+
+    pub fn antijoin_rhs(self) -> Option<(Key<Node>, usize)> {
+        match self {
+            Node::Antijoin { rhs, num, .. } => Some((rhs, num)),
+            _ => None,
+        }
+    }
+
     pub fn project_src(self) -> Option<Key<Node>> {
         match self {
             Node::Project { src, .. } => Some(src),
@@ -100,6 +249,62 @@ impl Node {
         }
     }
 
+    pub fn filter_src(self) -> Option<Key<Node>> {
+        match self {
+            Node::Filter { src, .. } => Some(src),
+            _ => None,
+        }
+    }
+
+    pub fn filter_conditions(self) -> Option<Arc<[Condition]>> {
+        match self {
+            Node::Filter { conditions, .. } => Some(conditions),
+            _ => None,
+        }
+    }
+
+    pub fn native_src(self) -> Option<Key<Node>> {
+        match self {
+            Node::Native { src, .. } => Some(src),
+            _ => None,
+        }
+    }
+
+    pub fn native_call(self) -> Option<(Builtin, Arc<[Argument]>)> {
+        match self {
+            Node::Native { builtin, args, .. } => Some((builtin, args)),
+            _ => None,
+        }
+    }
+
+    pub fn reduce(self) -> Option<(Key<Node>, (usize, Reducer))> {
+        match self {
+            Node::Reduce { src, num, reducer } => Some((src, (num, reducer))),
+            _ => None,
+        }
+    }
+
+    pub fn extend_src(self) -> Option<Key<Node>> {
+        match self {
+            Node::Extend { src, .. } => Some(src),
+            _ => None,
+        }
+    }
+
+    pub fn extend_values(self) -> Option<Tuple> {
+        match self {
+            Node::Extend { values, .. } => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn unit(self) -> Option<Tuple> {
+        match self {
+            Node::Unit => Some(Tuple::from([])),
+            _ => None,
+        }
+    }
+
     pub fn load_relation(self) -> Option<ResourceId> {
         match self {
             Node::LoadRelation { resource } => Some(resource),
@@ -0,0 +1,133 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! A stand-in for an external solver, built on the native solver.
+//!
+//! Reads a DIMACS or weighted DIMACS CNF problem from standard input and
+//! answers on standard output in the format of the SAT and MaxSAT
+//! competitions, so that the subprocess backend can be exercised without
+//! installing anything else.
+
+use std::io::Read;
+
+use archimedes::solver::{cdcl::Solver, maxsat, types::Lit};
+use clap::Parser;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Args {
+    /// Print the model as a string of digits instead of signed literals.
+    #[arg(long)]
+    model_string: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .expect("failed to read problem");
+
+    let mut solver = Solver::new();
+    let mut objective = Vec::new();
+    let mut is_weighted = false;
+    let mut top = None;
+
+    for line in input.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.clone().next() {
+            None | Some("c") => continue,
+            Some("p") => {
+                let header: Vec<_> = tokens.collect();
+                is_weighted = header[1] == "wcnf";
+                solver.reserve_vars(header[2].parse().expect("invalid variable count"));
+                top = header
+                    .get(4)
+                    .map(|top| top.parse().expect("invalid top weight"));
+                continue;
+            }
+            _ => {}
+        }
+
+        let weight: Option<u64> = is_weighted.then(|| {
+            let weight = tokens.next().unwrap();
+            weight.parse().expect("invalid clause weight")
+        });
+
+        let clause: Vec<_> = tokens
+            .map(|lit| lit.parse().expect("invalid literal"))
+            .filter_map(Lit::from_dimacs)
+            .collect();
+
+        match weight {
+            Some(weight) if Some(weight) != top => {
+                // penalize the soft clause being false with a fresh literal
+                let violated = Lit::positive(solver.num_vars());
+                solver.reserve_vars(solver.num_vars() + 1);
+                let mut clause = clause;
+                clause.push(violated);
+                solver.add_clause(&clause);
+                objective.push((violated, weight));
+            }
+            _ => solver.add_clause(&clause),
+        }
+    }
+
+    // only report the variables of the problem itself
+    let num_vars = solver.num_vars() - objective.len();
+
+    if maxsat::total(&objective).is_none() {
+        eprintln!("soft clause weights are too large to optimize");
+        std::process::exit(1);
+    }
+
+    let Some(optimum) = maxsat::minimize(&mut solver, &objective) else {
+        println!("s UNSATISFIABLE");
+        std::process::exit(20);
+    };
+
+    if is_weighted {
+        println!("o {}", optimum.cost);
+        println!("s OPTIMUM FOUND");
+    } else {
+        println!("s SATISFIABLE");
+    }
+
+    let model = &optimum.model[..num_vars];
+    if args.model_string {
+        let digits: String = model
+            .iter()
+            .map(|val| if *val { '1' } else { '0' })
+            .collect();
+        println!("v {digits}");
+    } else {
+        let lits: Vec<_> = model
+            .iter()
+            .enumerate()
+            .map(|(var, val)| Lit::new(var, *val).to_dimacs().to_string())
+            .collect();
+
+        for chunk in lits.chunks(10) {
+            println!("v {}", chunk.join(" "));
+        }
+
+        println!("v 0");
+    }
+
+    std::process::exit(if is_weighted { 30 } else { 10 });
+}
@@ -21,7 +21,7 @@ use std::{collections::BTreeMap, sync::Arc};
 use differential_dataflow::{
     input::Input,
     lattice::Lattice,
-    operators::{arrange::ArrangeBySelf, iterate::Variable, Join, Reduce, Threshold},
+    operators::{arrange::ArrangeBySelf, iterate::Variable, Iterate, Join, Reduce, Threshold},
     Collection, Hashable,
 };
 use timely::{
@@ -33,6 +33,7 @@ use timely::{
 use url::Url;
 
 use crate::{
+    backend::types::{Node, Tuple},
     frontend::{
         span::{MapSpan, Point, Span, Spanned},
         types::*,
@@ -47,7 +48,6 @@ where
     G::Timestamp: Lattice,
 {
     let mut scope = inputs.items.scope();
-    let hover = scope.new_collection().1;
 
     // index all items: convert string variable names to indices
     let indexed_items = inputs.items.map(ModuleItem::index_variables);
@@ -57,22 +57,132 @@ where
     // unspan items: decouple the contents of each (sub-)item from its physical span
     let unspanned = indexed_items.map(unspan);
     let span_keys = unspanned.flat_map(value);
-    let items = unspanned.map(key);
+    let unresolved = unspanned.map(key);
+
+    // resolve the targets of all imported relations relative to the project root
+    let imports = unresolved
+        .filter(|item| matches!(item.inner, ModuleItem::Import(_)))
+        .map(|item| ((), item))
+        .join(&inputs.roots.map(|root| ((), root)))
+        .flat_map(|((), (item, root))| item.imports(&root));
+
+    let imported = imports.flat_map(Result::ok);
+
+    // relations imported from other files must be defined by the head of a rule there
+    let modules = unresolved.map(|item| item.url).distinct();
+    let defined = unresolved
+        .flat_map(|item| item.rule_or_decision())
+        .map(|rule| rule.head.relation.inner.clone())
+        .distinct();
+
+    let from_files = imported
+        .filter(|import| import.file.is_some())
+        .map(|import| (import.target.inner.clone(), import));
+
+    let missing = from_files.antijoin(&defined).map(|(target, import)| {
+        let url = target.url().expect("module files are source URLs").clone();
+        (url, import)
+    });
+
+    let import_diagnostics = imports
+        .flat_map(Result::err)
+        .concat(
+            &missing
+                .semijoin(&modules)
+                .map(|(_, i)| i.missing_diagnostic(true)),
+        )
+        .concat(
+            &missing
+                .antijoin(&modules)
+                .map(|(_, i)| i.missing_diagnostic(false)),
+        );
+
+    let aliases = imported
+        .filter(|import| import.file.is_none())
+        .concat(&from_files.semijoin(&defined).map(value))
+        .map(|import| (import.local, import.target));
+
+    // replace imported relations within items with their targets
+    let items = resolve_aliases(
+        &unresolved,
+        &aliases.map(|(local, target)| (local, target.inner)),
+    );
 
     // create unique identifiers for each item
     let item_keys = items.map(Key::pair);
 
+    // find the named type of each symbol declared as one of its variants
+    let variant_types = items
+        .flat_map(IndexedItem::variants)
+        .reduce(resolve_variant_types);
+    let variant_diagnostics = variant_types.flat_map(|(_symbol, result)| result.err());
+    let variants = variant_types
+        .flat_map(|(symbol, result)| result.ok().map(|ty| ((), (symbol, ty))))
+        .reduce(|_key, input, output| {
+            let variants: Variants = input.iter().map(|(el, _diff)| (*el).clone()).collect();
+            output.push((variants, 1));
+        });
+
+    // pair each item with the variants that type its symbols
+    let typed_items = item_keys
+        .map(|item| ((), item))
+        .join(&variants)
+        .map(value)
+        .concat(
+            &item_keys
+                .map(|item| ((), item))
+                .antijoin(&variants.map(key))
+                .map(|((), item)| (item, Variants::new())),
+        );
+
     // find all of the typing combinators from the given items
-    let base_types = items.flat_map(IndexedItem::base_type);
-    let head_types = item_keys.flat_map(map_value(IndexedItem::head_type));
-    let body_types = item_keys.flat_map(IndexedItem::body_types);
+    let base_types = typed_items
+        .flat_map(|((_key, item), variants)| item.base_type(&variants))
+        .concat(&items.flat_map(|item| {
+            // built-in relations are typed wherever they are imported or used
+            item.relation_names()
+                .into_iter()
+                .filter_map(|relation| match relation.inner {
+                    ResourceId::Builtin(builtin) => {
+                        Some((relation.inner, builtin.ty(relation.span)))
+                    }
+                    _ => None,
+                })
+        }));
+    let head_types = typed_items
+        .flat_map(|((key, item), variants)| item.head_type(&variants).map(|ty| (key, ty)));
+    let body_types =
+        typed_items.flat_map(|(item, variants)| IndexedItem::body_types(item, &variants));
+    let aggregate_types = item_keys.flat_map(IndexedItem::aggregate_types);
+    let aggregate_aliases = item_keys.flat_map(IndexedItem::aggregate_aliases);
+
+    // declared relation types take precedence over any that are inferred
+    let declared_types = items
+        .flat_map(IndexedItem::declared_type)
+        .reduce(resolve_proposed_types)
+        .map(value);
+    let declared = declared_types.flat_map(Result::ok);
+
+    // every named type in a declaration must be declared by an enum
+    let enum_names = items.flat_map(|item| item.enum_name()).distinct();
+    let unknown_names = items
+        .flat_map(|item| item.type_names())
+        .map(|name| (name.inner.clone(), name))
+        .antijoin(&enum_names)
+        .map(|(_name, name)| unknown_type_name(name));
+
+    let declaration_diagnostics = declared_types
+        .flat_map(Result::err)
+        .concat(&unknown_names)
+        .concat(&variant_diagnostics);
 
     // iteratively derive the types of all relations using bottom-up fixed-point evaluation
     let (relation_types, type_diagnostics, item_types) = scope.iterative::<u16, _, _>(|scope| {
         // init loop variables
         let step = Product::new(Default::default(), 1);
         let proposed_types = Variable::new_from(base_types.enter(scope), step.clone());
-        let item_types = Variable::new(scope, step.clone());
+        let item_types: Variable<_, (ItemKey, BTreeMap<usize, Type<SpanKey>>), isize> =
+            Variable::new(scope, step.clone());
 
         let resolved_types: Variable<_, (ResourceId, Type<SpanKey>), isize> =
             Variable::new(scope, step.clone());
@@ -90,8 +200,21 @@ where
                 .flat_map(|((key, src), dst)| src.unify(key, dst)),
         );
 
+        // type aggregate results by their kind or by their target's type
+        let new_aggregates = item_types
+            .join(&aggregate_aliases.enter(scope))
+            .flat_map(|(key, (vars, (result, target)))| {
+                vars.get(&target).map(|ty| (key, (result, ty.clone())))
+            })
+            .concat(&aggregate_types.enter(scope));
+
         // reduce the variable types of each item into a single map
-        let var_maps = new_diagnostics.with_errs(&new_body.reduce(merge_var_types).map(value));
+        let var_maps = new_diagnostics.with_errs(
+            &new_body
+                .concat(&new_aggregates)
+                .reduce(merge_var_types)
+                .map(value),
+        );
 
         // infer types of items from complete variable maps
         let resolved_body = var_maps
@@ -103,13 +226,22 @@ where
             })
             .distinct();
 
-        // resolve proposed types for each relation
-        let new_resolved = new_diagnostics.with_errs(
-            &proposed_types
-                .reduce(resolve_proposed_types)
-                .map(value)
-                .consolidate(),
-        );
+        // check the proposed types of declared relations against their declarations
+        let declared = declared.enter(scope);
+        new_diagnostics.push(&proposed_types.join(&declared).flat_map(
+            |(_relation, (proposed, declared))| check_declared_type(&declared, &proposed),
+        ));
+
+        // resolve proposed types for each relation without a declaration
+        let new_resolved = new_diagnostics
+            .with_errs(
+                &proposed_types
+                    .antijoin(&declared.map(key))
+                    .reduce(resolve_proposed_types)
+                    .map(value)
+                    .consolidate(),
+            )
+            .concat(&declared);
 
         // feed forward all new results to next iteration
         let resolved = resolved_types.set_concat(&new_resolved);
@@ -144,9 +276,127 @@ where
         .map(|(_key, (h, (url, span)))| (url, h.with_span(span)))
         .distinct();
 
+    // show the type of each relation when hovering over its name
+    let hover = item_keys
+        .flat_map(|(_key, item)| item.relation_names())
+        .map(|name| (name.inner, name.span))
+        .join(&relation_types)
+        .map(|(relation, (span, ty))| {
+            let decl = TypeDeclaration {
+                relation: Spanned {
+                    span,
+                    inner: relation,
+                },
+                ty: ty.map_leaves(&mut |_span, prim| prim.to_string()),
+            };
+
+            (span, decl.to_string())
+        })
+        .join(&span_keys)
+        .map(|(_key, (contents, (url, span)))| (url, (span.start, (span.end, contents))))
+        .distinct();
+
+    // find the relations that each item's head depends on
+    let dependencies = item_keys.flat_map(|(key, item)| {
+        item.dependencies()
+            .into_iter()
+            .map(move |dependency| (key, dependency))
+    });
+
+    let edges = dependencies
+        .map(|(_key, (head, body, _stratified))| (head, body))
+        .distinct();
+
+    // find every relation that each relation transitively depends on
+    let reachable = edges.iterate(|reachable| {
+        let edges = edges.enter(&reachable.scope());
+        reachable
+            .map(swap)
+            .join(&edges)
+            .map(value)
+            .concat(&edges)
+            .distinct()
+    });
+
+    // find negations and aggregates of relations that depend on their head
+    let unstratified = dependencies
+        .flat_map(|(key, (head, body, stratified))| {
+            stratified.map(|span| ((body, head), (key, span)))
+        })
+        .semijoin(&reachable)
+        .map(|((body, head), (key, span))| {
+            let label = if body == head {
+                format!("{head} cannot negate or aggregate itself.")
+            } else {
+                format!("{head} cannot negate or aggregate {body}, which depends on {head}.")
+            };
+
+            let d = Diagnostic {
+                span,
+                kind: DiagnosticKind::Error,
+                message: format!("Dependency on {body} is not stratified"),
+                labels: vec![Spanned { span, inner: label }],
+            };
+
+            (key, d)
+        });
+
+    // find items with body patterns that do not fit their relations' types,
+    // such as records that are missing fields, which would lower positionally
+    let mismatched = body_types
+        .join(&relation_types)
+        .map(value)
+        .filter(|((key, src), dst)| {
+            let unified = src.clone().unify(*key, dst.clone());
+            unified.iter().any(Result::is_err)
+        })
+        .map(|((key, _src), _dst)| key);
+
+    // lower all stratified and well-typed rules into backend program nodes
+    let lowered = item_keys
+        .antijoin(&unstratified.map(key).concat(&mismatched).distinct())
+        .map(value)
+        .map(|item| (item.url.clone(), item.lower_nodes()));
+
+    let lower_diagnostics = lowered.flat_map(|(_url, result)| result.err());
+
+    let nodes = lowered
+        .flat_map(|(url, result)| {
+            result
+                .into_iter()
+                .flat_map(|(nodes, _ranges)| nodes)
+                .map(move |node| (url.clone(), node))
+        })
+        .distinct();
+
+    // locate the ranges that can only be bounded during evaluation
+    let ranges = lowered
+        .flat_map(|(_url, result)| result.into_iter().flat_map(|(_nodes, ranges)| ranges))
+        .map(swap)
+        .join(&span_keys)
+        .map(|(_key, (node, (url, span)))| (url, (node, span)))
+        .distinct();
+
+    // lower all ground facts into initial relation contents
+    let facts = items
+        .flat_map(|item| {
+            let url = item.url.clone();
+            item.lower_facts()
+                .into_iter()
+                .map(move |fact| (url.clone(), fact))
+        })
+        .distinct();
+
+    // restore the physical spans of every resolved item
+    let resolved = respan_items(&item_keys, &span_keys);
+
     // combine all diagnostics
     let diagnostics = type_diagnostics
+        .concat(&declaration_diagnostics)
         .concat(&unknown_types)
+        .concat(&unstratified.map(value))
+        .concat(&import_diagnostics)
+        .concat(&lower_diagnostics)
         .flat_map(|d| d.clone().span_set().map(move |span| (span, d.clone())))
         .join(&span_keys)
         .map(|(key, (d, span))| (d, (key, span)))
@@ -160,6 +410,10 @@ where
         diagnostics,
         hover,
         inlay_hints,
+        items: resolved,
+        nodes,
+        ranges,
+        facts,
     }
 }
 
@@ -168,11 +422,13 @@ pub fn frontend_worker<A: Allocate>(
 ) -> (FrontendWorkerInput, FrontendWorkerOutput) {
     let mut input = FrontendWorkerInput {
         items: InputSession::new(),
+        roots: InputSession::new(),
     };
 
     let output = worker.dataflow(|scope| {
         let inputs = FrontendInputs {
             items: input.items.to_collection(scope),
+            roots: input.roots.to_collection(scope),
         };
 
         let outputs = frontend(inputs);
@@ -180,16 +436,28 @@ pub fn frontend_worker<A: Allocate>(
         let diagnostics = outputs.diagnostics.arrange_by_self();
         let hover = outputs.hover.arrange_by_self();
         let inlay_hints = outputs.inlay_hints.arrange_by_self();
+        let items = outputs.items.arrange_by_self();
+        let nodes = outputs.nodes.arrange_by_self();
+        let ranges = outputs.ranges.arrange_by_self();
+        let facts = outputs.facts.arrange_by_self();
 
         FrontendWorkerOutput {
             probes: vec![
                 diagnostics.stream.probe(),
                 hover.stream.probe(),
                 inlay_hints.stream.probe(),
+                items.stream.probe(),
+                nodes.stream.probe(),
+                ranges.stream.probe(),
+                facts.stream.probe(),
             ],
             diagnostics: Box::new(TraceMap::new(diagnostics.trace)),
             hover: Box::new(TraceMap::new(hover.trace)),
             inlay_hints: Box::new(TraceMap::new(inlay_hints.trace)),
+            items: Box::new(TraceMap::new(items.trace)),
+            nodes: Box::new(TraceMap::new(nodes.trace)),
+            ranges: Box::new(TraceMap::new(ranges.trace)),
+            facts: Box::new(TraceMap::new(facts.trace)),
         }
     });
 
@@ -198,6 +466,7 @@ pub fn frontend_worker<A: Allocate>(
 
 pub struct FrontendWorkerInput {
     pub items: InputSession<(Url, ModuleItem<(Url, Span), String, String>)>,
+    pub roots: InputSession<Url>,
 }
 
 impl WorkerInput for FrontendWorkerInput {
@@ -205,6 +474,7 @@ impl WorkerInput for FrontendWorkerInput {
 
     fn advance_to(&mut self, time: Time) {
         self.items.advance_to(time);
+        self.roots.advance_to(time);
     }
 
     fn on_update(&mut self, update: Self::Update) {
@@ -212,18 +482,23 @@ impl WorkerInput for FrontendWorkerInput {
 
         use FrontendUpdate::*;
         match update {
-            Item(url, el, add) => self.items.update((url, el), diff(add)),
+            Item(url, el, add) => self.items.update((url, *el), diff(add)),
+            Root(url, add) => self.roots.update(url, diff(add)),
         }
     }
 
     fn flush(&mut self) {
         self.items.flush();
+        self.roots.flush();
     }
 }
 
 #[derive(Clone, Debug)]
 pub enum FrontendUpdate {
-    Item(Url, ModuleItem<(Url, Span), String, String>, bool),
+    Item(Url, Box<ModuleItem<(Url, Span), String, String>>, bool),
+
+    /// The directory that modules are imported relative to.
+    Root(Url, bool),
 }
 
 pub struct FrontendWorkerOutput {
@@ -231,6 +506,10 @@ pub struct FrontendWorkerOutput {
     pub diagnostics: Box<dyn DynTraceMap<Diagnostic<(Url, Span)>, ()>>,
     pub inlay_hints: Box<dyn DynTraceMap<(Url, InlayHint<Span>), ()>>,
     pub hover: Box<dyn DynTraceMap<(Url, (Point, (Point, String))), ()>>,
+    pub items: Box<dyn DynTraceMap<IndexedItem<(Url, Span), ResourceId>, ()>>,
+    pub nodes: Box<dyn DynTraceMap<(Url, Node), ()>>,
+    pub ranges: Box<dyn DynTraceMap<(Url, (Key<Node>, Span)), ()>>,
+    pub facts: Box<dyn DynTraceMap<(Url, (ResourceId, Tuple)), ()>>,
 }
 
 impl WorkerOutput for FrontendWorkerOutput {
@@ -240,6 +519,10 @@ impl WorkerOutput for FrontendWorkerOutput {
         self.diagnostics.advance_to(time);
         self.hover.advance_to(time);
         self.inlay_hints.advance_to(time);
+        self.items.advance_to(time);
+        self.nodes.advance_to(time);
+        self.ranges.advance_to(time);
+        self.facts.advance_to(time);
     }
 
     fn pending(&self, time: &Time) -> bool {
@@ -250,6 +533,10 @@ impl WorkerOutput for FrontendWorkerOutput {
         self.diagnostics.update();
         self.hover.update();
         self.inlay_hints.update();
+        self.items.update();
+        self.nodes.update();
+        self.ranges.update();
+        self.facts.update();
 
         let diagnostics = self
             .diagnostics
@@ -269,7 +556,38 @@ impl WorkerOutput for FrontendWorkerOutput {
             .into_iter()
             .map(|(url, hint)| (url, FrontendResultKind::InlayHint(hint)));
 
-        diagnostics.chain(hover).chain(inlay_hints).collect()
+        let items = self
+            .items
+            .distinct_keys()
+            .into_iter()
+            .map(|item| (item.url.clone(), FrontendResultKind::Item(Box::new(item))));
+
+        let nodes = self
+            .nodes
+            .distinct_keys()
+            .into_iter()
+            .map(|(url, node)| (url, FrontendResultKind::Node(node)));
+
+        let ranges = self
+            .ranges
+            .distinct_keys()
+            .into_iter()
+            .map(|(url, (node, span))| (url, FrontendResultKind::Range(node, span)));
+
+        let facts = self
+            .facts
+            .distinct_keys()
+            .into_iter()
+            .map(|(url, (relation, tuple))| (url, FrontendResultKind::Fact(relation, tuple)));
+
+        diagnostics
+            .chain(hover)
+            .chain(inlay_hints)
+            .chain(items)
+            .chain(nodes)
+            .chain(ranges)
+            .chain(facts)
+            .collect()
     }
 }
 
@@ -280,15 +598,22 @@ pub enum FrontendResultKind {
     Diagnostic(Diagnostic<(Url, Span)>),
     InlayHint(InlayHint<Span>),
     Hover((Point, (Point, String))),
+    Item(Box<IndexedItem<(Url, Span), ResourceId>>),
+    Node(Node),
+    Range(Key<Node>, Span),
+    Fact(ResourceId, Tuple),
 }
 
 #[derive(Clone)]
 pub struct FrontendInputs<G: Scope> {
     pub items: Collection<G, (Url, ModuleItem<(Url, Span), String, String>)>,
+    pub roots: Collection<G, Url>,
 }
 
 pub type SpanKey = (u64, usize);
 
+pub type ItemKey = Key<IndexedItem<SpanKey, ResourceId>>;
+
 pub fn unspan(
     item: IndexedItem<(Url, Span), String>,
 ) -> (
@@ -297,7 +622,7 @@ pub fn unspan(
 ) {
     let mut spans = Vec::new();
 
-    // TODO: technically this may erase URLs of relations...
+    // relations belong to the item's own file until its imports are resolved
     let url = Arc::new(item.url.clone());
 
     let unspanned = item.map_span(&mut |span| {
@@ -316,11 +641,40 @@ pub fn unspan(
 
     let respanned = unspanned
         .map_span(&mut |span| (key, span))
-        .map_relations(&mut |_span, sym| ResourceId::SourceSymbol(url.clone(), sym));
+        .map_relations(&mut |_span, sym| ResourceId::new(url.clone(), sym));
 
     (respanned, span_map)
 }
 
+pub fn resolve_aliases<G: Scope>(
+    items: &Collection<G, IndexedItem<SpanKey, ResourceId>>,
+    aliases: &Collection<G, (ResourceId, ResourceId)>,
+) -> Collection<G, IndexedItem<SpanKey, ResourceId>>
+where
+    G::Timestamp: Lattice,
+{
+    let items = items.map(Key::pair);
+
+    // find the aliased relations referenced by each item
+    let alias_maps = items
+        .flat_map(|(key, item)| {
+            item.relations()
+                .into_iter()
+                .map(move |relation| (relation, key))
+        })
+        .join(aliases)
+        .map(|(local, (key, target))| (key, (local, target)))
+        .reduce(reduce_map);
+
+    let unaliased = items.antijoin(&alias_maps.map(key)).map(value);
+
+    let aliased = items.join(&alias_maps).map(|(_key, (item, map))| {
+        item.map_relations(&mut |_span, relation| map.get(&relation).cloned().unwrap_or(relation))
+    });
+
+    unaliased.concat(&aliased)
+}
+
 pub fn resolve_proposed_types(
     key: &ResourceId,
     input: &[(&Type<SpanKey>, isize)],
@@ -361,6 +715,77 @@ pub fn resolve_proposed_types(
     }
 }
 
+/// Picks the type of a symbol declared as a variant, reporting any other type
+/// that it is also declared as a variant of.
+pub fn resolve_variant_types(
+    symbol: &String,
+    input: &[(&Spanned<SpanKey, String>, isize)],
+    output: &mut Vec<(Result<String, Diagnostic<SpanKey>>, isize)>,
+) {
+    let (first, _diff) = input[0];
+    output.push((Ok(first.inner.clone()), 1));
+
+    for (other, _diff) in input[1..].iter() {
+        if other.inner == first.inner {
+            continue;
+        }
+
+        let d = Diagnostic {
+            span: other.span,
+            kind: DiagnosticKind::Error,
+            message: format!(
+                "{symbol} is a variant of both {} and {}",
+                first.inner, other.inner
+            ),
+            labels: vec![
+                first.clone().map(|ty| format!("Declared as {ty} here...")),
+                (*other).clone().map(|ty| format!("...and as {ty} here.")),
+            ],
+        };
+
+        output.push((Err(d), 1));
+    }
+}
+
+/// Reports a type name that no enum declares.
+pub fn unknown_type_name(name: Spanned<SpanKey, String>) -> Diagnostic<SpanKey> {
+    Diagnostic {
+        span: name.span,
+        kind: DiagnosticKind::Error,
+        message: format!("Unknown type {}", name.inner),
+        labels: vec![Spanned {
+            span: name.span,
+            inner: "Expected Symbol, Integer, or a declared enum.".to_string(),
+        }],
+    }
+}
+
+/// Reports a proposed type of a relation that differs from its declaration.
+pub fn check_declared_type(
+    declared: &Type<SpanKey>,
+    proposed: &Type<SpanKey>,
+) -> Option<Diagnostic<SpanKey>> {
+    let lhs = declared.clone().map_span(&mut |_| ());
+    let rhs = proposed.clone().map_span(&mut |_| ());
+    if lhs == rhs {
+        return None;
+    }
+
+    Some(Diagnostic {
+        span: proposed.span,
+        kind: DiagnosticKind::Error,
+        message: format!("Expected {lhs}, got {rhs}"),
+        labels: vec![
+            declared
+                .clone()
+                .map(|declared| format!("Declared as {declared} here...")),
+            proposed
+                .clone()
+                .map(|proposed| format!("...but {proposed} was inferred here.")),
+        ],
+    })
+}
+
 pub fn merge_var_types(
     key: &Key<IndexedItem<(u64, usize), ResourceId>>,
     input: &[(&(Spanned<SpanKey, usize>, Type<SpanKey>), isize)],
@@ -410,6 +835,29 @@ pub fn merge_var_types(
     }
 }
 
+/// Restores the physical spans of items from the span keys that replaced them.
+pub fn respan_items<G: Scope>(
+    items: &Collection<G, (ItemKey, IndexedItem<SpanKey, ResourceId>)>,
+    span_keys: &Collection<G, (SpanKey, (Url, Span))>,
+) -> Collection<G, IndexedItem<(Url, Span), ResourceId>>
+where
+    G::Timestamp: Lattice,
+{
+    let span_maps = items
+        .flat_map(|(key, item)| {
+            let mut spans = Vec::new();
+            item.map_span(&mut |span| spans.push(span));
+            spans.into_iter().map(move |span| (span, key))
+        })
+        .join(span_keys)
+        .map(|(span_key, (key, span))| (key, (span_key, span)))
+        .reduce(reduce_map);
+
+    items
+        .join(&span_maps)
+        .map(|(_key, (item, span_map))| item.map_multi_span(&span_map))
+}
+
 pub fn reduce_map<K, S, O>(
     _key: &K,
     input: &[(&(S, O), isize)],
@@ -430,4 +878,8 @@ pub struct FrontendOutputs<G: Scope> {
     pub diagnostics: Collection<G, Diagnostic<(Url, Span)>>,
     pub hover: Collection<G, (Url, (Point, (Point, String)))>,
     pub inlay_hints: Collection<G, (Url, InlayHint<Span>)>,
+    pub items: Collection<G, IndexedItem<(Url, Span), ResourceId>>,
+    pub nodes: Collection<G, (Url, Node)>,
+    pub ranges: Collection<G, (Url, (Key<Node>, Span))>,
+    pub facts: Collection<G, (Url, (ResourceId, Tuple))>,
 }
@@ -0,0 +1,219 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Rewrites arithmetic expressions into atoms of their built-in relations.
+
+use std::mem;
+
+use crate::frontend::{span::Spanned, types::*};
+
+type Item<S> = ModuleItem<S, String, String>;
+type SpannedLiteral<S> = Spanned<S, Literal<S, String, Term<String>>>;
+
+/// Replaces each expression within an item with a variable named after it,
+/// which is bound by a new body atom of the expression's operator.
+///
+/// `Foo(x + 1) if Bar(x).` becomes `Foo(v) if Bar(x), +(x, 1, v).`, where
+/// `+` resolves to the built-in `Add` relation. Each variable is named by
+/// `text` after its expression's span in the source, so that diagnostics
+/// about it quote the expression as it was written.
+pub fn desugar<S: Clone>(item: Item<S>, text: &impl Fn(&S) -> String) -> Vec<Item<S>> {
+    let mut desugarer = Desugarer {
+        atoms: Vec::new(),
+        diagnostics: Vec::new(),
+        text,
+    };
+
+    let item = match item {
+        ModuleItem::Rule(rule) => ModuleItem::Rule(desugarer.rule(rule)),
+        ModuleItem::Decision(Decision(rule)) => {
+            ModuleItem::Decision(Decision(desugarer.rule(rule)))
+        }
+        ModuleItem::Constraint(mut constraint) => {
+            constraint.body = desugarer.body(constraint.body);
+            ModuleItem::Constraint(constraint)
+        }
+        ModuleItem::Objective(mut objective) => {
+            objective.body = desugarer.body(objective.body);
+            ModuleItem::Objective(objective)
+        }
+        ModuleItem::Type(decl) => match find_expression(&decl.ty) {
+            Some(span) => {
+                return vec![ModuleItem::Diagnostic(Diagnostic {
+                    span: span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: "Expected a type, got an expression".to_string(),
+                    labels: vec![Spanned {
+                        span,
+                        inner: "Expected Symbol, Integer, or a declared enum.".to_string(),
+                    }],
+                })]
+            }
+            None => ModuleItem::Type(decl),
+        },
+        item => item,
+    };
+
+    let mut items = vec![item];
+    items.extend(
+        desugarer
+            .diagnostics
+            .into_iter()
+            .map(ModuleItem::Diagnostic),
+    );
+    items
+}
+
+struct Desugarer<'a, S, F> {
+    /// The atoms that bind the expressions found so far.
+    atoms: Vec<SpannedLiteral<S>>,
+    diagnostics: Vec<Diagnostic<S>>,
+
+    /// Looks up the source text of a span.
+    text: &'a F,
+}
+
+impl<S: Clone, F: Fn(&S) -> String> Desugarer<'_, S, F> {
+    fn rule(&mut self, rule: Rule<S, String, String>) -> Rule<S, String, String> {
+        let mut body = self.body(rule.body);
+        let head = rule.head.map(|head| self.atom(head));
+        body.append(&mut self.atoms);
+        Rule { head, body }
+    }
+
+    fn body(&mut self, body: Vec<SpannedLiteral<S>>) -> Vec<SpannedLiteral<S>> {
+        let mut body: Vec<_> = body
+            .into_iter()
+            .map(|literal| literal.map(|literal| self.literal(literal)))
+            .collect();
+
+        body.append(&mut self.atoms);
+        body
+    }
+
+    fn literal(
+        &mut self,
+        literal: Literal<S, String, Term<String>>,
+    ) -> Literal<S, String, Term<String>> {
+        match literal {
+            Literal::Atom(atom) => Literal::Atom(self.atom(atom)),
+            Literal::Negated(atom) => Literal::Negated(self.atom(atom)),
+            Literal::Aggregate(mut aggregate) => {
+                // the operands of an aggregated expression would be grouped by
+                // instead of aggregated over, so expressions are rejected
+                if let Some(span) = find_expression(&aggregate.atom.pattern) {
+                    self.diagnostics.push(Diagnostic {
+                        span: span.clone(),
+                        kind: DiagnosticKind::Error,
+                        message: "Expressions are not allowed within aggregates".to_string(),
+                        labels: vec![Spanned {
+                            span,
+                            inner: "Aggregated atoms may only contain variables and values."
+                                .to_string(),
+                        }],
+                    });
+
+                    let atoms = mem::take(&mut self.atoms);
+                    aggregate.atom = self.atom(aggregate.atom);
+                    self.atoms = atoms;
+                }
+
+                Literal::Aggregate(aggregate)
+            }
+        }
+    }
+
+    fn atom(&mut self, atom: Atom<S, String, Term<String>>) -> Atom<S, String, Term<String>> {
+        Atom {
+            relation: atom.relation,
+            pattern: self.pattern(atom.pattern),
+        }
+    }
+
+    fn pattern(
+        &mut self,
+        pattern: SpannedPattern<S, Term<String>>,
+    ) -> SpannedPattern<S, Term<String>> {
+        let span = pattern.span;
+        let inner = match pattern.inner {
+            Pattern::Leaf(leaf) => Pattern::Leaf(leaf),
+            Pattern::Tuple(els) => {
+                Pattern::Tuple(els.into_iter().map(|el| self.pattern(el)).collect())
+            }
+            Pattern::Record(fields) => Pattern::Record(
+                fields
+                    .into_iter()
+                    .map(|Field { key, value }| Field {
+                        key,
+                        value: self.pattern(value),
+                    })
+                    .collect(),
+            ),
+            Pattern::Expression(expr) => {
+                let result = Pattern::Leaf(Term::Variable((self.text)(&span)));
+                let lhs = self.operand(expr.lhs);
+                let rhs = self.operand(expr.rhs);
+                let result = Spanned {
+                    span: span.clone(),
+                    inner: result,
+                };
+
+                self.atoms.push(Spanned {
+                    span: span.clone(),
+                    inner: Literal::Atom(Atom {
+                        relation: expr.op.map(|op| op.to_string()),
+                        pattern: Spanned {
+                            span: span.clone(),
+                            inner: Pattern::Tuple(vec![lhs, rhs, result.clone()]),
+                        },
+                    }),
+                });
+
+                result.inner
+            }
+        };
+
+        Spanned { span, inner }
+    }
+
+    /// Parenthesized operands only group their expressions.
+    fn operand(
+        &mut self,
+        operand: SpannedPattern<S, Term<String>>,
+    ) -> SpannedPattern<S, Term<String>> {
+        match operand.inner {
+            Pattern::Tuple(mut els) if els.len() == 1 => self.operand(els.pop().unwrap()),
+            inner => self.pattern(Spanned {
+                span: operand.span,
+                inner,
+            }),
+        }
+    }
+}
+
+/// Finds the span of the first expression within a pattern.
+fn find_expression<S: Clone, T>(pattern: &SpannedPattern<S, T>) -> Option<S> {
+    match &pattern.inner {
+        Pattern::Leaf(_) => None,
+        Pattern::Tuple(els) => els.iter().find_map(find_expression),
+        Pattern::Record(fields) => fields
+            .iter()
+            .find_map(|field| find_expression(&field.value)),
+        Pattern::Expression(_) => Some(pattern.span.clone()),
+    }
+}
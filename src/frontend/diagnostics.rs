@@ -100,7 +100,7 @@ where
 
 impl Diagnostic<(Url, Span)> {
     pub fn to_lsp(&self) -> tower_lsp::lsp_types::Diagnostic {
-        use tower_lsp::lsp_types::DiagnosticSeverity;
+        use tower_lsp::lsp_types::{DiagnosticRelatedInformation, DiagnosticSeverity, Location};
         let severity = match self.kind {
             DiagnosticKind::Error => DiagnosticSeverity::ERROR,
             DiagnosticKind::Warning => DiagnosticSeverity::WARNING,
@@ -108,10 +108,23 @@ impl Diagnostic<(Url, Span)> {
             DiagnosticKind::Note => DiagnosticSeverity::HINT,
         };
 
+        let related = self
+            .labels
+            .iter()
+            .map(|label| DiagnosticRelatedInformation {
+                location: Location {
+                    uri: label.span.0.clone(),
+                    range: label.span.1.into(),
+                },
+                message: label.inner.clone(),
+            })
+            .collect();
+
         tower_lsp::lsp_types::Diagnostic {
             range: self.span.1.into(),
             severity: Some(severity),
             message: self.message.clone(),
+            related_information: Some(related),
             ..Default::default()
         }
     }
@@ -0,0 +1,390 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Estimates of how large the decisions and constraints of a program ground.
+//!
+//! Estimates are made from the sizes of the relations that the frontend has
+//! evaluated, before any grounding. The matches of a body are bounded both by
+//! the product of its atoms' sizes and by the product of its variables'
+//! domains, where the domain of a variable is the smallest number of distinct
+//! values in any column that it is bound to. A range binds as many values as
+//! it spans if its bounds are constant, and as many as the variable threshold
+//! if they are not. Other builtins only filter the terms bound elsewhere or
+//! compute a term from them, so they add no matches, and each term that they
+//! compute is assumed to take one value per match, as is each aggregate.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{
+    backend::{builtins::Builtin, types::Tuple},
+    frontend::{span::Spanned, types::*},
+};
+
+/// The sizes beyond which the grounding of a decision or constraint is warned about.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Thresholds {
+    /// The most solver variables that an item may ground into.
+    pub variables: u64,
+
+    /// The most clauses that an item may ground into.
+    pub clauses: u64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            variables: 10_000,
+            clauses: 100_000,
+        }
+    }
+}
+
+/// The estimated grounding of a decision or constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Estimate<S> {
+    /// The span of the decision's head or the constraint's kind.
+    pub span: S,
+
+    /// The decision relation, or nothing for a constraint.
+    pub decision: Option<ResourceId>,
+
+    /// The number of decision tuples or constraint instances.
+    pub instances: u64,
+
+    /// The number of solver variables.
+    pub variables: u64,
+
+    /// The number of clauses, counting each cardinality constraint as one.
+    pub clauses: u64,
+}
+
+impl<S: Clone> Estimate<S> {
+    /// Summarizes this estimate next to its span.
+    pub fn hint(&self) -> InlayHint<S> {
+        let instances = match self.decision {
+            Some(_) => "tuples",
+            None => "instances",
+        };
+
+        InlayHint {
+            span: self.span.clone(),
+            contents: format!(
+                " ~{} {instances}, {} variables, {} clauses",
+                self.instances, self.variables, self.clauses
+            ),
+        }
+    }
+
+    /// Warns about this estimate if it exceeds either threshold.
+    pub fn diagnostic(&self, thresholds: &Thresholds) -> Option<Diagnostic<S>> {
+        if self.variables <= thresholds.variables && self.clauses <= thresholds.clauses {
+            return None;
+        }
+
+        let (name, label) = match &self.decision {
+            Some(relation) => (
+                relation.to_string(),
+                format!("About {} possible tuples of {relation}.", self.instances),
+            ),
+            None => (
+                "Constraint".to_string(),
+                format!("About {} instances of this constraint.", self.instances),
+            ),
+        };
+
+        Some(Diagnostic {
+            span: self.span.clone(),
+            kind: DiagnosticKind::Warning,
+            message: format!(
+                "{name} grounds into about {} variables and {} clauses",
+                self.variables, self.clauses
+            ),
+            labels: vec![Spanned {
+                span: self.span.clone(),
+                inner: label,
+            }],
+        })
+    }
+}
+
+/// Estimates the grounding of every decision and every constraint that
+/// depends on a decision.
+///
+/// Ranges whose bounds are only known during evaluation are assumed to span
+/// the variable threshold.
+pub fn estimate<S: Clone>(
+    items: &[IndexedItem<S, ResourceId>],
+    relations: &BTreeMap<ResourceId, BTreeSet<Tuple>>,
+    thresholds: &Thresholds,
+) -> Vec<Estimate<S>> {
+    let mut sizes: HashMap<ResourceId, Size> = relations
+        .iter()
+        .map(|(relation, tuples)| (relation.clone(), Size::of(tuples)))
+        .collect();
+
+    // find every relation that transitively depends on a decision
+    let mut conditional: BTreeSet<_> = items
+        .iter()
+        .filter_map(|item| match &item.inner {
+            ModuleItem::Decision(Decision(rule)) => Some(rule.head.relation.inner.clone()),
+            _ => None,
+        })
+        .collect();
+
+    loop {
+        let len = conditional.len();
+        for item in items.iter() {
+            for (head, body, _stratified) in item.dependencies() {
+                if conditional.contains(&body) {
+                    conditional.insert(head);
+                }
+            }
+        }
+
+        if conditional.len() == len {
+            break;
+        }
+    }
+
+    // the frontend does not evaluate relations that depend on decisions, so
+    // their sizes are estimated from their rules, giving up on deep recursion
+    for _ in 0..=items.len() {
+        let mut estimated: HashMap<ResourceId, Size> = HashMap::new();
+        for item in items.iter() {
+            let Some(rule) = item.rule_or_decision() else {
+                continue;
+            };
+
+            let relation = rule.head.relation.inner.clone();
+            if !conditional.contains(&relation) {
+                continue;
+            }
+
+            let body = Body::estimate(item, &sizes, thresholds);
+            let head = Size {
+                tuples: body.matches,
+                columns: rule
+                    .head
+                    .inner
+                    .pattern
+                    .into_leaves()
+                    .iter()
+                    .map(|term| body.domain(term))
+                    .collect(),
+            };
+
+            estimated
+                .entry(relation)
+                .and_modify(|size| size.add(&head))
+                .or_insert(head);
+        }
+
+        let changed = estimated
+            .iter()
+            .any(|(relation, size)| sizes.get(relation) != Some(size));
+
+        sizes.extend(estimated);
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut estimates = Vec::new();
+    for item in items.iter() {
+        let body = Body::estimate(item, &sizes, thresholds);
+
+        // about one clause for each decision-dependent literal of each match
+        let dependent = item
+            .body()
+            .iter()
+            .filter(|literal| conditional.contains(&literal.inner.atom().relation.inner))
+            .count() as u64;
+
+        let clauses = body.matches.saturating_mul(dependent);
+
+        match &item.inner {
+            ModuleItem::Decision(Decision(rule)) => estimates.push(Estimate {
+                span: rule.head.span.clone(),
+                decision: Some(rule.head.relation.inner.clone()),
+                instances: body.matches,
+                variables: body.matches,
+                clauses,
+            }),
+            ModuleItem::Constraint(constraint) if dependent > 0 => {
+                let instances = constraint
+                    .captures
+                    .iter()
+                    .map(|capture| body.domain(&AnyTerm::Variable(capture.inner)))
+                    .fold(1, u64::saturating_mul)
+                    .min(body.matches);
+
+                // matches of more than one literal are each a conjunction
+                let conjunctions = if dependent > 1 { body.matches } else { 0 };
+
+                estimates.push(Estimate {
+                    span: constraint.kind.span.clone(),
+                    decision: None,
+                    instances,
+                    variables: instances.saturating_add(conjunctions),
+                    clauses: clauses.saturating_add(instances),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    estimates
+}
+
+/// The number of tuples in a relation and of distinct values in each column.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Size {
+    tuples: u64,
+    columns: Vec<u64>,
+}
+
+impl Size {
+    fn of(tuples: &BTreeSet<Tuple>) -> Self {
+        let mut columns: Vec<BTreeSet<&Value>> = Vec::new();
+        for tuple in tuples.iter() {
+            columns.resize_with(tuple.len(), BTreeSet::new);
+            for (column, value) in columns.iter_mut().zip(tuple.iter()) {
+                column.insert(value);
+            }
+        }
+
+        Self {
+            tuples: tuples.len() as u64,
+            columns: columns.iter().map(|column| column.len() as u64).collect(),
+        }
+    }
+
+    /// Adds the tuples of another rule for the same relation.
+    fn add(&mut self, other: &Self) {
+        self.tuples = self.tuples.saturating_add(other.tuples);
+        self.columns.resize(other.columns.len(), 0);
+        for (column, other) in self.columns.iter_mut().zip(other.columns.iter()) {
+            *column = column.saturating_add(*other).min(self.tuples);
+        }
+    }
+}
+
+/// The estimated matches of a body and domains of its variables.
+struct Body {
+    matches: u64,
+    domains: BTreeMap<usize, u64>,
+}
+
+impl Body {
+    fn estimate<S: Clone>(
+        item: &IndexedItem<S, ResourceId>,
+        sizes: &HashMap<ResourceId, Size>,
+        thresholds: &Thresholds,
+    ) -> Self {
+        let mut atoms: u64 = 1;
+        let mut computed = Vec::new();
+        let mut domains: BTreeMap<usize, u64> = BTreeMap::new();
+        let mut bind = |var: usize, domain: u64| {
+            domains
+                .entry(var)
+                .and_modify(|other| *other = (*other).min(domain))
+                .or_insert(domain);
+        };
+
+        for (idx, literal) in item.body().iter().enumerate() {
+            let (atom, outer) = match &literal.inner {
+                Literal::Atom(atom) => (atom, None),
+                Literal::Aggregate(aggregate) => {
+                    if let AnyTerm::Variable(var) = aggregate.result.inner {
+                        bind(var, 1);
+                    }
+
+                    (&aggregate.atom, Some(item.outer_variables(idx)))
+                }
+                Literal::Negated(_) => continue,
+            };
+
+            let terms = atom.pattern.clone().into_leaves();
+            let vars = terms
+                .iter()
+                .enumerate()
+                .filter_map(|(column, term)| match term {
+                    AnyTerm::Variable(var) => Some((column, *var)),
+                    AnyTerm::Value(_) => None,
+                });
+
+            if let ResourceId::Builtin(builtin) = &atom.relation.inner {
+                // a range enumerates its last term, while other builtins are
+                // left until every atom has bound what it can
+                if let (Builtin::Range, [start, end, AnyTerm::Variable(var)]) =
+                    (builtin, terms.as_slice())
+                {
+                    let values = match (start, end) {
+                        (
+                            AnyTerm::Value(Value::Integer(start)),
+                            AnyTerm::Value(Value::Integer(end)),
+                        ) => end.saturating_sub(*start).max(0) as u64,
+                        _ => thresholds.variables,
+                    };
+
+                    bind(*var, values);
+                    if outer.is_none() {
+                        atoms = atoms.saturating_mul(values);
+                    }
+                } else {
+                    computed.extend(vars.map(|(_column, var)| var));
+                }
+
+                continue;
+            }
+
+            let size = sizes.get(&atom.relation.inner).cloned().unwrap_or_default();
+            for (column, var) in vars {
+                if outer.as_ref().is_none_or(|outer| outer.contains(&var)) {
+                    bind(var, size.columns.get(column).copied().unwrap_or(0));
+                }
+            }
+
+            if outer.is_none() {
+                atoms = atoms.saturating_mul(size.tuples);
+            }
+        }
+
+        // filters leave the domains of their bound terms as they are
+        for var in computed {
+            domains.entry(var).or_insert(1);
+        }
+
+        let variables = domains.values().copied().fold(1, u64::saturating_mul);
+
+        Self {
+            matches: atoms.min(variables),
+            domains,
+        }
+    }
+
+    /// Estimates the number of distinct values of a term over the matches.
+    fn domain(&self, term: &Term<usize>) -> u64 {
+        match term {
+            AnyTerm::Variable(var) => self.domains.get(var).copied().unwrap_or(1),
+            AnyTerm::Value(_) => 1,
+        }
+        .min(self.matches)
+    }
+}
@@ -16,13 +16,20 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::BTreeMap, fmt::Debug};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Debug, Display},
+    sync::Arc,
+};
 
 use indexmap::IndexMap;
 use url::Url;
 
 use crate::{
-    frontend::{span::Spanned, types::*},
+    frontend::{
+        span::Spanned,
+        types::{self, *},
+    },
     utils::Key,
 };
 
@@ -63,8 +70,237 @@ impl<S: Clone> IndexedItem<S, ResourceId> {
     }
 }
 
+impl<S: Clone> IndexedItem<S, ResourceId> {
+    /// Resolves each relation imported by this item to its target.
+    ///
+    /// Modules that are not a part of the standard library are files within
+    /// the project at `root`. Relations imported from those files are not
+    /// checked to exist.
+    pub fn imports(self, root: &Url) -> Vec<DiagnosticResult<S, ImportedRelation<S>>> {
+        let ModuleItem::Import(import) = self.inner else {
+            return vec![];
+        };
+
+        let module: Vec<_> = import.module.iter().map(|el| el.inner.as_str()).collect();
+        let path = module.join(".");
+        let first = &import.module[0];
+
+        if !Builtin::is_module(&module) {
+            let Some(url) = import.module_url(root) else {
+                return vec![Err(Diagnostic {
+                    span: first.span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: format!("Unknown module {path}"),
+                    labels: vec![first.clone().map(|_| "Imported here.".to_string())],
+                })];
+            };
+
+            let url = Arc::new(url);
+            return import
+                .relations
+                .into_iter()
+                .map(|relation| {
+                    let ResourceId::SourceSymbol(_, name) = &relation.inner else {
+                        unreachable!("imported relation was already resolved");
+                    };
+
+                    let target = ResourceId::SourceSymbol(url.clone(), name.clone());
+                    Ok(ImportedRelation {
+                        local: relation.inner.clone(),
+                        target: relation.map(|_| target),
+                        file: Some(first.clone().map(|_| path.clone())),
+                    })
+                })
+                .collect();
+        }
+
+        import
+            .relations
+            .into_iter()
+            .map(|relation| {
+                let ResourceId::SourceSymbol(_, name) = &relation.inner else {
+                    unreachable!("imported relation was already resolved");
+                };
+
+                match Builtin::lookup(&module, name) {
+                    Some(builtin) => Ok(ImportedRelation {
+                        local: relation.inner.clone(),
+                        target: relation.map(|_| ResourceId::Builtin(builtin)),
+                        file: None,
+                    }),
+                    None => Err(Diagnostic {
+                        span: relation.span.clone(),
+                        kind: DiagnosticKind::Error,
+                        message: format!("{path} has no relation {name}"),
+                        labels: vec![relation.map(|_| "Imported here.".to_string())],
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+impl<S, R> Import<S, R> {
+    /// Locates the file of the imported module within the project at `root`.
+    ///
+    /// Returns nothing for modules of the standard library, and for paths
+    /// that cannot name a file.
+    pub fn module_url(&self, root: &Url) -> Option<Url> {
+        let module: Vec<_> = self.module.iter().map(|el| el.inner.as_str()).collect();
+        if Builtin::is_module(&module) {
+            return None;
+        }
+
+        let path = format!("{}.ful", module.join("/"));
+        root.join(&path).ok()
+    }
+}
+
+impl<S: Clone> ImportedRelation<S> {
+    /// Reports that this relation is not defined by the file it is imported from.
+    pub fn missing_diagnostic(&self, has_module: bool) -> Diagnostic<S> {
+        let module = self.file.clone().expect("relation is imported from a file");
+        let (span, message) = if has_module {
+            let message = format!("{} has no relation {}", module.inner, self.target.inner);
+            (self.target.span.clone(), message)
+        } else {
+            (module.span, format!("Unknown module {}", module.inner))
+        };
+
+        Diagnostic {
+            span: span.clone(),
+            kind: DiagnosticKind::Error,
+            message,
+            labels: vec![Spanned {
+                span,
+                inner: "Imported here.".to_string(),
+            }],
+        }
+    }
+}
+
+impl Builtin {
+    /// Creates the type of this relation with every term at the same span.
+    pub fn ty<S: Clone>(&self, span: S) -> Type<S> {
+        let terms = self
+            .signature()
+            .iter()
+            .map(|prim| Spanned {
+                span: span.clone(),
+                inner: Pattern::Leaf(prim.clone()),
+            })
+            .collect();
+
+        Spanned {
+            span,
+            inner: Pattern::Tuple(terms),
+        }
+    }
+}
+
 impl<S: Clone, R: Clone> IndexedItem<S, R> {
-    pub fn base_type(self) -> Option<(R, Type<S>)> {
+    /// Lists every relation referenced by this item.
+    pub fn relations(&self) -> Vec<R> {
+        let mut relations = Vec::new();
+        self.clone().map_relations(&mut |_span, relation| {
+            relations.push(relation);
+        });
+
+        relations
+    }
+
+    /// Finds the type that a type or enum declaration gives its relation.
+    pub fn declared_type(self) -> Option<(R, Type<S>)>
+    where
+        R: Display,
+    {
+        match self.inner {
+            ModuleItem::Type(decl) => {
+                let ty = decl
+                    .ty
+                    .map_leaves(&mut |_span, name| PrimitiveType::from_name(name));
+
+                Some((decl.relation.inner, ty))
+            }
+            ModuleItem::Enum(decl) => {
+                let ty = Spanned {
+                    span: decl.name.span,
+                    inner: Pattern::Leaf(PrimitiveType::Named(decl.name.inner.to_string())),
+                };
+
+                Some((decl.name.inner, ty))
+            }
+            _ => None,
+        }
+    }
+
+    /// Lists the named types that a type declaration refers to.
+    pub fn type_names(&self) -> Vec<Spanned<S, String>> {
+        let ModuleItem::Type(decl) = &self.inner else {
+            return vec![];
+        };
+
+        let mut names = Vec::new();
+        decl.ty.clone().map_leaves(&mut |span, name| {
+            if let PrimitiveType::Named(name) = PrimitiveType::from_name(name) {
+                names.push(Spanned {
+                    span: span.clone(),
+                    inner: name,
+                });
+            }
+        });
+
+        names
+    }
+
+    /// Names the type declared by an enum declaration.
+    pub fn enum_name(&self) -> Option<String>
+    where
+        R: Display,
+    {
+        match &self.inner {
+            ModuleItem::Enum(decl) => Some(decl.name.inner.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Pairs each variant of an enum declaration with its type's name.
+    pub fn variants(self) -> Vec<(String, Spanned<S, String>)>
+    where
+        R: Display,
+    {
+        let ModuleItem::Enum(decl) = self.inner else {
+            return vec![];
+        };
+
+        let name = decl.name.inner.to_string();
+        decl.variants
+            .into_iter()
+            .map(|variant| {
+                let ty = Spanned {
+                    span: variant.span,
+                    inner: name.clone(),
+                };
+
+                (variant.inner, ty)
+            })
+            .collect()
+    }
+
+    /// Lists every relation referenced by this item, spanned by its name.
+    pub fn relation_names(&self) -> Vec<Spanned<S, R>> {
+        let mut names = Vec::new();
+        self.clone().map_relations(&mut |span, relation| {
+            names.push(Spanned {
+                span: span.clone(),
+                inner: relation,
+            });
+        });
+
+        names
+    }
+
+    pub fn base_type(self, variants: &Variants) -> Option<(R, Type<S>)> {
         let rule = self.rule_or_decision()?;
 
         if !rule.body.is_empty() {
@@ -78,12 +314,12 @@ impl<S: Clone, R: Clone> IndexedItem<S, R> {
             .inner
             .pattern
             .flat_quantify(&mut |_, _| None)?
-            .map_leaves(&mut |_span, leaf| leaf.ty());
+            .map_leaves(&mut |_span, leaf| leaf.ty(variants));
 
         Some((relation, ty))
     }
 
-    pub fn head_type(self) -> Option<(R, SpannedPattern<S, TypeTerm<usize>>)> {
+    pub fn head_type(self, variants: &Variants) -> Option<(R, SpannedPattern<S, TypeTerm<usize>>)> {
         let rule = self.rule_or_decision()?;
 
         // TODO: this actually would replace the work of base_types if unblocked
@@ -99,7 +335,7 @@ impl<S: Clone, R: Clone> IndexedItem<S, R> {
             .pattern
             .map_leaves(&mut |_span, term| match term {
                 AnyTerm::Variable(name) => AnyTerm::Variable(name),
-                AnyTerm::Value(val) => AnyTerm::Value(val.ty()),
+                AnyTerm::Value(val) => AnyTerm::Value(val.ty(variants)),
             });
 
         Some((relation, ty))
@@ -107,31 +343,229 @@ impl<S: Clone, R: Clone> IndexedItem<S, R> {
 
     pub fn body_types(
         (key, item): (Key<Self>, Self),
+        variants: &Variants,
     ) -> Vec<(R, (Key<Self>, SpannedPattern<S, TypeTerm<usize>>))> {
         let body = match item.inner {
             ModuleItem::Decision(Decision(rule)) => rule.body,
             ModuleItem::Rule(rule) => rule.body,
             ModuleItem::Constraint(constraint) => constraint.body,
+            ModuleItem::Objective(objective) => objective.body,
             _ => return vec![],
         };
 
         body.into_iter()
-            .map(move |atom| {
+            .map(move |literal| {
+                let atom = literal.inner.into_atom();
                 let relation = atom.relation.inner.clone();
 
-                let ty = atom
-                    .inner
-                    .pattern
-                    .map_leaves(&mut |_span, term| match term {
-                        AnyTerm::Variable(name) => AnyTerm::Variable(name),
-                        AnyTerm::Value(val) => AnyTerm::Value(val.ty()),
-                    });
+                let ty = atom.pattern.map_leaves(&mut |_span, term| match term {
+                    AnyTerm::Variable(name) => AnyTerm::Variable(name),
+                    AnyTerm::Value(val) => AnyTerm::Value(val.ty(variants)),
+                });
 
                 (relation, (key, ty))
             })
             .collect()
     }
 
+    /// Lists the variable types fixed by the kind of each aggregate.
+    ///
+    /// Counts and sums are integers, and only integers can be summed.
+    /// Objectives only aggregate integers.
+    pub fn aggregate_types(
+        (key, item): (Key<Self>, Self),
+    ) -> Vec<(Key<Self>, (Spanned<S, usize>, Type<S>))> {
+        let mut types = Vec::new();
+        if let ModuleItem::Objective(objective) = &item.inner {
+            if let Some(target) = &objective.target {
+                let ty = Spanned {
+                    span: target.span.clone(),
+                    inner: Pattern::Leaf(PrimitiveType::Integer),
+                };
+
+                types.push((key, (target.clone(), ty)));
+            }
+        }
+
+        for literal in item.body() {
+            let Literal::Aggregate(aggregate) = &literal.inner else {
+                continue;
+            };
+
+            let mut vars = vec![&aggregate.result];
+            match aggregate.kind.inner {
+                AggregateKind::Count => {}
+                AggregateKind::Sum => vars.extend(&aggregate.target),
+                AggregateKind::Min | AggregateKind::Max => continue,
+            }
+
+            for var in vars {
+                let AnyTerm::Variable(idx) = var.inner else {
+                    continue;
+                };
+
+                let ty = Spanned {
+                    span: var.span.clone(),
+                    inner: Pattern::Leaf(PrimitiveType::Integer),
+                };
+
+                types.push((key, (var.clone().map(|_| idx), ty)));
+            }
+        }
+
+        types
+    }
+
+    /// Lists each aggregate result variable whose type is that of its target.
+    pub fn aggregate_aliases(
+        (key, item): (Key<Self>, Self),
+    ) -> Vec<(Key<Self>, (Spanned<S, usize>, usize))> {
+        item.body()
+            .iter()
+            .filter_map(|literal| {
+                let Literal::Aggregate(aggregate) = &literal.inner else {
+                    return None;
+                };
+
+                let (AggregateKind::Min | AggregateKind::Max) = aggregate.kind.inner else {
+                    return None;
+                };
+
+                let AnyTerm::Variable(result) = aggregate.result.inner else {
+                    return None;
+                };
+
+                let AnyTerm::Variable(target) = aggregate.target.as_ref()?.inner else {
+                    return None;
+                };
+
+                Some((key, (aggregate.result.clone().map(|_| result), target)))
+            })
+            .collect()
+    }
+
+    /// Lists each relation that the head of this item depends on.
+    ///
+    /// Dependencies that must be stratified include the span of their literal.
+    pub fn dependencies(&self) -> Vec<(R, R, Option<S>)> {
+        let Some(rule) = self.rule_or_decision() else {
+            return vec![];
+        };
+
+        let head = rule.head.relation.inner.clone();
+
+        rule.body
+            .into_iter()
+            .map(|literal| {
+                let stratified = literal.is_nonmonotonic().then(|| literal.span.clone());
+                let body = literal.inner.into_atom().relation.inner;
+                (head.clone(), body, stratified)
+            })
+            .collect()
+    }
+
+    pub fn unsafe_negation_diagnostics(&self) -> Vec<Diagnostic<S>> {
+        let mut bound = BTreeSet::new();
+        for literal in self.body() {
+            let (atom, result) = match &literal.inner {
+                Literal::Atom(atom) => (atom, None),
+                Literal::Aggregate(aggregate) => (&aggregate.atom, Some(&aggregate.result.inner)),
+                Literal::Negated(_) => continue,
+            };
+
+            bound.extend(
+                atom.pattern
+                    .clone()
+                    .into_leaves()
+                    .iter()
+                    .chain(result)
+                    .filter_map(|term| match term {
+                        AnyTerm::Variable(var) => Some(*var),
+                        AnyTerm::Value(_) => None,
+                    }),
+            );
+        }
+
+        let mut diagnostics = Vec::new();
+        for literal in self.body() {
+            let Literal::Negated(atom) = &literal.inner else {
+                continue;
+            };
+
+            atom.pattern.clone().map_leaves(&mut |span, term| {
+                let AnyTerm::Variable(var) = term else {
+                    return;
+                };
+
+                if bound.contains(&var) {
+                    return;
+                }
+
+                let name = &self.variables[var].inner;
+                diagnostics.push(Diagnostic {
+                    span: span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: format!("{name:?} only appears within negated atoms"),
+                    labels: vec![Spanned {
+                        span: span.clone(),
+                        inner: "Negated here.".to_string(),
+                    }],
+                });
+            });
+        }
+
+        diagnostics
+    }
+
+    /// Finds the variables appearing outside of the body literal at the given index.
+    ///
+    /// These are the variables that an aggregate literal groups its tuples by.
+    pub fn outer_variables(&self, literal: usize) -> BTreeSet<usize> {
+        let mut vars = BTreeSet::new();
+        let mut insert = |_span: &S, var| {
+            vars.insert(var);
+        };
+
+        match &self.inner {
+            ModuleItem::Decision(Decision(rule)) | ModuleItem::Rule(rule) => {
+                rule.head.inner.clone().map_variables(&mut insert);
+            }
+            ModuleItem::Constraint(constraint) => {
+                for capture in constraint.captures.iter() {
+                    insert(&capture.span, capture.inner);
+                }
+            }
+            ModuleItem::Objective(objective) => {
+                if let Some(target) = &objective.target {
+                    insert(&target.span, target.inner);
+                }
+            }
+            _ => {}
+        }
+
+        for (idx, other) in self.body().iter().enumerate() {
+            if idx != literal {
+                other.inner.clone().map_variables(&mut insert);
+            }
+        }
+
+        vars
+    }
+
+    pub fn body(&self) -> &[Spanned<S, Literal<S, R, Term<usize>>>] {
+        match &self.inner {
+            ModuleItem::Decision(Decision(rule)) | ModuleItem::Rule(rule) => &rule.body,
+            ModuleItem::Constraint(constraint) => &constraint.body,
+            ModuleItem::Objective(objective) => &objective.body,
+            _ => &[],
+        }
+    }
+
+    /// Tests if this item is a rule without a body.
+    pub fn is_fact(&self) -> bool {
+        matches!(&self.inner, ModuleItem::Rule(rule) if rule.body.is_empty())
+    }
+
     pub fn rule_or_decision(&self) -> Option<Rule<S, R, usize>> {
         match &self.inner {
             ModuleItem::Decision(Decision(rule)) | ModuleItem::Rule(rule) => Some(rule.clone()),
@@ -196,9 +630,13 @@ impl<S: Clone, R: Clone> ModuleItem<S, R, String> {
         let inner = {
             use ModuleItem::*;
             match item {
+                Import(el) => Import(el),
+                Type(el) => Type(el),
+                Enum(el) => Enum(el),
                 Decision(el) => Decision(el.map_variables(&mut map)),
                 Rule(el) => Rule(el.map_variables(&mut map)),
                 Constraint(el) => Constraint(el.map_variables(&mut map)),
+                Objective(el) => Objective(el.map_variables(&mut map)),
                 Diagnostic(el) => Diagnostic(el),
             }
         };
@@ -214,6 +652,13 @@ impl<S: Clone, R: Clone> ModuleItem<S, R, String> {
             variables,
         };
 
+        diagnostics.extend(
+            indexed
+                .unsafe_negation_diagnostics()
+                .into_iter()
+                .map(|d| (indexed.url.clone(), d)),
+        );
+
         (indexed, diagnostics)
     }
 }
@@ -222,10 +667,54 @@ impl<S, R, T> ModuleItem<S, R, T> {
     pub fn map_relations<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> ModuleItem<S, O, T> {
         use ModuleItem::*;
         match self {
+            Import(el) => Import(el.map_relations(cb)),
+            Type(el) => Type(el.map_relations(cb)),
+            Enum(el) => Enum(el.map_relations(cb)),
             Decision(el) => Decision(el.map_relations(cb)),
             Rule(el) => Rule(el.map_relations(cb)),
             Constraint(el) => Constraint(el.map_relations(cb)),
-            Diagnostic(el) => return Diagnostic(el),
+            Objective(el) => Objective(el.map_relations(cb)),
+            Diagnostic(el) => Diagnostic(el),
+        }
+    }
+}
+
+impl<S, R> Import<S, R> {
+    pub fn map_relations<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> Import<S, O> {
+        Import {
+            module: self.module,
+            relations: self
+                .relations
+                .into_iter()
+                .map(|relation| Spanned {
+                    inner: cb(&relation.span, relation.inner),
+                    span: relation.span,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<S, R> TypeDeclaration<S, R> {
+    pub fn map_relations<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> TypeDeclaration<S, O> {
+        TypeDeclaration {
+            relation: Spanned {
+                inner: cb(&self.relation.span, self.relation.inner),
+                span: self.relation.span,
+            },
+            ty: self.ty,
+        }
+    }
+}
+
+impl<S, R> EnumDeclaration<S, R> {
+    pub fn map_relations<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> EnumDeclaration<S, O> {
+        EnumDeclaration {
+            name: Spanned {
+                inner: cb(&self.name.span, self.name.inner),
+                span: self.name.span,
+            },
+            variants: self.variants,
         }
     }
 }
@@ -289,6 +778,7 @@ impl<S, R, T> Constraint<S, R, T> {
             .collect();
 
         Constraint {
+            soft: self.soft,
             captures,
             kind: self.kind,
             body,
@@ -297,6 +787,7 @@ impl<S, R, T> Constraint<S, R, T> {
 
     pub fn map_relations<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> Constraint<S, O, T> {
         Constraint {
+            soft: self.soft,
             captures: self.captures,
             kind: self.kind,
             body: self
@@ -308,6 +799,104 @@ impl<S, R, T> Constraint<S, R, T> {
     }
 }
 
+impl<S, R, T> Objective<S, R, T> {
+    pub fn map_variables<O>(self, cb: &mut impl FnMut(bool, &S, T) -> O) -> Objective<S, R, O> {
+        let body = self
+            .body
+            .into_iter()
+            .map(|atom| {
+                atom.map(|inner| inner.map_variables(&mut |span, var| cb(false, span, var)))
+            })
+            .collect();
+
+        // like a rule's head, the target must appear within the body
+        let target = self.target.map(|var| Spanned {
+            inner: cb(true, &var.span, var.inner),
+            span: var.span,
+        });
+
+        Objective {
+            sense: self.sense,
+            priority: self.priority,
+            kind: self.kind,
+            target,
+            body,
+        }
+    }
+
+    pub fn map_relations<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> Objective<S, O, T> {
+        Objective {
+            sense: self.sense,
+            priority: self.priority,
+            kind: self.kind,
+            target: self.target,
+            body: self
+                .body
+                .into_iter()
+                .map(|atom| atom.map(|inner| inner.map_relation(cb)))
+                .collect(),
+        }
+    }
+}
+
+impl<S, R, T, V> Literal<S, R, AnyTerm<T, V>> {
+    pub fn map_variables<O>(self, cb: &mut impl FnMut(&S, T) -> O) -> Literal<S, R, AnyTerm<O, V>> {
+        use Literal::*;
+        match self {
+            Atom(el) => Atom(el.map_variables(cb)),
+            Negated(el) => Negated(el.map_variables(cb)),
+            Aggregate(el) => Aggregate(el.map_variables(cb)),
+        }
+    }
+}
+
+impl<S, R, T> Literal<S, R, T> {
+    pub fn map_relation<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> Literal<S, O, T> {
+        use Literal::*;
+        match self {
+            Atom(el) => Atom(el.map_relation(cb)),
+            Negated(el) => Negated(el.map_relation(cb)),
+            Aggregate(el) => Aggregate(el.map_relation(cb)),
+        }
+    }
+}
+
+impl<S, R, T, V> Aggregation<S, R, AnyTerm<T, V>> {
+    pub fn map_variables<O>(
+        self,
+        cb: &mut impl FnMut(&S, T) -> O,
+    ) -> Aggregation<S, R, AnyTerm<O, V>> {
+        let mut map_term = |term: Spanned<S, AnyTerm<T, V>>| Spanned {
+            inner: match term.inner {
+                AnyTerm::Variable(var) => AnyTerm::Variable(cb(&term.span, var)),
+                AnyTerm::Value(val) => AnyTerm::Value(val),
+            },
+            span: term.span,
+        };
+
+        let result = map_term(self.result);
+        let target = self.target.map(&mut map_term);
+
+        Aggregation {
+            result,
+            kind: self.kind,
+            target,
+            atom: self.atom.map_variables(cb),
+        }
+    }
+}
+
+impl<S, R, T> Aggregation<S, R, T> {
+    pub fn map_relation<O>(self, cb: &mut impl FnMut(&S, R) -> O) -> Aggregation<S, O, T> {
+        Aggregation {
+            result: self.result,
+            kind: self.kind,
+            target: self.target,
+            atom: self.atom.map_relation(cb),
+        }
+    }
+}
+
 impl<S, R, T, V> Atom<S, R, AnyTerm<T, V>> {
     pub fn map_variables<O>(self, cb: &mut impl FnMut(&S, T) -> O) -> Atom<S, R, AnyTerm<O, V>> {
         Atom {
@@ -371,7 +960,7 @@ impl<S: Clone + Debug + Eq, T: Clone> SpannedPattern<S, TypeTerm<T>> {
                             labels: vec![],
                         });
                     } else {
-                        lhs.into_iter()
+                        lhs.iter()
                             .zip(rhs)
                             .for_each(|(lhs, rhs)| lhs.clone().unify_inner(rhs, diagnostics, cb));
                     }
@@ -385,7 +974,87 @@ impl<S: Clone + Debug + Eq, T: Clone> SpannedPattern<S, TypeTerm<T>> {
                         labels: vec![],
                     });
                 }
+                Pattern::Record(_) => {
+                    diagnostics.push(Diagnostic {
+                        kind: DiagnosticKind::Error,
+                        message: format!(
+                            "Expected record {}, got tuple of arity {}",
+                            target.inner,
+                            lhs.len()
+                        ),
+                        span: self.span.clone(),
+                        labels: vec![Spanned {
+                            span: self.span.clone(),
+                            inner: "Pattern is a tuple.".to_string(),
+                        }],
+                    });
+                }
+                Pattern::Expression(_) => unreachable!("types never contain expressions"),
             },
+            Pattern::Record(lhs) => {
+                let Pattern::Record(rhs) = target.inner else {
+                    diagnostics.push(Diagnostic {
+                        kind: DiagnosticKind::Error,
+                        message: format!("Expected {}, got a record", target.inner),
+                        span: self.span.clone(),
+                        labels: vec![Spanned {
+                            span: self.span.clone(),
+                            inner: "Pattern is a record.".to_string(),
+                        }],
+                    });
+                    return;
+                };
+
+                // both sides are sorted by key, so duplicates are adjacent
+                for pair in lhs.windows(2) {
+                    if pair[0].key.inner == pair[1].key.inner {
+                        diagnostics.push(Diagnostic {
+                            kind: DiagnosticKind::Error,
+                            message: format!("Field {} is given more than once", pair[1].key.inner),
+                            span: pair[1].key.span.clone(),
+                            labels: vec![Spanned {
+                                span: pair[1].key.span.clone(),
+                                inner: "Duplicate field.".to_string(),
+                            }],
+                        });
+                    }
+                }
+
+                for field in rhs.iter() {
+                    if !lhs.iter().any(|lhs| lhs.key.inner == field.key.inner) {
+                        diagnostics.push(Diagnostic {
+                            kind: DiagnosticKind::Error,
+                            message: format!("Missing field {}", field.key.inner),
+                            span: self.span.clone(),
+                            labels: vec![Spanned {
+                                span: self.span.clone(),
+                                inner: format!("Record has no {} field.", field.key.inner),
+                            }],
+                        });
+                    }
+                }
+
+                for field in lhs.iter() {
+                    match rhs.iter().find(|rhs| rhs.key.inner == field.key.inner) {
+                        Some(rhs) => {
+                            field
+                                .value
+                                .clone()
+                                .unify_inner(rhs.value.clone(), diagnostics, cb)
+                        }
+                        None => diagnostics.push(Diagnostic {
+                            kind: DiagnosticKind::Error,
+                            message: format!("Unknown field {}", field.key.inner),
+                            span: field.key.span.clone(),
+                            labels: vec![Spanned {
+                                span: field.key.span.clone(),
+                                inner: "Relation has no such field.".to_string(),
+                            }],
+                        }),
+                    }
+                }
+            }
+            Pattern::Expression(_) => unreachable!("expressions are desugared before typing"),
             Pattern::Leaf(AnyTerm::Variable(var)) => {
                 let var = var.clone();
                 cb(self.map(|_| var), target);
@@ -394,10 +1063,12 @@ impl<S: Clone + Debug + Eq, T: Clone> SpannedPattern<S, TypeTerm<T>> {
                 if Pattern::Leaf(prim.clone()) != target.inner {
                     diagnostics.push(Diagnostic {
                         kind: DiagnosticKind::Error,
-                        message: format!("Expected {:?}, got {target:?}", prim),
-                        span: target.span,
-                        // TODO
-                        labels: vec![],
+                        message: format!("Expected {}, got {prim}", target.inner),
+                        span: self.span.clone(),
+                        labels: vec![Spanned {
+                            span: self.span.clone(),
+                            inner: format!("This value is of type {prim}."),
+                        }],
                     });
                 }
             }
@@ -442,6 +1113,28 @@ impl<S, T, V> SpannedPattern<S, AnyTerm<T, V>> {
                     span: self.span,
                 }
             }
+            Record(fields) => {
+                let mut els = Vec::with_capacity(fields.len());
+                for Field { key, value } in fields {
+                    let value = value.flat_quantify(cb)?;
+                    els.push(Field { key, value });
+                }
+
+                Spanned {
+                    inner: Record(els),
+                    span: self.span,
+                }
+            }
+            Expression(expr) => {
+                let types::Expression { op, lhs, rhs } = *expr;
+                let lhs = lhs.flat_quantify(cb)?;
+                let rhs = rhs.flat_quantify(cb)?;
+
+                Spanned {
+                    inner: Expression(Box::new(types::Expression { op, lhs, rhs })),
+                    span: self.span,
+                }
+            }
         })
     }
 
@@ -474,6 +1167,28 @@ impl<S, T> SpannedPattern<S, T> {
                     span: self.span,
                 }
             }
+            Record(fields) => {
+                let mut els = Vec::with_capacity(fields.len());
+                for Field { key, value } in fields {
+                    let value = value.flat_map_leaves(cb)?;
+                    els.push(Field { key, value });
+                }
+
+                Spanned {
+                    inner: Record(els),
+                    span: self.span,
+                }
+            }
+            Expression(expr) => {
+                let types::Expression { op, lhs, rhs } = *expr;
+                let lhs = lhs.flat_map_leaves(cb)?;
+                let rhs = rhs.flat_map_leaves(cb)?;
+
+                Spanned {
+                    inner: Expression(Box::new(types::Expression { op, lhs, rhs })),
+                    span: self.span,
+                }
+            }
         })
     }
 
@@ -481,6 +1196,12 @@ impl<S, T> SpannedPattern<S, T> {
         self.flat_map_leaves(&mut |span, var| Some(cb(span, var)))
             .unwrap()
     }
+
+    pub fn into_leaves(self) -> Vec<T> {
+        let mut leaves = Vec::new();
+        self.map_leaves(&mut |_span, leaf| leaves.push(leaf));
+        leaves
+    }
 }
 
 impl<T, V> AnyTerm<T, V> {
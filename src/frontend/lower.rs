@@ -0,0 +1,515 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::{
+    backend::types::{Argument, Condition, Node, Reducer, Tuple},
+    frontend::types::*,
+    utils::Key,
+};
+
+impl<S: Clone> IndexedItem<S, ResourceId> {
+    /// Lowers a body-less, fully-ground rule into an initial relation tuple,
+    /// or an enum declaration into a tuple for each of its variants.
+    pub fn lower_facts(self) -> Vec<(ResourceId, Tuple)> {
+        if let ModuleItem::Enum(decl) = self.inner {
+            return decl
+                .variants
+                .into_iter()
+                .map(|variant| {
+                    let tuple = Arc::from([Value::Symbol(variant.inner)]);
+                    (decl.name.inner.clone(), tuple)
+                })
+                .collect();
+        }
+
+        let Some(rule) = self.rule_or_decision() else {
+            return vec![];
+        };
+
+        if !rule.body.is_empty() {
+            return vec![];
+        }
+
+        let relation = rule.head.relation.inner.clone();
+
+        let tuple = rule
+            .head
+            .inner
+            .pattern
+            .into_leaves()
+            .into_iter()
+            .map(|term| term.flat_quantify(&mut |_var| None))
+            .collect::<Option<_>>();
+
+        tuple.map(|tuple| (relation, tuple)).into_iter().collect()
+    }
+
+    /// Lowers a rule into the backend nodes that evaluate it, along with the
+    /// native range nodes whose bounds are only known during evaluation and
+    /// the spans of the literals they were lowered from.
+    ///
+    /// Returns no nodes if the item is not a rule, is a fact that is lowered
+    /// by [IndexedItem::lower_facts] instead, or has unbound variables that
+    /// are reported before lowering.
+    pub fn lower_nodes(self) -> DiagnosticResult<S, (Vec<Node>, Vec<(Key<Node>, S)>)> {
+        let Some(rule) = self.rule_or_decision() else {
+            return Ok((vec![], vec![]));
+        };
+
+        let mut head = BTreeSet::new();
+        rule.head.inner.clone().map_variables(&mut |_span, var| {
+            head.insert(var);
+        });
+
+        let mut body = BTreeSet::new();
+        for literal in rule.body.iter() {
+            literal.inner.clone().map_variables(&mut |_span, var| {
+                body.insert(var);
+            });
+        }
+
+        // facts are lowered separately, and head variables missing from the
+        // body and unsafe negations are reported before lowering
+        let is_unsafe = !self.unsafe_negation_diagnostics().is_empty();
+        if rule.body.is_empty() || !head.is_subset(&body) || is_unsafe {
+            return Ok((vec![], vec![]));
+        }
+
+        let outer = (0..rule.body.len())
+            .map(|idx| self.outer_variables(idx))
+            .collect();
+
+        let mut lowering = Lowering::default();
+        let ranges = lowering.rule(rule, outer, &self.variables)?;
+        Ok((lowering.nodes, ranges))
+    }
+}
+
+/// A node whose terms are bound to a list of item variables.
+#[derive(Clone, Debug)]
+struct Bound {
+    key: Key<Node>,
+    vars: Vec<usize>,
+}
+
+#[derive(Default)]
+struct Lowering {
+    nodes: Vec<Node>,
+}
+
+impl Lowering {
+    fn push(&mut self, node: Node) -> Key<Node> {
+        let key = Key::new(&node);
+        self.nodes.push(node);
+        key
+    }
+
+    /// Lowers a rule given the variables outside of each body literal.
+    ///
+    /// Returns the ranges whose sizes can only be checked during evaluation.
+    fn rule<S: Clone>(
+        &mut self,
+        rule: Rule<S, ResourceId, usize>,
+        outer: Vec<BTreeSet<usize>>,
+        variables: &[Spanned<S, String>],
+    ) -> DiagnosticResult<S, Vec<(Key<Node>, S)>> {
+        let mut positive = Vec::new();
+        let mut natives = Vec::new();
+        let mut negative = Vec::new();
+        for (idx, literal) in rule.body.into_iter().enumerate() {
+            match literal.inner {
+                Literal::Atom(Atom {
+                    relation:
+                        Spanned {
+                            inner: ResourceId::Builtin(builtin),
+                            ..
+                        },
+                    pattern,
+                }) => natives.push((literal.span, builtin, pattern.into_leaves())),
+                Literal::Atom(atom) => positive.push(self.atom(atom)),
+                Literal::Negated(Atom {
+                    relation:
+                        Spanned {
+                            inner: ResourceId::Builtin(builtin),
+                            ..
+                        },
+                    ..
+                }) => {
+                    return Err(Diagnostic {
+                        span: literal.span.clone(),
+                        kind: DiagnosticKind::Error,
+                        message: format!("Built-in relation {builtin} cannot be negated"),
+                        labels: vec![Spanned {
+                            span: literal.span,
+                            inner: "Negated here.".to_string(),
+                        }],
+                    });
+                }
+                Literal::Negated(atom) => negative.push((literal.span, self.atom(atom))),
+                Literal::Aggregate(aggregate) => {
+                    positive.push(self.aggregate(literal.span, aggregate, &outer[idx])?);
+                }
+            }
+        }
+
+        // rules without relations to join start from a single empty tuple
+        let mut positive = positive.into_iter();
+        let mut src = match positive.next() {
+            Some(first) => positive.fold(first, |lhs, rhs| self.join(lhs, rhs)),
+            None => Bound {
+                key: self.push(Node::Unit),
+                vars: vec![],
+            },
+        };
+
+        // evaluate built-in relations in whichever order binds their terms
+        let mut ranges = Vec::new();
+        while !natives.is_empty() {
+            let next = natives.iter().position(|(_span, builtin, leaves)| {
+                let bound: Vec<_> = leaves
+                    .iter()
+                    .map(|term| match term {
+                        AnyTerm::Variable(var) => src.vars.contains(var),
+                        AnyTerm::Value(_) => true,
+                    })
+                    .collect();
+
+                builtin.can_eval(&bound)
+            });
+
+            let Some(next) = next else {
+                let (span, builtin, _leaves) = natives.swap_remove(0);
+                return Err(Diagnostic {
+                    span: span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: format!("Not enough bound terms to evaluate {builtin}"),
+                    labels: vec![Spanned {
+                        span,
+                        inner: "Bind more of these terms in other atoms.".to_string(),
+                    }],
+                });
+            };
+
+            let (span, builtin, leaves) = natives.remove(next);
+
+            // arguments bound by other atoms are only known during evaluation
+            let args: Option<Vec<_>> = leaves
+                .iter()
+                .map(|term| match term {
+                    AnyTerm::Value(val) => Some(Some(val.clone())),
+                    AnyTerm::Variable(var) if src.vars.contains(var) => None,
+                    AnyTerm::Variable(_) => Some(None),
+                })
+                .collect();
+
+            match args {
+                Some(args) => {
+                    if let Some(d) = builtin.limit_diagnostic(span, &args) {
+                        return Err(d);
+                    }
+
+                    src = self.native(src, builtin, leaves).1;
+                }
+                None => {
+                    let (key, bound) = self.native(src, builtin, leaves);
+                    if builtin == Builtin::Range {
+                        ranges.push((key, span));
+                    }
+
+                    src = bound;
+                }
+            }
+        }
+
+        for (span, rhs) in negative {
+            let Some(antijoin) = self.antijoin(src.clone(), rhs) else {
+                return Err(Diagnostic {
+                    span: span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: "Negated terms are not bound".to_string(),
+                    labels: vec![Spanned {
+                        span,
+                        inner: "Bind these terms in other atoms.".to_string(),
+                    }],
+                });
+            };
+
+            src = antijoin;
+        }
+
+        // constant head terms are appended after the bound variables
+        let head = rule.head.inner.pattern.into_leaves();
+        let mut constants = Vec::new();
+        let mut map = Vec::new();
+        for term in head {
+            match term {
+                AnyTerm::Variable(var) => match src.vars.iter().position(|el| *el == var) {
+                    Some(idx) => map.push(idx),
+                    None => {
+                        let variable = &variables[var];
+                        return Err(Diagnostic {
+                            span: rule.head.span.clone(),
+                            kind: DiagnosticKind::Error,
+                            message: format!(
+                                "Head variable {} is not bound by the body",
+                                variable.inner
+                            ),
+                            labels: vec![Spanned {
+                                span: variable.span.clone(),
+                                inner: "Bind this variable in a body atom.".to_string(),
+                            }],
+                        });
+                    }
+                },
+                AnyTerm::Value(val) => {
+                    map.push(src.vars.len() + constants.len());
+                    constants.push(val);
+                }
+            }
+        }
+
+        let src = if constants.is_empty() {
+            src.key
+        } else {
+            self.push(Node::Extend {
+                src: src.key,
+                values: Tuple::from(constants),
+            })
+        };
+
+        self.push(Node::StoreRelation {
+            src,
+            dst: rule.head.inner.relation.inner,
+            map: Arc::from(map),
+        });
+
+        Ok(ranges)
+    }
+
+    fn atom<S>(&mut self, atom: Atom<S, ResourceId, Term<usize>>) -> Bound {
+        let resource = atom.relation.inner;
+        let key = self.push(Node::LoadRelation { resource });
+        self.select(key, &atom.pattern.into_leaves())
+    }
+
+    /// Lowers an aggregate, grouping by the atom's variables in the given set.
+    fn aggregate<S: Clone>(
+        &mut self,
+        span: S,
+        aggregate: Aggregation<S, ResourceId, Term<usize>>,
+        outer: &BTreeSet<usize>,
+    ) -> DiagnosticResult<S, Bound> {
+        let src = self.atom(aggregate.atom);
+
+        let (group, local): (Vec<usize>, Vec<usize>) =
+            src.vars.iter().partition(|var| outer.contains(var));
+
+        let vars: Vec<_> = group.iter().chain(local.iter()).copied().collect();
+        let key = self.rearrange(&src, vars.iter());
+
+        let target = aggregate.target.and_then(|target| match target.inner {
+            AnyTerm::Variable(var) => vars.iter().position(|el| *el == var),
+            AnyTerm::Value(_) => None,
+        });
+
+        let reducer = match (aggregate.kind.inner, target) {
+            (AggregateKind::Count, _) => Reducer::Count,
+            (AggregateKind::Sum, Some(target)) => Reducer::Sum(target),
+            (AggregateKind::Min, Some(target)) => Reducer::Min(target),
+            (AggregateKind::Max, Some(target)) => Reducer::Max(target),
+            (kind, None) => {
+                return Err(Diagnostic {
+                    span: span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: format!("{kind:?} target does not appear within its atom"),
+                    labels: vec![Spanned {
+                        span,
+                        inner: "Aggregated here.".to_string(),
+                    }],
+                });
+            }
+        };
+
+        let AnyTerm::Variable(result) = aggregate.result.inner else {
+            unreachable!("aggregate results are always variables");
+        };
+
+        let key = self.push(Node::Reduce {
+            src: key,
+            num: group.len(),
+            reducer,
+        });
+
+        let vars = group.into_iter().chain([result]).collect();
+        Ok(Bound { key, vars })
+    }
+
+    /// Lowers a built-in relation, returning its native node and its terms.
+    fn native(
+        &mut self,
+        src: Bound,
+        builtin: Builtin,
+        leaves: Vec<Term<usize>>,
+    ) -> (Key<Node>, Bound) {
+        let mut terms: Vec<_> = src.vars.iter().copied().map(AnyTerm::Variable).collect();
+
+        let args = leaves
+            .into_iter()
+            .map(|term| match term {
+                AnyTerm::Variable(var) => match src.vars.iter().position(|el| *el == var) {
+                    Some(idx) => Argument::Term(idx),
+                    None => {
+                        terms.push(AnyTerm::Variable(var));
+                        Argument::Free
+                    }
+                },
+                AnyTerm::Value(val) => Argument::Constant(val),
+            })
+            .collect();
+
+        let key = self.push(Node::Native {
+            src: src.key,
+            builtin,
+            args,
+        });
+
+        (key, self.select(key, &terms))
+    }
+
+    /// Binds the terms of a node to variables.
+    ///
+    /// Constant and repeated terms select only matching tuples.
+    fn select(&mut self, key: Key<Node>, terms: &[Term<usize>]) -> Bound {
+        let mut vars = Vec::new();
+        let mut map = Vec::new();
+        let mut conditions = Vec::new();
+        for (idx, term) in terms.iter().enumerate() {
+            match term {
+                AnyTerm::Variable(var) => match vars.iter().position(|el| el == var) {
+                    Some(first) => conditions.push(Condition::Equal(map[first], idx)),
+                    None => {
+                        vars.push(*var);
+                        map.push(idx);
+                    }
+                },
+                AnyTerm::Value(val) => conditions.push(Condition::Constant(idx, val.clone())),
+            }
+        }
+
+        let key = if conditions.is_empty() {
+            key
+        } else {
+            self.push(Node::Filter {
+                src: key,
+                conditions: Arc::from(conditions),
+            })
+        };
+
+        let key = self.project(key, terms.len(), map);
+        Bound { key, vars }
+    }
+
+    fn join(&mut self, lhs: Bound, rhs: Bound) -> Bound {
+        let shared: Vec<_> = lhs
+            .vars
+            .iter()
+            .filter(|var| rhs.vars.contains(var))
+            .copied()
+            .collect();
+
+        let lhs_rest: Vec<_> = lhs
+            .vars
+            .iter()
+            .filter(|var| !shared.contains(var))
+            .copied()
+            .collect();
+
+        let rhs_rest: Vec<_> = rhs
+            .vars
+            .iter()
+            .filter(|var| !shared.contains(var))
+            .copied()
+            .collect();
+
+        let lhs = self.rearrange(&lhs, shared.iter().chain(lhs_rest.iter()));
+        let rhs = self.rearrange(&rhs, shared.iter().chain(rhs_rest.iter()));
+
+        let key = self.push(Node::Join {
+            lhs,
+            rhs,
+            num: shared.len(),
+        });
+
+        let vars = shared.into_iter().chain(lhs_rest).chain(rhs_rest).collect();
+
+        Bound { key, vars }
+    }
+
+    /// Removes the tuples of a bound node that match another.
+    ///
+    /// Fails if the right-hand node has variables that the left does not bind.
+    fn antijoin(&mut self, lhs: Bound, rhs: Bound) -> Option<Bound> {
+        if !rhs.vars.iter().all(|var| lhs.vars.contains(var)) {
+            return None;
+        }
+
+        let lhs_rest: Vec<_> = lhs
+            .vars
+            .iter()
+            .filter(|var| !rhs.vars.contains(var))
+            .copied()
+            .collect();
+
+        let lhs = self.rearrange(&lhs, rhs.vars.iter().chain(lhs_rest.iter()));
+
+        let key = self.push(Node::Antijoin {
+            lhs,
+            rhs: rhs.key,
+            num: rhs.vars.len(),
+        });
+
+        let vars = rhs.vars.into_iter().chain(lhs_rest).collect();
+
+        Some(Bound { key, vars })
+    }
+
+    /// Projects a bound node so that its terms follow the given variable order.
+    fn rearrange<'a>(&mut self, src: &Bound, vars: impl Iterator<Item = &'a usize>) -> Key<Node> {
+        let map = vars
+            .map(|var| src.vars.iter().position(|el| el == var).unwrap())
+            .collect();
+
+        self.project(src.key, src.vars.len(), map)
+    }
+
+    /// Projects a node, skipping the projection if it would be the identity.
+    fn project(&mut self, src: Key<Node>, arity: usize, map: Vec<usize>) -> Key<Node> {
+        let is_identity = map.len() == arity && map.iter().enumerate().all(|(idx, el)| idx == *el);
+
+        if is_identity {
+            return src;
+        }
+
+        self.push(Node::Project {
+            src,
+            map: Arc::from(map),
+        })
+    }
+}
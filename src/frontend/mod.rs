@@ -17,8 +17,11 @@
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
 pub mod dataflow;
+pub mod desugar;
 pub mod diagnostics;
+pub mod estimate;
 pub mod logic;
+pub mod lower;
 pub mod parser;
 pub mod span;
 pub mod types;
@@ -22,6 +22,7 @@ use tree_sitter::{InputEdit, Node, Parser, Range, Tree, TreeCursor};
 use tree_sitter_fulcrum::LANGUAGE;
 
 use crate::frontend::{
+    desugar::desugar,
     span::{MapSpan, Span, Spanless, Spanned},
     types::*,
 };
@@ -62,7 +63,7 @@ impl Module {
                 while let Some(node) = stack.pop() {
                     stack.extend(node.children(&mut cursor).filter(|node| node.has_error()));
 
-                    if node.is_error() {
+                    if node.is_error() || node.is_missing() {
                         items.push(ModuleItem::Diagnostic(Diagnostic {
                             span: node.range(),
                             kind: DiagnosticKind::Error,
@@ -95,12 +96,78 @@ impl Module {
                     &node,
                     &mut cursor,
                 ))),
-                "comment" | "import" => continue,
+                "objective" => items.push(ModuleItem::Objective(Parse::parse(
+                    &self.src,
+                    &node,
+                    &mut cursor,
+                ))),
+                "import" => items.push(ModuleItem::Import(Parse::parse(
+                    &self.src,
+                    &node,
+                    &mut cursor,
+                ))),
+                "type_declaration" => items.push(ModuleItem::Type(Parse::parse(
+                    &self.src,
+                    &node,
+                    &mut cursor,
+                ))),
+                "enum_declaration" => items.push(ModuleItem::Enum(Parse::parse(
+                    &self.src,
+                    &node,
+                    &mut cursor,
+                ))),
+                "comment" => continue,
                 other => unimplemented!("unexpected node {other:?}"),
             }
         }
 
-        items.map_span(&mut |range| range.into())
+        let text = |range: &Range| self.src[range.start_byte..range.end_byte].to_string();
+        items
+            .into_iter()
+            .flat_map(|item| desugar(item, &text))
+            .collect::<Vec<_>>()
+            .map_span(&mut |range| range.into())
+    }
+}
+
+impl Parse for Import<Range, String> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let module: Vec<_> = node.children_by_field_name("module", cursor).collect();
+        let relations: Vec<_> = node.children_by_field_name("relation", cursor).collect();
+
+        let module = module
+            .into_iter()
+            .map(|node| Parse::parse(src, &node, cursor))
+            .collect();
+
+        let relations = relations
+            .into_iter()
+            .map(|node| Parse::parse(src, &node, cursor))
+            .collect();
+
+        Self { module, relations }
+    }
+}
+
+impl Parse for TypeDeclaration<Range, String> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let relation = Parse::parse(src, &node.child_by_field_name("relation").unwrap(), cursor);
+        let ty = Parse::parse(src, &node.child_by_field_name("type").unwrap(), cursor);
+        Self { relation, ty }
+    }
+}
+
+impl Parse for EnumDeclaration<Range, String> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let name = Parse::parse(src, &node.child_by_field_name("name").unwrap(), cursor);
+        let variants: Vec<_> = node.children_by_field_name("variant", cursor).collect();
+
+        let variants = variants
+            .into_iter()
+            .map(|node| Parse::parse(src, &node, cursor))
+            .collect();
+
+        Self { name, variants }
     }
 }
 
@@ -130,6 +197,12 @@ impl Parse for Rule<Range, String, String> {
 
 impl Parse for Constraint<Range, String, String> {
     fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let soft = node
+            .children_by_field_name("soft", cursor)
+            .find(|node| node.kind() == "integer");
+
+        let soft = soft.map(|node| Parse::parse(src, &node, cursor));
+
         let captures = node
             .child_by_field_name("captures")
             .map(|node| Parse::parse(src, &node, cursor))
@@ -151,6 +224,7 @@ impl Parse for Constraint<Range, String, String> {
             .collect();
 
         Self {
+            soft,
             captures,
             kind,
             body,
@@ -158,6 +232,52 @@ impl Parse for Constraint<Range, String, String> {
     }
 }
 
+impl Parse for Objective<Range, String, String> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let sense = node
+            .child_by_field_name("sense")
+            .map(|node| Parse::parse(src, &node, cursor))
+            .unwrap();
+
+        let priority = node
+            .children_by_field_name("priority", cursor)
+            .find(|node| node.kind() == "integer");
+
+        let priority = priority.map(|node| Parse::parse(src, &node, cursor));
+
+        let kind = node.child_by_field_name("kind").unwrap();
+        let target = kind.child_by_field_name("target");
+
+        let body: Vec<_> = node
+            .children_by_field_name("body", cursor)
+            .filter(|node| node.is_named())
+            .collect();
+
+        let body = body
+            .into_iter()
+            .map(|node| Parse::parse(src, &node, cursor))
+            .collect();
+
+        Self {
+            sense,
+            priority,
+            kind: Parse::parse(src, &kind, cursor),
+            target: target.map(|target| Parse::parse(src, &target, cursor)),
+            body,
+        }
+    }
+}
+
+impl Parse for ObjectiveSense {
+    fn parse<'tree>(_src: &str, node: &Node<'tree>, _cursor: &mut TreeCursor<'tree>) -> Self {
+        match node.kind() {
+            "minimize" => ObjectiveSense::Minimize,
+            "maximize" => ObjectiveSense::Maximize,
+            other => unreachable!("unexpected node kind {other:?}"),
+        }
+    }
+}
+
 impl Parse for ConstraintKind {
     fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
         use ConstraintKind::*;
@@ -188,6 +308,68 @@ impl Parse for CardinalityConstraintKind {
     }
 }
 
+impl Parse for Literal<Range, String, Term<String>> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        match node.kind() {
+            "atom" => Self::Atom(Parse::parse(src, node, cursor)),
+            "negation" => {
+                let atom = node.child_by_field_name("atom").unwrap();
+                Self::Negated(Parse::parse(src, &atom, cursor))
+            }
+            "aggregate" => Self::Aggregate(Parse::parse(src, node, cursor)),
+            "comparison" => {
+                // comparisons are atoms of the operator's built-in relation
+                let op = node.child_by_field_name("op").unwrap();
+                let lhs = node.child_by_field_name("lhs").unwrap();
+                let rhs = node.child_by_field_name("rhs").unwrap();
+                Self::Atom(Atom {
+                    relation: Spanned {
+                        span: op.range(),
+                        inner: op.kind().to_string(),
+                    },
+                    pattern: Spanned {
+                        span: node.range(),
+                        inner: Pattern::Tuple(vec![
+                            Parse::parse(src, &lhs, cursor),
+                            Parse::parse(src, &rhs, cursor),
+                        ]),
+                    },
+                })
+            }
+            other => unreachable!("unexpected node kind {other:?}"),
+        }
+    }
+}
+
+impl Parse for Aggregation<Range, String, Term<String>> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let result = node.child_by_field_name("result").unwrap();
+        let kind = node.child_by_field_name("kind").unwrap();
+        let target = kind.child_by_field_name("target");
+        let atom = node.child_by_field_name("atom").unwrap();
+
+        Self {
+            result: Parse::parse(src, &result, cursor),
+            kind: Parse::parse(src, &kind, cursor),
+            target: target.map(|target| Parse::parse(src, &target, cursor)),
+            atom: Parse::parse(src, &atom, cursor),
+        }
+    }
+}
+
+impl Parse for AggregateKind {
+    fn parse<'tree>(_src: &str, node: &Node<'tree>, _cursor: &mut TreeCursor<'tree>) -> Self {
+        use AggregateKind::*;
+        match node.kind() {
+            "count" => Count,
+            "sum" => Sum,
+            "min" => Min,
+            "max" => Max,
+            other => unreachable!("unexpected node kind {other:?}"),
+        }
+    }
+}
+
 impl Parse for Atom<Range, String, Term<String>> {
     fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
         let relation_node = node.child_by_field_name("relation").unwrap();
@@ -209,11 +391,48 @@ impl<T: Parse> Parse for Pattern<Range, T> {
         let node = &node;
         match node.kind() {
             "tuple" => Self::Tuple(Parse::parse(src, node, cursor)),
+            "record" => {
+                let mut fields: Vec<Field<Range, T>> = Parse::parse(src, node, cursor);
+                fields.sort_by(|lhs, rhs| lhs.key.inner.cmp(&rhs.key.inner));
+                Self::Record(fields)
+            }
+            "expression" => Self::Expression(Box::new(Parse::parse(src, node, cursor))),
             _ => Self::Leaf(Parse::parse(src, node, cursor)),
         }
     }
 }
 
+impl<T: Parse> Parse for Expression<Range, T> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let op = Parse::parse(src, &node.child_by_field_name("op").unwrap(), cursor);
+        let lhs = Parse::parse(src, &node.child_by_field_name("lhs").unwrap(), cursor);
+        let rhs = Parse::parse(src, &node.child_by_field_name("rhs").unwrap(), cursor);
+        Self { op, lhs, rhs }
+    }
+}
+
+impl Parse for Operator {
+    fn parse<'tree>(_src: &str, node: &Node<'tree>, _cursor: &mut TreeCursor<'tree>) -> Self {
+        use Operator::*;
+        match node.kind() {
+            "+" => Add,
+            "-" => Sub,
+            "*" => Mul,
+            "/" => Div,
+            "%" => Mod,
+            other => unreachable!("unexpected node kind {other:?}"),
+        }
+    }
+}
+
+impl<T: Parse> Parse for Field<Range, T> {
+    fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
+        let key = Parse::parse(src, &node.child_by_field_name("key").unwrap(), cursor);
+        let value = Parse::parse(src, &node.child_by_field_name("value").unwrap(), cursor);
+        Self { key, value }
+    }
+}
+
 impl<T: Parse> Parse for Term<T> {
     fn parse<'tree>(src: &str, node: &Node<'tree>, cursor: &mut TreeCursor<'tree>) -> Self {
         let node = &node;
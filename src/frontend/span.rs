@@ -71,14 +71,57 @@ where
     fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
         use ModuleItem::*;
         match self {
+            Import(el) => Import(el.map_span(cb)),
+            Type(el) => Type(el.map_span(cb)),
+            Enum(el) => Enum(el.map_span(cb)),
             Rule(el) => Rule(el.map_span(cb)),
             Decision(el) => Decision(el.map_span(cb)),
             Constraint(el) => Constraint(el.map_span(cb)),
+            Objective(el) => Objective(el.map_span(cb)),
             Diagnostic(el) => Diagnostic(el.map_span(cb)),
         }
     }
 }
 
+impl<S, O, R> MapSpan<S, O> for Import<S, R>
+where
+    R: MapSpan<S, O>,
+{
+    type Target = Import<O, R::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let module = self.module.map_span(cb);
+        let relations = self.relations.map_span(cb);
+        Import { module, relations }
+    }
+}
+
+impl<S, O, R> MapSpan<S, O> for TypeDeclaration<S, R>
+where
+    R: MapSpan<S, O>,
+{
+    type Target = TypeDeclaration<O, R::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let relation = self.relation.map_span(cb);
+        let ty = self.ty.map_span(cb);
+        TypeDeclaration { relation, ty }
+    }
+}
+
+impl<S, O, R> MapSpan<S, O> for EnumDeclaration<S, R>
+where
+    R: MapSpan<S, O>,
+{
+    type Target = EnumDeclaration<O, R::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let name = self.name.map_span(cb);
+        let variants = self.variants.map_span(cb);
+        EnumDeclaration { name, variants }
+    }
+}
+
 impl<S, O, R, T> MapSpan<S, O> for Decision<S, R, T>
 where
     R: MapSpan<S, O>,
@@ -113,11 +156,13 @@ where
     type Target = Constraint<O, R::Target, T::Target>;
 
     fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let soft = self.soft.map(|soft| soft.map_span(cb));
         let captures = self.captures.map_span(cb);
         let kind = self.kind.map_span(cb);
         let body = self.body.map_span(cb);
 
         Constraint {
+            soft,
             captures,
             kind,
             body,
@@ -127,6 +172,73 @@ where
 
 impl Spanless for ConstraintKind {}
 
+impl<S, O, R, T> MapSpan<S, O> for Objective<S, R, T>
+where
+    R: MapSpan<S, O>,
+    T: MapSpan<S, O>,
+{
+    type Target = Objective<O, R::Target, T::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let sense = self.sense.map_span(cb);
+        let priority = self.priority.map(|priority| priority.map_span(cb));
+        let kind = self.kind.map_span(cb);
+        let target = self.target.map(|target| target.map_span(cb));
+        let body = self.body.map_span(cb);
+
+        Objective {
+            sense,
+            priority,
+            kind,
+            target,
+            body,
+        }
+    }
+}
+
+impl Spanless for ObjectiveSense {}
+
+impl<S, O, R, T> MapSpan<S, O> for Literal<S, R, T>
+where
+    R: MapSpan<S, O>,
+    T: MapSpan<S, O>,
+{
+    type Target = Literal<O, R::Target, T::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        use Literal::*;
+        match self {
+            Atom(el) => Atom(el.map_span(cb)),
+            Negated(el) => Negated(el.map_span(cb)),
+            Aggregate(el) => Aggregate(el.map_span(cb)),
+        }
+    }
+}
+
+impl<S, O, R, T> MapSpan<S, O> for Aggregation<S, R, T>
+where
+    R: MapSpan<S, O>,
+    T: MapSpan<S, O>,
+{
+    type Target = Aggregation<O, R::Target, T::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let result = self.result.map_span(cb);
+        let kind = self.kind.map_span(cb);
+        let target = self.target.map(|target| target.map_span(cb));
+        let atom = self.atom.map_span(cb);
+
+        Aggregation {
+            result,
+            kind,
+            target,
+            atom,
+        }
+    }
+}
+
+impl Spanless for AggregateKind {}
+
 impl<S, O, R, T> MapSpan<S, O> for Atom<S, R, T>
 where
     R: MapSpan<S, O>,
@@ -152,10 +264,39 @@ where
         match self {
             Leaf(el) => Leaf(el.map_span(cb)),
             Tuple(el) => Tuple(el.map_span(cb)),
+            Record(el) => Record(el.map_span(cb)),
+            Expression(el) => Expression(Box::new((*el).map_span(cb))),
         }
     }
 }
 
+impl<S, O, T> MapSpan<S, O> for Expression<S, T>
+where
+    T: MapSpan<S, O>,
+{
+    type Target = Expression<O, T::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let op = self.op.map_span(cb);
+        let lhs = self.lhs.map_span(cb);
+        let rhs = self.rhs.map_span(cb);
+        Expression { op, lhs, rhs }
+    }
+}
+
+impl<S, O, T> MapSpan<S, O> for Field<S, T>
+where
+    T: MapSpan<S, O>,
+{
+    type Target = Field<O, T::Target>;
+
+    fn map_span(self, cb: &mut impl FnMut(S) -> O) -> Self::Target {
+        let key = self.key.map_span(cb);
+        let value = self.value.map_span(cb);
+        Field { key, value }
+    }
+}
+
 impl<S, O, T, V> MapSpan<S, O> for AnyTerm<T, V>
 where
     T: MapSpan<S, O>,
@@ -175,6 +316,8 @@ where
 impl Spanless for Value {}
 impl Spanless for PrimitiveType {}
 impl Spanless for usize {}
+impl Spanless for Operator {}
+impl Spanless for ResourceId {}
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Spanned<S, T> {
@@ -17,6 +17,7 @@
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
+    collections::BTreeMap,
     fmt::{Debug, Display},
     hash::Hash,
     sync::Arc,
@@ -25,7 +26,10 @@ use std::{
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-pub use crate::frontend::{diagnostics::*, span::Spanned};
+pub use crate::{
+    backend::builtins::Builtin,
+    frontend::{diagnostics::*, span::Spanned},
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct IndexedItem<S, R> {
@@ -36,9 +40,13 @@ pub struct IndexedItem<S, R> {
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum ModuleItem<S, R, T> {
+    Import(Import<S, R>),
+    Type(TypeDeclaration<S, R>),
+    Enum(EnumDeclaration<S, R>),
     Rule(Rule<S, R, T>),
     Decision(Decision<S, R, T>),
     Constraint(Constraint<S, R, T>),
+    Objective(Objective<S, R, T>),
     Diagnostic(Diagnostic<S>),
 }
 
@@ -58,25 +66,73 @@ impl<S, R, T> ModuleItem<S, R, T> {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Import<S, R> {
+    pub module: Vec<Spanned<S, String>>,
+    pub relations: Vec<Spanned<S, R>>,
+}
+
+/// An explicit type for a relation, given as a pattern of type names.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct TypeDeclaration<S, R> {
+    pub relation: Spanned<S, R>,
+    pub ty: SpannedPattern<S, String>,
+}
+
+impl<S, R: Display> Display for TypeDeclaration<S, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ty.inner {
+            Pattern::Tuple(_) => write!(f, "type {}{}.", self.relation.inner, self.ty.inner),
+            _ => write!(f, "type {} {}.", self.relation.inner, self.ty.inner),
+        }
+    }
+}
+
+/// A named type of symbols, which is also the unary relation of its variants.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct EnumDeclaration<S, R> {
+    pub name: Spanned<S, R>,
+    pub variants: Vec<Spanned<S, String>>,
+}
+
+/// A relation brought into a file by an import.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct ImportedRelation<S> {
+    /// The relation as it is named within the importing file.
+    pub local: ResourceId,
+
+    /// The relation that it refers to, spanned by its name within the import.
+    pub target: Spanned<S, ResourceId>,
+
+    /// The path of the module it is imported from, spanned by the first name
+    /// of the path, if that module is another file of the project.
+    pub file: Option<Spanned<S, String>>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Decision<S, R, T>(pub Rule<S, R, T>);
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Rule<S, R, T> {
     pub head: Spanned<S, Atom<S, R, Term<T>>>,
-    pub body: Vec<Spanned<S, Atom<S, R, Term<T>>>>,
+    pub body: Vec<Spanned<S, Literal<S, R, Term<T>>>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Constraint<S, R, T> {
+    /// The penalty for violating this constraint, if it is soft.
+    pub soft: Option<Spanned<S, i64>>,
     pub captures: Vec<Spanned<S, T>>,
     pub kind: Spanned<S, ConstraintKind>,
-    pub body: Vec<Spanned<S, Atom<S, R, Term<T>>>>,
+    pub body: Vec<Spanned<S, Literal<S, R, Term<T>>>>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum ConstraintKind {
+    /// Every instance of the body holds, or none of them do.
     Uniform,
+
+    /// Bounds the number of instances of the body that hold.
     Cardinality(CardinalityConstraintKind, i64),
 }
 
@@ -87,6 +143,85 @@ pub enum CardinalityConstraintKind {
     AtLeast,
 }
 
+/// An aggregate over the instances of a body to minimize or maximize.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Objective<S, R, T> {
+    pub sense: Spanned<S, ObjectiveSense>,
+
+    /// Objectives of higher priority are optimized before those of lower
+    /// priority, including soft constraints, which have a priority of zero.
+    pub priority: Option<Spanned<S, i64>>,
+
+    pub kind: Spanned<S, AggregateKind>,
+    pub target: Option<Spanned<S, T>>,
+    pub body: Vec<Spanned<S, Literal<S, R, Term<T>>>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum ObjectiveSense {
+    Minimize,
+    Maximize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Literal<S, R, T> {
+    /// Matches the tuples of a relation.
+    Atom(Atom<S, R, T>),
+
+    /// Matches when no tuple of a relation matches.
+    Negated(Atom<S, R, T>),
+
+    /// Binds a variable to an aggregate over the tuples of a relation.
+    Aggregate(Aggregation<S, R, T>),
+}
+
+impl<S, R, T> Literal<S, R, T> {
+    pub fn atom(&self) -> &Atom<S, R, T> {
+        match self {
+            Literal::Atom(atom) | Literal::Negated(atom) => atom,
+            Literal::Aggregate(aggregate) => &aggregate.atom,
+        }
+    }
+
+    pub fn into_atom(self) -> Atom<S, R, T> {
+        match self {
+            Literal::Atom(atom) | Literal::Negated(atom) => atom,
+            Literal::Aggregate(aggregate) => aggregate.atom,
+        }
+    }
+
+    /// Tests if this literal needs the complete contents of its relation.
+    ///
+    /// Such literals may only reference relations in a lower stratum.
+    pub fn is_nonmonotonic(&self) -> bool {
+        !matches!(self, Literal::Atom(_))
+    }
+}
+
+/// An aggregate over the tuples of an atom.
+///
+/// The tuples are grouped by the atom's variables that also appear elsewhere
+/// in the item. The remaining variables are local to the aggregate.
+///
+/// Groups are only formed by the tuples of the atom, so a group without any
+/// matching tuples has no result at all rather than a count or sum of zero.
+/// A sum that overflows an `i64` likewise has no result.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Aggregation<S, R, T> {
+    pub result: Spanned<S, T>,
+    pub kind: Spanned<S, AggregateKind>,
+    pub target: Option<Spanned<S, T>>,
+    pub atom: Atom<S, R, T>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum AggregateKind {
+    Count,
+    Sum,
+    Min,
+    Max,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Atom<S, R, T> {
     pub relation: Spanned<S, R>,
@@ -101,6 +236,50 @@ pub type SpannedPattern<S, T> = Spanned<S, Pattern<S, T>>;
 pub enum Pattern<S, T> {
     Leaf(T),
     Tuple(Vec<Spanned<S, Self>>),
+    /// Fields sorted by key, so that records flatten in a canonical order.
+    Record(Vec<Field<S, T>>),
+    /// Arithmetic on integers, which is desugared into built-in atoms.
+    Expression(Box<Expression<S, T>>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Expression<S, T> {
+    pub op: Spanned<S, Operator>,
+    pub lhs: SpannedPattern<S, T>,
+    pub rhs: SpannedPattern<S, T>,
+}
+
+impl<S, T: Display> Display for Expression<S, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.lhs, self.op.inner, self.rhs)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Sub => write!(f, "-"),
+            Operator::Mul => write!(f, "*"),
+            Operator::Div => write!(f, "/"),
+            Operator::Mod => write!(f, "%"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Field<S, T> {
+    pub key: Spanned<S, String>,
+    pub value: SpannedPattern<S, T>,
 }
 
 impl<S, T: Display> Display for Pattern<S, T> {
@@ -116,6 +295,16 @@ impl<S, T: Display> Display for Pattern<S, T> {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Pattern::Record(fields) => write!(
+                f,
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|field| format!("{}: {}", field.key.inner, field.value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Pattern::Expression(expr) => write!(f, "{expr}"),
         }
     }
 }
@@ -129,25 +318,65 @@ pub enum AnyTerm<T, V> {
     Value(V),
 }
 
+impl<T: Display, V: Display> Display for AnyTerm<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnyTerm::Variable(var) => write!(f, "{var}"),
+            AnyTerm::Value(val) => write!(f, "{val}"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum Value {
     Symbol(String),
     Integer(i64),
 }
 
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Symbol(symbol) => write!(f, "{symbol}"),
+            Value::Integer(integer) => write!(f, "{integer}"),
+        }
+    }
+}
+
+/// The named type of each symbol declared as one of its variants.
+pub type Variants = BTreeMap<String, String>;
+
 impl Value {
-    pub fn ty(&self) -> PrimitiveType {
+    pub fn ty(&self, variants: &Variants) -> PrimitiveType {
         match self {
-            Value::Symbol(_) => PrimitiveType::Symbol,
+            Value::Symbol(symbol) => match variants.get(symbol) {
+                Some(name) => PrimitiveType::Named(name.clone()),
+                None => PrimitiveType::Symbol,
+            },
             Value::Integer(_) => PrimitiveType::Integer,
         }
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum PrimitiveType {
     Symbol,
     Integer,
+
+    /// A declared type whose values are only its variant symbols.
+    Named(String),
+}
+
+impl PrimitiveType {
+    /// Looks up a type by the name it is declared with.
+    ///
+    /// Any name besides Symbol and Integer refers to a named type.
+    pub fn from_name(name: String) -> Self {
+        match name.as_str() {
+            "Symbol" => PrimitiveType::Symbol,
+            "Integer" => PrimitiveType::Integer,
+            _ => PrimitiveType::Named(name),
+        }
+    }
 }
 
 impl Display for PrimitiveType {
@@ -155,6 +384,7 @@ impl Display for PrimitiveType {
         match self {
             PrimitiveType::Symbol => write!(f, "Symbol"),
             PrimitiveType::Integer => write!(f, "Integer"),
+            PrimitiveType::Named(name) => write!(f, "{name}"),
         }
     }
 }
@@ -162,4 +392,32 @@ impl Display for PrimitiveType {
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum ResourceId {
     SourceSymbol(Arc<Url>, String),
+    Builtin(Builtin),
+}
+
+impl ResourceId {
+    /// The relation named `sym` in the file at `url`, or the built-in
+    /// relation of an operator.
+    pub fn new(url: Arc<Url>, sym: String) -> Self {
+        match Builtin::from_operator(&sym) {
+            Some(builtin) => ResourceId::Builtin(builtin),
+            None => ResourceId::SourceSymbol(url, sym),
+        }
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        match self {
+            ResourceId::SourceSymbol(url, _) => Some(url),
+            ResourceId::Builtin(_) => None,
+        }
+    }
+}
+
+impl Display for ResourceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceId::SourceSymbol(_, name) => write!(f, "{name}"),
+            ResourceId::Builtin(builtin) => write!(f, "{builtin}"),
+        }
+    }
 }
@@ -16,6 +16,9 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
+#![allow(clippy::type_complexity)]
+
 pub mod backend;
 pub mod frontend;
+pub mod solver;
 pub mod utils;
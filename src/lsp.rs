@@ -17,18 +17,26 @@
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     sync::Arc,
 };
 
 use archimedes::{
+    backend::{
+        builtins::Builtin,
+        dataflow::{backend_worker, BackendResult, BackendUpdate},
+        types::{Node, ResourceId},
+    },
     frontend::{
         dataflow::{frontend_worker, FrontendResultKind, FrontendUpdate},
+        diagnostics::{Diagnostic, DiagnosticKind},
+        estimate::{estimate, Estimate, Thresholds},
         parser::Module,
-        span::{MapSpan, Span},
-        types::ModuleItem,
+        span::{MapSpan, Point, Span},
+        types::{IndexedItem, ModuleItem},
     },
-    utils::run_dataflow,
+    solver::{explain::explain, ground::Program, incremental::Incremental},
+    utils::{run_dataflow, Key},
 };
 use flume::Sender;
 use tokio::sync::Mutex;
@@ -40,29 +48,83 @@ use tower_lsp::{
 
 type FileMap = Arc<Mutex<HashMap<Url, File>>>;
 
+type ProjectRef = Arc<Mutex<Project>>;
+
 pub struct LspBackend {
     pub client: Client,
     pub files: FileMap,
+    pub project: ProjectRef,
     pub update_tx: Sender<Vec<FrontendUpdate>>,
+    pub thresholds: Thresholds,
 }
 
 impl LspBackend {
-    pub fn new(client: Client) -> Self {
+    /// Creates a language server, importing modules relative to `root` if
+    /// it is given, or else relative to the client's workspace.
+    pub fn new(client: Client, thresholds: Thresholds, root: Option<Url>) -> Self {
         let files = FileMap::default();
+        let project = ProjectRef::default();
         let (update_tx, update_rx) = flume::unbounded();
 
         let result_rx = run_dataflow(update_rx, frontend_worker);
+        let (lowered_tx, lowered_rx) = flume::unbounded();
+        let relation_rx = run_dataflow(lowered_rx, backend_worker);
         eprintln!("started dataflow");
 
         tokio::spawn({
             let files = files.clone();
+            let project = project.clone();
             async move {
                 let mut file_results: HashMap<Url, Vec<_>> = HashMap::new();
+                let mut lowered = HashSet::new();
                 while let Ok(results) = result_rx.recv_async().await {
                     for (url, result) in results {
                         file_results.entry(url).or_default().push(result);
                     }
 
+                    // only changes to the lowered program are evaluated by the backend
+                    let new_lowered: HashSet<_> = file_results
+                        .values()
+                        .flatten()
+                        .filter_map(|result| match result {
+                            FrontendResultKind::Node(node) => {
+                                Some(BackendUpdate::Node(node.clone(), true))
+                            }
+                            FrontendResultKind::Fact(relation, tuple) => {
+                                Some(BackendUpdate::Fact(relation.clone(), tuple.clone(), true))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+
+                    // the resolved items are solved once their relations are evaluated
+                    let mut project = project.lock().await;
+                    project.resolved = file_results
+                        .values()
+                        .flatten()
+                        .filter_map(|result| match result {
+                            FrontendResultKind::Item(item) => Some(*item.clone()),
+                            _ => None,
+                        })
+                        .collect();
+
+                    project.ranges = file_results
+                        .iter()
+                        .flat_map(|(url, results)| {
+                            results.iter().filter_map(|result| match result {
+                                FrontendResultKind::Range(node, span) => {
+                                    Some((*node, (url.clone(), *span)))
+                                }
+                                _ => None,
+                            })
+                        })
+                        .collect();
+
+                    drop(project);
+
+                    let _ = lowered_tx.send(lowered_diff(&lowered, &new_lowered));
+                    lowered = new_lowered;
+
                     let mut files = files.lock().await;
                     for (url, results) in file_results.clone() {
                         if let Some(file) = files.get_mut(&url) {
@@ -77,17 +139,113 @@ impl LspBackend {
             }
         });
 
+        // every frontend update is followed by the relations evaluated from it
+        tokio::spawn({
+            let files = files.clone();
+            let project = project.clone();
+            async move {
+                // the whole project is solved at once, however many files are open
+                let mut solver = Incremental::new();
+                while let Ok(results) = relation_rx.recv_async().await {
+                    let mut relations: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+                    let mut oversized = BTreeMap::new();
+                    for result in results {
+                        match result {
+                            BackendResult::Tuple(relation, tuple) => {
+                                relations.entry(relation).or_default().insert(tuple);
+                            }
+                            BackendResult::Oversized(node, len) => {
+                                let max = oversized.entry(node).or_insert(len);
+                                *max = len.max(*max);
+                            }
+                        }
+                    }
+
+                    // snapshot the project so that solving does not hold it
+                    let (mut program, ranges) = {
+                        let project = project.lock().await;
+                        (project.program(), project.ranges.clone())
+                    };
+
+                    // ranges bounded during evaluation are only checked by the backend
+                    let oversized: Vec<_> = oversized
+                        .into_iter()
+                        .filter_map(|(node, len)| {
+                            let span = ranges.get(&node)?.clone();
+                            Some(Builtin::oversized_diagnostic(span, len))
+                        })
+                        .collect();
+
+                    // files with errors keep their stale results instead
+                    if files.lock().await.values().all(|file| file.has_errors) {
+                        continue;
+                    }
+
+                    let solved = if !oversized.is_empty() {
+                        Solved {
+                            estimates: Vec::new(),
+                            diagnostics: oversized,
+                        }
+                    } else {
+                        program.relations = relations;
+                        let estimates = estimate(&program.items, &program.relations, &thresholds);
+                        let mut solving = std::mem::take(&mut solver);
+                        let solved = tokio::task::spawn_blocking(move || {
+                            let diagnostics = solve(&mut solving, &program);
+                            (solving, diagnostics)
+                        })
+                        .await;
+
+                        // a panicking solver is replaced with a fresh one
+                        let diagnostics = match solved {
+                            Ok((solved, diagnostics)) => {
+                                solver = solved;
+                                diagnostics
+                            }
+                            Err(_) => Vec::new(),
+                        };
+
+                        Solved {
+                            estimates,
+                            diagnostics,
+                        }
+                    };
+
+                    let mut files = files.lock().await;
+                    for file in files.values_mut() {
+                        file.on_solved(&solved).await;
+                    }
+                }
+            }
+        });
+
+        if let Some(root) = root {
+            project.try_lock().unwrap().set_root(root, &update_tx);
+        }
+
         Self {
             client,
             files,
+            project,
             update_tx,
+            thresholds,
         }
     }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for LspBackend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        let folder = params
+            .workspace_folders
+            .and_then(|folders| folders.into_iter().next())
+            .map(|folder| folder.uri);
+
+        #[allow(deprecated)]
+        if let Some(root) = folder.or(params.root_uri) {
+            self.project.lock().await.set_root(root, &self.update_tx);
+        }
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 inlay_hint_provider: Some(OneOf::Left(true)),
@@ -116,7 +274,20 @@ impl LanguageServer for LspBackend {
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri.clone();
-        let file = File::new(self.client.clone(), self.update_tx.clone(), params).await;
+
+        // without a workspace, modules are imported relative to the first open file
+        if let Ok(dir) = uri.join(".") {
+            self.project.lock().await.set_root(dir, &self.update_tx);
+        }
+
+        let file = File::new(
+            self.client.clone(),
+            self.project.clone(),
+            self.update_tx.clone(),
+            self.thresholds,
+            params,
+        )
+        .await;
         self.files.lock().await.insert(uri, file);
     }
 
@@ -153,7 +324,177 @@ impl LanguageServer for LspBackend {
 
         eprintln!("inlay hints: {:?}", file.inlay_hints);
 
-        Ok(Some(file.inlay_hints.clone()))
+        let hints = file.inlay_hints.iter().chain(&file.estimates).cloned();
+        Ok(Some(hints.collect()))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let files = self.files.lock().await;
+        let params = params.text_document_position_params;
+
+        let Some(file) = files.get(&params.text_document.uri) else {
+            return Ok(None);
+        };
+
+        let point = Point {
+            row: params.position.line as usize,
+            col: params.position.character as usize,
+        };
+
+        let hover = file
+            .hovers
+            .iter()
+            .find(|(span, _contents)| span.start <= point && point < span.end)
+            .map(|(span, contents)| Hover {
+                contents: HoverContents::Scalar(MarkedString::from_language_code(
+                    "fulcrum".to_string(),
+                    contents.clone(),
+                )),
+                range: Some((*span).into()),
+            });
+
+        Ok(hover)
+    }
+}
+
+/// Solves a program's decisions, reporting violated soft constraints or why
+/// no solution exists.
+fn solve(solver: &mut Incremental, program: &Program<(Url, Span)>) -> Vec<Diagnostic<(Url, Span)>> {
+    let (problem, diagnostics) = program.ground();
+    if !diagnostics.is_empty() || problem.decisions.is_empty() {
+        return diagnostics;
+    }
+
+    match solver.minimize(&problem) {
+        Some(optimum) => problem.violations(&optimum.model),
+        None => explain(program).into_iter().collect(),
+    }
+}
+
+/// The results of estimating and solving a whole project.
+struct Solved {
+    /// The grounding estimates of every item.
+    estimates: Vec<Estimate<(Url, Span)>>,
+
+    /// The diagnostics of every file, reported by the backend or the solver.
+    diagnostics: Vec<Diagnostic<(Url, Span)>>,
+}
+
+/// Lists the updates that replace an old lowered program with a new one.
+///
+/// Both programs are given as the updates that would add them.
+fn lowered_diff(old: &HashSet<BackendUpdate>, new: &HashSet<BackendUpdate>) -> Vec<BackendUpdate> {
+    let removed = old.difference(new).map(|update| match update.clone() {
+        BackendUpdate::Fact(relation, tuple, _) => BackendUpdate::Fact(relation, tuple, false),
+        BackendUpdate::Node(node, _) => BackendUpdate::Node(node, false),
+    });
+
+    let added = new.difference(old).cloned();
+    removed.chain(added).collect()
+}
+
+/// Every file sent to the frontend, whether it is open or only imported.
+#[derive(Default)]
+pub struct Project {
+    /// The directory that modules are imported relative to, once it is known.
+    root: Option<Url>,
+
+    /// The items of each file, which are empty for files that do not exist.
+    items: HashMap<Url, HashSet<ModuleItem<Span, String, String>>>,
+
+    /// The items of every file with their imports resolved by the frontend.
+    resolved: Vec<IndexedItem<(Url, Span), ResourceId>>,
+
+    /// Where each range that is bounded during evaluation was lowered from.
+    ranges: HashMap<Key<Node>, (Url, Span)>,
+}
+
+impl Project {
+    /// Sets the root of the project, unless it is already set.
+    fn set_root(&mut self, root: Url, update_tx: &Sender<Vec<FrontendUpdate>>) {
+        if self.root.is_none() {
+            self.root = Some(root.clone());
+            let _ = update_tx.send(vec![FrontendUpdate::Root(root, true)]);
+            self.load_imports(update_tx);
+        }
+    }
+
+    /// Replaces the items of a file and loads any modules that it newly imports.
+    fn update(
+        &mut self,
+        url: &Url,
+        items: HashSet<ModuleItem<Span, String, String>>,
+        update_tx: &Sender<Vec<FrontendUpdate>>,
+    ) {
+        let old_items = self.items.remove(url).unwrap_or_default();
+        let _ = update_tx.send(Self::diff(url, &old_items, &items));
+        self.items.insert(url.clone(), items);
+        self.load_imports(update_tx);
+    }
+
+    /// Loads every imported module that is not loaded yet from disk.
+    fn load_imports(&mut self, update_tx: &Sender<Vec<FrontendUpdate>>) {
+        let Some(root) = self.root.clone() else {
+            return;
+        };
+
+        loop {
+            let missing: HashSet<_> = self
+                .items
+                .values()
+                .flatten()
+                .filter_map(|item| match item {
+                    ModuleItem::Import(import) => import.module_url(&root),
+                    _ => None,
+                })
+                .filter(|url| !self.items.contains_key(url))
+                .collect();
+
+            if missing.is_empty() {
+                break;
+            }
+
+            for url in missing {
+                // missing modules are reported by the frontend
+                let src = url
+                    .to_file_path()
+                    .ok()
+                    .and_then(|path| std::fs::read_to_string(path).ok());
+
+                let items: HashSet<_> = src
+                    .map(|src| Module::new(&src).items().into_iter().collect())
+                    .unwrap_or_default();
+
+                let _ = update_tx.send(Self::diff(&url, &HashSet::new(), &items));
+                self.items.insert(url, items);
+            }
+        }
+    }
+
+    /// Lists the updates that replace the old items of a file with new ones.
+    fn diff(
+        url: &Url,
+        old_items: &HashSet<ModuleItem<Span, String, String>>,
+        new_items: &HashSet<ModuleItem<Span, String, String>>,
+    ) -> Vec<FrontendUpdate> {
+        let removed = old_items
+            .difference(new_items)
+            .map(|removed| (removed, false));
+
+        let added = new_items.difference(old_items).map(|added| (added, true));
+
+        removed
+            .chain(added)
+            .map(|(el, added)| {
+                let el = el.clone().map_span(&mut |span| (url.clone(), span));
+                FrontendUpdate::Item(url.clone(), Box::new(el), added)
+            })
+            .collect()
+    }
+
+    /// Creates a program from the items of every file, as resolved by the frontend.
+    fn program(&self) -> Program<(Url, Span)> {
+        Program::new(self.resolved.clone())
     }
 }
 
@@ -161,23 +502,49 @@ pub struct File {
     client: Client,
     module: Module,
     url: Url,
-    old_items: HashSet<ModuleItem<Span, String, String>>,
+    project: ProjectRef,
     update_tx: Sender<Vec<FrontendUpdate>>,
     inlay_hints: Vec<InlayHint>,
+
+    /// Hover contents by the span they cover.
+    hovers: Vec<(Span, String)>,
+
+    /// The diagnostics reported by the frontend.
+    diagnostics: Vec<tower_lsp::lsp_types::Diagnostic>,
+
+    /// Whether the frontend reported any errors, so that there is nothing to solve.
+    has_errors: bool,
+
+    /// The grounding estimates of this file's items.
+    estimates: Vec<InlayHint>,
+
+    /// The diagnostics reported by estimating and solving the program.
+    solved: Vec<tower_lsp::lsp_types::Diagnostic>,
+
+    /// The sizes beyond which grounding is warned about.
+    thresholds: Thresholds,
 }
 
 impl File {
     pub async fn new(
         client: Client,
+        project: ProjectRef,
         update_tx: Sender<Vec<FrontendUpdate>>,
+        thresholds: Thresholds,
         params: DidOpenTextDocumentParams,
     ) -> Self {
         let mut file = File {
             client,
             module: Module::new(&params.text_document.text),
             url: params.text_document.uri.clone(),
-            old_items: HashSet::new(),
+            project,
             inlay_hints: Vec::new(),
+            hovers: Vec::new(),
+            diagnostics: Vec::new(),
+            has_errors: false,
+            estimates: Vec::new(),
+            solved: Vec::new(),
+            thresholds,
             update_tx,
         };
 
@@ -195,48 +562,83 @@ impl File {
     async fn on_change(&mut self, params: TextDocumentItem) {
         self.module.update(&params.text);
 
-        let items = self.module.items();
-        let new_items: HashSet<_> = items.iter().cloned().collect();
-
-        let removed = self
-            .old_items
-            .difference(&new_items)
-            .map(|removed| (removed, false));
-
-        let added = new_items
-            .difference(&self.old_items)
-            .map(|added| (added, true));
-
-        let mut updates = Vec::new();
-        for (el, added) in removed.chain(added) {
-            updates.push(FrontendUpdate::Item(
-                self.url.clone(),
-                el.clone().map_span(&mut |span| (self.url.clone(), span)),
-                added,
-            ));
-        }
-
-        if !updates.is_empty() {
-            let _ = self.update_tx.send(updates);
-        }
-
-        self.old_items = new_items;
+        let items = self.module.items().into_iter().collect();
+        let mut project = self.project.lock().await;
+        project.update(&self.url, items, &self.update_tx);
     }
 
+    /// Updates this file with the results of the frontend.
     async fn on_results(&mut self, results: Vec<FrontendResultKind>) {
-        let mut diagnostics = Vec::new();
+        self.diagnostics.clear();
         self.inlay_hints.clear();
+        self.hovers.clear();
+        self.has_errors = false;
 
         eprintln!("results {:?}", results);
 
         for result in results {
             match result {
-                FrontendResultKind::Diagnostic(diagnostic) => diagnostics.push(diagnostic.to_lsp()),
-                FrontendResultKind::Hover(_) => {}
+                FrontendResultKind::Diagnostic(diagnostic) => {
+                    self.has_errors |= diagnostic.kind == DiagnosticKind::Error;
+                    self.diagnostics.push(diagnostic.to_lsp());
+                }
+                FrontendResultKind::Hover((start, (end, contents))) => {
+                    self.hovers.push((Span { start, end }, contents))
+                }
                 FrontendResultKind::InlayHint(hint) => self.inlay_hints.push(hint.to_lsp()),
+                FrontendResultKind::Item(_)
+                | FrontendResultKind::Node(_)
+                | FrontendResultKind::Range(_, _)
+                | FrontendResultKind::Fact(_, _) => {}
             }
         }
 
+        // results of solving a program with errors are stale
+        if self.has_errors {
+            self.estimates.clear();
+            self.solved.clear();
+        }
+
+        self.publish().await;
+    }
+
+    /// Updates this file with the results of estimating and solving the
+    /// project, unless its own errors leave nothing to solve.
+    async fn on_solved(&mut self, solved: &Solved) {
+        if self.has_errors {
+            return;
+        }
+
+        self.estimates.clear();
+        self.solved.clear();
+
+        for estimate in solved.estimates.iter().filter(|e| e.span.0 == self.url) {
+            self.estimates
+                .push(estimate.hint().with_span(estimate.span.1).to_lsp());
+            self.solved
+                .extend(estimate.diagnostic(&self.thresholds).map(|d| d.to_lsp()));
+        }
+
+        self.solved.extend(
+            solved
+                .diagnostics
+                .iter()
+                .filter(|d| d.span.0 == self.url)
+                .map(Diagnostic::to_lsp),
+        );
+
+        self.publish().await;
+    }
+
+    /// Publishes the diagnostics of both the frontend and the solver.
+    async fn publish(&self) {
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .chain(&self.solved)
+            .cloned()
+            .collect();
+
         self.client
             .publish_diagnostics(self.url.clone(), diagnostics, None)
             .await;
@@ -16,17 +16,39 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
 
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
 
 use archimedes::{
+    backend::{
+        builtins::Builtin,
+        dataflow::{backend_worker, BackendResult, BackendUpdate},
+        types::{ResourceId, Tuple},
+    },
     frontend::{
         dataflow::{frontend_worker, FrontendResultKind, FrontendUpdate},
+        diagnostics::{Diagnostic, DiagnosticKind},
+        estimate::{self, Thresholds},
         parser::Module,
-        span::{MapSpan, Point},
+        span::{MapSpan, Point, Span, Spanned},
+        types::{AggregateKind, ModuleItem},
+    },
+    solver::{
+        backend::{Backend, Native},
+        cdcl::Solver,
+        enumerate::Enumerator,
+        explain, export, facts,
+        ground::{Pin, Problem, Program},
+        maxsat,
+        subprocess::{self, Subprocess},
+        types::Lit,
     },
     utils::run_dataflow,
 };
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use tower_lsp::{LspService, Server};
 use url::Url;
 
@@ -36,12 +58,73 @@ pub mod lsp;
 pub struct Args {
     #[command(subcommand)]
     pub command: Command,
+
+    #[command(flatten)]
+    pub load: LoadArgs,
+}
+
+/// Options for loading Fulcrum files, shared by every command.
+#[derive(Clone, Debug, clap::Args)]
+#[command(about = None, long_about = None)]
+pub struct LoadArgs {
+    /// Import modules relative to this directory instead of the directory of
+    /// the loaded file.
+    #[arg(long, global = true)]
+    pub root: Option<PathBuf>,
+
+    /// Warn about items estimated to ground into more variables than this.
+    #[arg(long, global = true, default_value_t = Thresholds::default().variables)]
+    pub max_variables: u64,
+
+    /// Warn about items estimated to ground into more clauses than this.
+    #[arg(long, global = true, default_value_t = Thresholds::default().clauses)]
+    pub max_clauses: u64,
+}
+
+impl LoadArgs {
+    pub fn thresholds(&self) -> Thresholds {
+        Thresholds {
+            variables: self.max_variables,
+            clauses: self.max_clauses,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Subcommand)]
 pub enum Command {
     /// Executes a Fulcrum file and displays its decision selection.
-    Run { path: PathBuf },
+    Run(Box<RunArgs>),
+
+    /// Grounds a Fulcrum file and writes its decision problem for other solvers.
+    ///
+    /// A mapping from each variable to its meaning is written alongside the
+    /// output, with `.map` appended to its path.
+    Export {
+        path: PathBuf,
+
+        /// The format of the written problem.
+        #[arg(short, long, value_enum)]
+        format: ExportFormat,
+
+        /// The path to write the problem to.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Checks a solution of a Fulcrum file's decisions against its constraints.
+    ///
+    /// The solution is a file of facts about decision relations, like those
+    /// written by `run --save`. Every decision tuple not in it does not hold.
+    CheckSolution {
+        path: PathBuf,
+
+        /// The file of decision facts to check.
+        solution: PathBuf,
+
+        /// Only display the relations with these names.
+        #[arg(short, long)]
+        relation: Vec<String>,
+    },
 
     /// Parses a Fulcrum file and dumps the AST debug-print to stderr.
     Parse { path: PathBuf },
@@ -50,12 +133,193 @@ pub enum Command {
     Lsp,
 }
 
+/// Options for executing a Fulcrum file.
+#[derive(Clone, Debug, clap::Args)]
+pub struct RunArgs {
+    pub path: PathBuf,
+
+    /// Only display the relations with these names.
+    #[arg(short, long)]
+    pub relation: Vec<String>,
+
+    /// Find up to this many distinct solutions, best first.
+    #[arg(short = 'n', long, default_value_t = 1, conflicts_with = "all")]
+    pub solutions: usize,
+
+    /// Find every distinct solution, best first.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only tell solutions apart by these decision relations.
+    #[arg(short, long)]
+    pub project: Vec<String>,
+
+    /// Save the decisions of the best solution to this file as facts.
+    #[arg(long)]
+    pub save: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub prior: PriorArgs,
+
+    #[command(flatten)]
+    pub pins: PinArgs,
+
+    #[command(flatten)]
+    pub solver: SolverArgs,
+}
+
+/// Options for choosing how decisions are solved.
+#[derive(Clone, Debug, clap::Args)]
+pub struct SolverArgs {
+    /// Solve with this external solver instead of the built-in one.
+    #[arg(long)]
+    pub solver: Option<PathBuf>,
+
+    /// Pass an argument to the external solver.
+    #[arg(long, requires = "solver", allow_hyphen_values = true)]
+    pub solver_arg: Vec<String>,
+
+    /// The format that the external solver reads.
+    #[arg(long, value_enum, requires = "solver", default_value = "wcnf")]
+    pub solver_format: SolverFormat,
+}
+
+impl SolverArgs {
+    pub fn backend(&self) -> Box<dyn Backend> {
+        let Some(program) = self.solver.clone() else {
+            return Box::new(Native);
+        };
+
+        let format = match self.solver_format {
+            SolverFormat::Dimacs => subprocess::Format::Dimacs,
+            SolverFormat::Wcnf => subprocess::Format::Wcnf,
+        };
+
+        Box::new(Subprocess {
+            program,
+            args: self.solver_arg.clone(),
+            format,
+        })
+    }
+}
+
+/// Options for re-planning against a prior solution.
+#[derive(Clone, Debug, clap::Args)]
+pub struct PriorArgs {
+    /// Change as few decisions as possible from the solution saved in this
+    /// file, and display what changed.
+    #[arg(long)]
+    pub prior: Option<PathBuf>,
+
+    /// The penalty for each decision added or removed from the prior solution.
+    #[arg(long, requires = "prior", default_value_t = 1)]
+    pub change_cost: u64,
+}
+
+/// Options for fixing decision tuples without editing the program.
+///
+/// Each tuple is written as a fact, such as `Assignment(0, Dishes, Walter)`.
+#[derive(Clone, Debug, clap::Args)]
+pub struct PinArgs {
+    /// Require this decision tuple to hold.
+    #[arg(long)]
+    pub pin: Vec<String>,
+
+    /// Forbid this decision tuple from holding.
+    #[arg(long)]
+    pub forbid: Vec<String>,
+
+    /// Require every decision tuple in this file of facts to hold.
+    #[arg(long)]
+    pub pin_file: Vec<PathBuf>,
+
+    /// Forbid every decision tuple in this file of facts from holding.
+    #[arg(long)]
+    pub forbid_file: Vec<PathBuf>,
+}
+
+impl PinArgs {
+    /// Loads every pin, exiting on any errors.
+    pub fn load(&self, decisions: &BTreeSet<ResourceId>) -> Vec<Pin> {
+        let facts = |flag: &str, arg: &str| {
+            let src = if arg.trim_end().ends_with('.') {
+                arg.to_string()
+            } else {
+                format!("{arg}.")
+            };
+
+            (flag.to_string(), src)
+        };
+
+        let file = |path: &PathBuf| {
+            let src = std::fs::read_to_string(path).expect("failed to read pin file");
+            (path.to_string_lossy().to_string(), src)
+        };
+
+        let pinned = self.pin.iter().map(|arg| facts("--pin", arg));
+        let pinned = pinned.chain(self.pin_file.iter().map(file));
+        let forbidden = self.forbid.iter().map(|arg| facts("--forbid", arg));
+        let forbidden = forbidden.chain(self.forbid_file.iter().map(file));
+
+        let pinned = pinned.map(|source| (source, true));
+        let forbidden = forbidden.map(|source| (source, false));
+
+        let mut pins = Vec::new();
+        for ((filename, src), value) in pinned.chain(forbidden) {
+            let facts = load_decision_facts(&filename, &src, decisions);
+            for (relation, tuples) in facts::group_facts(facts) {
+                pins.extend(tuples.into_iter().map(|tuple| Pin {
+                    relation: relation.clone(),
+                    tuple,
+                    value,
+                }));
+            }
+        }
+
+        pins
+    }
+}
+
+/// A file format read by external solvers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SolverFormat {
+    /// DIMACS CNF, for SAT solvers that cannot optimize soft constraints.
+    Dimacs,
+
+    /// Weighted DIMACS CNF, for MaxSAT solvers.
+    Wcnf,
+}
+
+/// A file format for grounded decision problems.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// DIMACS CNF, with only the hard constraints.
+    Dimacs,
+
+    /// Weighted DIMACS CNF, with soft constraints as soft clauses.
+    Wcnf,
+
+    /// Linear pseudo-boolean optimization.
+    Opb,
+}
+
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
+    let load = args.load;
 
     match args.command {
-        Command::Run { path } => command_run(path),
+        Command::Run(args) => command_run(*args, &load),
+        Command::Export {
+            path,
+            format,
+            output,
+        } => command_export(path, format, output, &load),
+        Command::CheckSolution {
+            path,
+            solution,
+            relation,
+        } => command_check_solution(path, solution, relation, &load),
         Command::Parse { path } => {
             let src = std::fs::read_to_string(path).unwrap();
             let module = Module::new(&src);
@@ -64,65 +328,651 @@ async fn main() {
         Command::Lsp => {
             let stdin = tokio::io::stdin();
             let stdout = tokio::io::stdout();
-            let (service, socket) = LspService::new(lsp::LspBackend::new);
+            let thresholds = load.thresholds();
+            let root = load.root.as_ref().map(|root| {
+                let root = root.canonicalize().expect("failed to canonicalize root");
+                Url::from_directory_path(root).expect("failed to create URI to root")
+            });
+
+            let (service, socket) =
+                LspService::new(|client| lsp::LspBackend::new(client, thresholds, root));
             Server::new(stdin, stdout, socket).serve(service).await;
         }
     }
 }
 
-pub fn command_run(path: PathBuf) {
-    let url = Url::from_file_path(path.canonicalize().expect("failed to canonicalize path"))
-        .expect("failed to create URI to file path");
+/// A Fulcrum file and the files it imports, evaluated by the frontend.
+pub struct Session {
+    pub url: Url,
+
+    /// The display name and source of each loaded file.
+    pub sources: HashMap<Url, (String, ariadne::Source)>,
+
+    pub program: Program<(Url, Span)>,
+    pub has_errors: bool,
+}
+
+impl Session {
+    /// Loads and evaluates a file along with every file that it imports,
+    /// printing every frontend diagnostic and a warning for each item
+    /// estimated to ground beyond the thresholds.
+    pub fn load(path: &Path, args: &LoadArgs) -> Self {
+        let path = path.canonicalize().expect("failed to canonicalize path");
+        let url = Url::from_file_path(&path).expect("failed to create URI to file path");
+
+        let root = match args.root.as_ref() {
+            Some(root) => root.canonicalize().expect("failed to canonicalize root"),
+            None => path.parent().expect("file has no parent").to_path_buf(),
+        };
+
+        let root = Url::from_directory_path(root).expect("failed to create URI to root");
+
+        let mut sources = HashMap::new();
+        let mut items = Vec::new();
+        let mut queue = vec![url.clone()];
+        while let Some(next) = queue.pop() {
+            if sources.contains_key(&next) {
+                continue;
+            }
+
+            // missing modules are reported by the frontend
+            let file_path = next.to_file_path().expect("module URI is not a file path");
+            let src = match std::fs::read_to_string(&file_path) {
+                Ok(src) => src,
+                Err(_) if next != url => continue,
+                Err(err) => panic!("failed to read {}: {err}", file_path.display()),
+            };
+
+            for item in Module::new(&src).items() {
+                if let ModuleItem::Import(import) = &item {
+                    queue.extend(import.module_url(&root));
+                }
+
+                let item = item.map_span(&mut |span| (next.clone(), span));
+                items.push((next.clone(), item));
+            }
+
+            let name = match root.make_relative(&next) {
+                Some(name) if next != url => name,
+                _ => file_path
+                    .file_name()
+                    .expect("failed to get file name")
+                    .to_string_lossy()
+                    .to_string(),
+            };
+
+            sources.insert(next, (name, ariadne::Source::from(src)));
+        }
+
+        let (update_tx, update_rx) = flume::unbounded();
+        let result_rx = run_dataflow(update_rx, frontend_worker);
+
+        let mut updates = vec![FrontendUpdate::Root(root.clone(), true)];
+        updates.extend(
+            items
+                .iter()
+                .map(|(url, item)| FrontendUpdate::Item(url.clone(), Box::new(item.clone()), true)),
+        );
+
+        update_tx
+            .send(updates)
+            .expect("failed to send item updates to dataflow");
+
+        let results = result_rx
+            .recv()
+            .expect("failed to receive dataflow results");
+
+        let mut session = Self {
+            program: Program::new([]),
+            url,
+            sources,
+            has_errors: false,
+        };
+
+        let mut resolved = Vec::new();
+        let mut lowered = Vec::new();
+        let mut ranges = HashMap::new();
+        for (url, result) in results {
+            match result {
+                FrontendResultKind::Diagnostic(d) => {
+                    session.has_errors |= d.kind == DiagnosticKind::Error;
+                    session.print_diagnostic(d);
+                }
+                FrontendResultKind::Item(item) => resolved.push(*item),
+                FrontendResultKind::Node(node) => lowered.push(BackendUpdate::Node(node, true)),
+                FrontendResultKind::Range(node, span) => {
+                    ranges.insert(node, (url, span));
+                }
+                FrontendResultKind::Fact(relation, tuple) => {
+                    lowered.push(BackendUpdate::Fact(relation, tuple, true))
+                }
+                _ => {}
+            }
+        }
+
+        session.program = Program::new(resolved);
+
+        // evaluate the lowered program in a separate stage
+        let (update_tx, update_rx) = flume::unbounded();
+        let relation_rx = run_dataflow(update_rx, backend_worker);
+
+        update_tx
+            .send(lowered)
+            .expect("failed to send lowered program to dataflow");
+
+        let relations = relation_rx
+            .recv()
+            .expect("failed to receive dataflow results");
+
+        let mut oversized = BTreeMap::new();
+        for result in relations {
+            match result {
+                BackendResult::Tuple(relation, tuple) => {
+                    session
+                        .program
+                        .relations
+                        .entry(relation)
+                        .or_default()
+                        .insert(tuple);
+                }
+                BackendResult::Oversized(node, len) => {
+                    let max = oversized.entry(node).or_insert(len);
+                    *max = len.max(*max);
+                }
+            }
+        }
+
+        // ranges bounded during evaluation are only checked by the backend
+        for (node, len) in oversized {
+            if let Some(span) = ranges.get(&node) {
+                session.has_errors = true;
+                session.print_diagnostic(Builtin::oversized_diagnostic(span.clone(), len));
+            }
+        }
+
+        if !session.has_errors {
+            let thresholds = args.thresholds();
+            let estimates = estimate::estimate(
+                &session.program.items,
+                &session.program.relations,
+                &thresholds,
+            );
+            for d in estimates.iter().filter_map(|e| e.diagnostic(&thresholds)) {
+                session.print_diagnostic(d);
+            }
+        }
+
+        session
+    }
+
+    /// Grounds the loaded program, printing any diagnostics from doing so.
+    ///
+    /// Returns nothing if there are errors, either in grounding or in loading.
+    pub fn ground(&self) -> Option<Problem<(Url, Span)>> {
+        if self.has_errors {
+            return None;
+        }
+
+        let (problem, diagnostics) = self.program.ground();
+
+        if !diagnostics.is_empty() {
+            diagnostics
+                .into_iter()
+                .for_each(|d| self.print_diagnostic(d));
+            return None;
+        }
+
+        Some(problem)
+    }
+
+    pub fn print_diagnostic(&self, d: Diagnostic<(Url, Span)>) {
+        d.map_span(&mut |(span_url, span)| {
+            let (id, src) = &self.sources[&span_url];
+            let map_point = |point: Point| src.line(point.row).unwrap().offset() + point.col;
+            (id.clone(), map_point(span.start)..map_point(span.end))
+        })
+        .to_ariadne()
+        .print(ariadne::sources(
+            self.sources
+                .values()
+                .map(|(id, src)| (id.clone(), src.text().to_string())),
+        ))
+        .expect("failed to print report");
+    }
+
+    /// Formats a span as a `file:line:column` location.
+    pub fn location(&self, (url, span): &(Url, Span)) -> String {
+        let id = match self.sources.get(url) {
+            Some((id, _src)) => id.clone(),
+            None => url.to_string(),
+        };
+
+        format!("{id}:{}:{}", span.start.row + 1, span.start.col + 1)
+    }
+}
+
+pub fn command_run(args: RunArgs, load: &LoadArgs) {
+    let RunArgs {
+        path,
+        relation: filter,
+        solutions,
+        all,
+        project,
+        save,
+        prior: PriorArgs { prior, change_cost },
+        pins,
+        solver,
+    } = args;
+
+    let limit = (!all).then_some(solutions);
+    let session = Session::load(&path, load);
+
+    let Some(mut problem) = session.ground() else {
+        if session.has_errors {
+            print_relations(&session.program.relations, &filter);
+        }
+
+        return;
+    };
+
+    if problem.decisions.is_empty() {
+        print_relations(&session.program.relations, &filter);
+        return;
+    }
+
+    let decisions: BTreeSet<_> = problem.decisions.iter().map(ToString::to_string).collect();
+    for name in project.iter() {
+        if !decisions.contains(name) {
+            eprintln!("{name} is not a decision relation");
+            std::process::exit(1);
+        }
+    }
+
+    let pins = pins.load(&problem.decisions);
+    for pin in pins.iter() {
+        if !problem.pin(pin) {
+            let fact = facts::format_fact(&pin.relation, &pin.tuple);
+            eprintln!("{fact} can never hold, so it cannot be pinned");
+            std::process::exit(1);
+        }
+    }
+
+    let prior = prior.map(|path| load_prior(&path, &problem.decisions));
+
+    let mut objective = problem.objective();
+    if let Some(prior) = prior.as_ref() {
+        objective.extend(problem.deviations(prior, change_cost));
+    }
+
+    if maxsat::total(&objective).is_none() {
+        eprintln!("the change cost is too large to optimize alongside the objective");
+        std::process::exit(1);
+    }
+
+    let projection = problem
+        .decision_vars(|relation| project.is_empty() || project.contains(&relation.to_string()));
+
+    let mut backend = solver.backend();
+    let solutions = Enumerator::new(backend.as_mut(), problem.formula(), objective, projection);
+
+    let is_enumerating = limit != Some(1);
+    let mut num = 0;
+    for optimum in solutions.take(limit.unwrap_or(usize::MAX)) {
+        let optimum = match optimum {
+            Ok(optimum) => optimum,
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        };
+
+        num += 1;
+        if is_enumerating {
+            println!("Solution {num}:");
+            println!();
+        }
+
+        let solution = problem.solution(&optimum.model);
+
+        if let Some(path) = save.as_ref().filter(|_| num == 1) {
+            let decisions = solution
+                .iter()
+                .filter(|(relation, _)| problem.decisions.contains(relation))
+                .map(|(relation, tuples)| (relation.clone(), tuples.clone()))
+                .collect();
+
+            let file = std::fs::File::create(path).expect("failed to create save file");
+            let mut out = BufWriter::new(file);
+            facts::write_facts(&decisions, &mut out)
+                .and_then(|_| out.flush())
+                .expect("failed to write save file");
+        }
+
+        match prior.as_ref() {
+            Some(prior) => print_changes(prior, &solution, &problem.decisions, &filter),
+            None => {
+                let mut relations = session.program.relations.clone();
+                relations.extend(solution);
+                print_relations(&relations, &filter);
+            }
+        }
+
+        for objective in problem.objectives.iter() {
+            let location = session.location(&objective.span);
+            match objective.value(&optimum.model) {
+                Some(value) => println!("Objective at {location} is {value}."),
+                None if objective.kind == AggregateKind::Sum => {
+                    println!("Objective at {location} overflows.")
+                }
+                None => println!("Objective at {location} has no instances."),
+            }
+        }
+
+        if !problem.objectives.is_empty() {
+            println!();
+        }
+
+        let penalty = problem.penalty(&optimum.model);
+        if penalty > 0 {
+            for d in problem.violations(&optimum.model) {
+                session.print_diagnostic(d);
+            }
+
+            println!("Total penalty of violated soft constraints: {penalty}");
+        }
+
+        if is_enumerating {
+            println!();
+        }
+    }
+
+    if num == 0 {
+        let Some((d, blamed)) = explain::explain_pinned(&session.program, &pins) else {
+            println!("No solution satisfies every constraint.");
+            return;
+        };
+
+        let has_diagnostic = d.is_some();
+        if let Some(d) = d {
+            session.print_diagnostic(d);
+        }
+
+        if !blamed.is_empty() {
+            if has_diagnostic {
+                println!("These pinned decisions are also to blame:");
+            } else {
+                println!("No solution satisfies these pinned decisions together:");
+            }
+
+            for pin in blamed.into_iter().map(|idx| &pins[idx]) {
+                let sign = if pin.value { '+' } else { '-' };
+                let fact = facts::format_fact(&pin.relation, &pin.tuple);
+                println!("  {sign} {fact}");
+            }
+        } else if !has_diagnostic {
+            println!("No solution satisfies every constraint.");
+        }
+    } else if is_enumerating && limit.is_none_or(|limit| num < limit) {
+        println!("Found all {num} distinct solutions.");
+    }
+}
+
+pub fn command_export(path: PathBuf, format: ExportFormat, output: PathBuf, load: &LoadArgs) {
+    let session = Session::load(&path, load);
+
+    let Some(problem) = session.ground() else {
+        return;
+    };
+
+    let formula = problem.formula();
+    let objective = problem.objective();
+
+    let file = std::fs::File::create(&output).expect("failed to create output file");
+    let mut out = BufWriter::new(file);
+
+    let num_vars = match format {
+        ExportFormat::Dimacs => export::write_dimacs(&formula, &mut out),
+        ExportFormat::Wcnf => export::write_wcnf(&formula, &objective, &mut out),
+        ExportFormat::Opb => export::write_opb(&formula, &objective, &mut out),
+    }
+    .and_then(|num_vars| out.flush().map(|_| num_vars))
+    .expect("failed to write output file");
+
+    let mut map_path = output.into_os_string();
+    map_path.push(".map");
+
+    let file = std::fs::File::create(&map_path).expect("failed to create mapping file");
+    let mut out = BufWriter::new(file);
+    let location = |span: &(Url, Span)| session.location(span);
+    export::write_mapping(&problem, num_vars, location, &mut out)
+        .and_then(|_| out.flush())
+        .expect("failed to write mapping file");
+}
+
+pub fn command_check_solution(
+    path: PathBuf,
+    solution: PathBuf,
+    filter: Vec<String>,
+    load: &LoadArgs,
+) {
+    let session = Session::load(&path, load);
 
-    let src = std::fs::read_to_string(&path).unwrap();
-    let module = Module::new(&src);
-    let (update_tx, update_rx) = flume::unbounded();
-    let result_rx = run_dataflow(update_rx, frontend_worker);
+    let Some(problem) = session.ground() else {
+        std::process::exit(1);
+    };
 
-    let updates = module
-        .items()
+    let filename = solution.to_string_lossy();
+    let src = std::fs::read_to_string(&solution).expect("failed to read solution");
+    let facts = load_decision_facts(&filename, &src, &problem.decisions);
+
+    let mut holds = BTreeSet::new();
+    let mut impossible = Vec::new();
+    for fact in facts {
+        let (relation, tuple) = &fact.inner;
+        match problem.tuple_var(relation, tuple) {
+            Some(var) => {
+                holds.insert(var);
+            }
+            None => impossible.push(Diagnostic {
+                span: fact.span,
+                kind: DiagnosticKind::Error,
+                message: format!("{} can never hold", facts::format_fact(relation, tuple)),
+                labels: vec![Spanned {
+                    span: fact.span,
+                    inner: "Decision that is never possible.".to_string(),
+                }],
+            }),
+        }
+    }
+
+    if !impossible.is_empty() {
+        let src = ariadne::Source::from(src.clone());
+        for d in impossible {
+            print_file_diagnostic(&filename, &src, d);
+        }
+
+        std::process::exit(1);
+    }
+
+    // every decision tuple is assumed to hold exactly when it is in the file
+    let assumptions: Vec<_> = problem
+        .decision_vars(|_| true)
         .into_iter()
-        .map(|item| item.map_span(&mut |span| (url.clone(), span)))
-        .map(|item| FrontendUpdate::Item(url.clone(), item, true))
+        .map(|var| Lit::new(var, holds.contains(&var)))
         .collect();
 
-    update_tx
-        .send(updates)
-        .expect("failed to send item updates to dataflow");
-
-    let results = result_rx
-        .recv()
-        .expect("failed to receive dataflow results");
-
-    let filename = path
-        .file_name()
-        .expect("failed to get file name")
-        .to_string_lossy()
-        .to_string();
-
-    let src = ariadne::Source::from(src);
-    for (result_url, result) in results {
-        match result {
-            FrontendResultKind::Diagnostic(d) => {
-                d.map_span(&mut |(url, span)| {
-                    let map_point =
-                        |point: Point| src.line(point.row).unwrap().offset() + point.col;
-
-                    let span = map_point(span.start)..map_point(span.end);
-
-                    let id = if url == result_url {
-                        filename.clone()
-                    } else {
-                        result_url.to_string()
-                    };
-
-                    (id, span)
-                })
-                .to_ariadne()
-                .print((filename.clone(), src.clone()))
-                .expect("failed to print report");
+    // constraints are left unenforced so that their violations can be reported
+    let mut solver = Solver::from_formula(&problem.relaxed_formula());
+    let Some(model) = solver.solve_assuming(&assumptions) else {
+        println!("These decisions contradict the rules of the program.");
+        std::process::exit(1);
+    };
+
+    let mut relations = session.program.relations.clone();
+    relations.extend(problem.solution(&model));
+    print_relations(&relations, &filter);
+
+    for objective in problem.objectives.iter() {
+        let location = session.location(&objective.span);
+        match objective.value(&model) {
+            Some(value) => println!("Objective at {location} is {value}."),
+            None if objective.kind == AggregateKind::Sum => {
+                println!("Objective at {location} overflows.")
             }
-            _ => {}
+            None => println!("Objective at {location} has no instances."),
+        }
+    }
+
+    if !problem.objectives.is_empty() {
+        println!();
+    }
+
+    let violations = problem.violations(&model);
+    let num_hard = violations
+        .iter()
+        .filter(|d| d.kind == DiagnosticKind::Error)
+        .count();
+
+    for d in violations {
+        session.print_diagnostic(d);
+    }
+
+    let penalty = problem.penalty(&model);
+    println!("Total penalty of violated soft constraints: {penalty}");
+
+    match num_hard {
+        0 => println!("The solution satisfies every hard constraint."),
+        1 => println!("The solution violates 1 hard constraint."),
+        num => println!("The solution violates {num} hard constraints."),
+    }
+
+    if num_hard > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Loads the decision tuples of a prior solution, exiting on any errors.
+pub fn load_prior(
+    path: &Path,
+    decisions: &BTreeSet<ResourceId>,
+) -> BTreeMap<ResourceId, BTreeSet<Tuple>> {
+    let src = std::fs::read_to_string(path).expect("failed to read prior solution");
+    facts::group_facts(load_decision_facts(
+        &path.to_string_lossy(),
+        &src,
+        decisions,
+    ))
+}
+
+/// Reads a file of facts about decision relations, exiting on any errors.
+pub fn load_decision_facts(
+    filename: &str,
+    src: &str,
+    decisions: &BTreeSet<ResourceId>,
+) -> Vec<Spanned<Span, (ResourceId, Tuple)>> {
+    let (read, mut diagnostics) = facts::read_facts(src);
+    let (resolved, resolve_diagnostics) = facts::resolve_facts(read, decisions);
+    diagnostics.extend(resolve_diagnostics);
+
+    if diagnostics.is_empty() {
+        return resolved;
+    }
+
+    let src = ariadne::Source::from(src.to_string());
+    for d in diagnostics {
+        print_file_diagnostic(filename, &src, d);
+    }
+
+    std::process::exit(1);
+}
+
+/// Prints a diagnostic in a file that is not a part of a [Session].
+pub fn print_file_diagnostic(filename: &str, src: &ariadne::Source, d: Diagnostic<Span>) {
+    d.map_span(&mut |span| {
+        let map_point = |point: Point| src.line(point.row).unwrap().offset() + point.col;
+        (
+            filename.to_string(),
+            map_point(span.start)..map_point(span.end),
+        )
+    })
+    .to_ariadne()
+    .print((filename.to_string(), src.clone()))
+    .expect("failed to print report");
+}
+
+/// Prints the decision tuples that a solution adds to or removes from a
+/// prior solution.
+pub fn print_changes(
+    prior: &BTreeMap<ResourceId, BTreeSet<Tuple>>,
+    solution: &BTreeMap<ResourceId, BTreeSet<Tuple>>,
+    decisions: &BTreeSet<ResourceId>,
+    filter: &[String],
+) {
+    let empty = BTreeSet::new();
+    let mut changes = Vec::new();
+    for relation in decisions.iter() {
+        if !filter.is_empty() && !filter.contains(&relation.to_string()) {
+            continue;
+        }
+
+        let before = prior.get(relation).unwrap_or(&empty);
+        let after = solution.get(relation).unwrap_or(&empty);
+
+        for tuple in before.difference(after) {
+            changes.push(format!("- {}", facts::format_fact(relation, tuple)));
+        }
+
+        for tuple in after.difference(before) {
+            changes.push(format!("+ {}", facts::format_fact(relation, tuple)));
+        }
+    }
+
+    if changes.is_empty() {
+        println!("No changes to the prior solution.");
+    } else {
+        println!("{} changes to the prior solution:", changes.len());
+        for change in changes {
+            println!("  {change}");
+        }
+    }
+
+    println!();
+}
+
+pub fn print_relations(relations: &BTreeMap<ResourceId, BTreeSet<Tuple>>, filter: &[String]) {
+    for (relation, tuples) in relations.iter() {
+        let name = relation.to_string();
+        if filter.is_empty() || filter.contains(&name) {
+            print_relation(&name, tuples);
         }
     }
 }
+
+pub fn print_relation(name: &str, tuples: &BTreeSet<Tuple>) {
+    let rows: Vec<Vec<String>> = tuples
+        .iter()
+        .map(|tuple| tuple.iter().map(ToString::to_string).collect())
+        .collect();
+
+    let mut widths = Vec::new();
+    for row in rows.iter() {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, col) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(col.len());
+        }
+    }
+
+    println!("{name} ({} tuples):", rows.len());
+
+    for row in rows {
+        let cols: Vec<_> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(col, width)| format!("{col:width$}"))
+            .collect();
+
+        println!("  {}", cols.join(" | ").trim_end());
+    }
+
+    println!();
+}
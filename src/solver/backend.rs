@@ -0,0 +1,82 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Interchangeable procedures for solving grounded problems.
+
+use std::fmt::Display;
+
+use crate::solver::{
+    cdcl::Solver,
+    maxsat::{self, Optimum},
+    types::*,
+};
+
+/// A procedure that finds optimal models of formulas.
+pub trait Backend {
+    /// Finds a model of a formula that minimizes the total penalty of an
+    /// objective's true literals.
+    ///
+    /// Returns nothing if no model satisfies the formula.
+    fn minimize(
+        &mut self,
+        formula: &Formula,
+        objective: &[(Lit, u64)],
+    ) -> Result<Option<Optimum>, BackendError>;
+}
+
+/// A failure of a backend to reach a conclusion about a formula.
+#[derive(Debug)]
+pub enum BackendError {
+    /// Communicating with the backend failed.
+    Io(std::io::Error),
+
+    /// The backend did not answer, or answered with something nonsensical.
+    Protocol(String),
+}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::Io(err) => write!(f, "failed to communicate with solver: {err}"),
+            BackendError::Protocol(msg) => write!(f, "solver misbehaved: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+impl From<std::io::Error> for BackendError {
+    fn from(err: std::io::Error) -> Self {
+        BackendError::Io(err)
+    }
+}
+
+/// The built-in CDCL solver, optimizing with [maxsat::minimize].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Native;
+
+impl Backend for Native {
+    fn minimize(
+        &mut self,
+        formula: &Formula,
+        objective: &[(Lit, u64)],
+    ) -> Result<Option<Optimum>, BackendError> {
+        let mut solver = Solver::from_formula(formula);
+        Ok(maxsat::minimize(&mut solver, objective))
+    }
+}
@@ -0,0 +1,932 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! A conflict-driven clause learning (CDCL) SAT solver.
+//!
+//! Alongside clauses, the solver natively propagates linear constraints of
+//! the form `w1 * l1 + w2 * l2 + ... <= bound` over literals, which encode
+//! cardinality constraints without expanding them into clauses.
+
+use crate::solver::types::*;
+
+/// Why a variable was assigned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Reason {
+    /// Assigned by a decision or as a top-level unit.
+    Decision,
+
+    /// Implied by the clause at the given index.
+    Clause(usize),
+
+    /// Implied by the linear constraint at the given index.
+    Linear(usize),
+}
+
+/// A constraint that the weighted sum of its true literals is at most a bound.
+#[derive(Clone, Debug)]
+struct Linear {
+    terms: Vec<(Lit, u64)>,
+    bound: u64,
+
+    /// The sum of the weights of the currently-true literals.
+    ///
+    /// This is wider than the weights so that it cannot overflow.
+    sum: u128,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Solver {
+    /// The current value of each variable.
+    values: Vec<Option<bool>>,
+
+    /// The decision level that each variable was assigned at.
+    levels: Vec<usize>,
+
+    /// The position of each assigned variable within the trail.
+    positions: Vec<usize>,
+
+    /// Why each variable was assigned.
+    reasons: Vec<Reason>,
+
+    /// The preferred polarity of each variable when it is next decided.
+    phases: Vec<bool>,
+
+    /// The branching activity of each variable.
+    activity: Vec<f64>,
+    activity_inc: f64,
+    order: VarHeap,
+
+    /// Assigned literals in order of assignment.
+    trail: Vec<Lit>,
+
+    /// The start of each decision level within the trail.
+    trail_lim: Vec<usize>,
+
+    /// The next trail position to propagate.
+    queue_head: usize,
+
+    clauses: Vec<Vec<Lit>>,
+
+    /// The clauses that watch each literal, visited when it becomes false.
+    watches: Vec<Vec<usize>>,
+
+    linears: Vec<Linear>,
+
+    /// The linear constraints and weights that each literal appears in.
+    occurrences: Vec<Vec<(usize, u64)>>,
+
+    /// Set once a conflict is found without any decisions.
+    is_unsat: bool,
+
+    /// The assumptions that could not all hold in the last search.
+    core: Vec<Lit>,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self {
+            activity_inc: 1.0,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a solver for every clause and cardinality constraint of a formula.
+    pub fn from_formula(formula: &Formula) -> Self {
+        let mut solver = Self::new();
+        solver.reserve_vars(formula.num_vars);
+
+        for clause in formula.clauses.iter() {
+            solver.add_clause(clause);
+        }
+
+        for cardinality in formula.cardinalities.iter() {
+            solver.add_cardinality(cardinality);
+        }
+
+        solver
+    }
+
+    /// The number of variables in this solver.
+    pub fn num_vars(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Ensures that this solver has at least the given number of variables.
+    pub fn reserve_vars(&mut self, num: usize) {
+        while self.values.len() < num {
+            let var = self.values.len();
+            self.values.push(None);
+            self.levels.push(0);
+            self.positions.push(0);
+            self.reasons.push(Reason::Decision);
+            self.phases.push(false);
+            self.activity.push(0.0);
+            self.watches.push(vec![]);
+            self.watches.push(vec![]);
+            self.occurrences.push(vec![]);
+            self.occurrences.push(vec![]);
+            self.order.insert(var, &self.activity);
+        }
+    }
+
+    /// Adds a clause that at least one of the given literals holds.
+    pub fn add_clause(&mut self, lits: &[Lit]) {
+        self.backtrack(0);
+
+        if self.is_unsat {
+            return;
+        }
+
+        for lit in lits {
+            self.reserve_vars(lit.var() + 1);
+        }
+
+        let mut lits = lits.to_vec();
+        lits.sort();
+        lits.dedup();
+
+        // drop clauses that are already satisfied or are tautologies
+        if lits
+            .windows(2)
+            .any(|pair| pair[0].var() == pair[1].var() || self.value(pair[0]) == Some(true))
+            || lits
+                .last()
+                .is_some_and(|lit| self.value(*lit) == Some(true))
+        {
+            return;
+        }
+
+        lits.retain(|lit| self.value(*lit).is_none());
+
+        match lits.as_slice() {
+            [] => self.is_unsat = true,
+            [lit] => {
+                self.assign(*lit, Reason::Decision);
+                if self.propagate().is_some() {
+                    self.is_unsat = true;
+                }
+            }
+            _ => {
+                let idx = self.clauses.len();
+                self.watches[lits[0].index()].push(idx);
+                self.watches[lits[1].index()].push(idx);
+                self.clauses.push(lits);
+            }
+        }
+    }
+
+    /// Adds a constraint on the number of literals in a group that hold.
+    pub fn add_cardinality(&mut self, cardinality: &Cardinality) {
+        let num = cardinality.lits.len();
+        let guard = cardinality.guard;
+
+        // too few literals to ever meet the minimum, so the guard must fail
+        if cardinality.min > num {
+            match guard {
+                Some(guard) => self.add_clause(&[!guard]),
+                None => self.add_clause(&[]),
+            }
+
+            return;
+        }
+
+        // when the guard is false, its weight makes up for any number of literals
+        let guarded = |terms: Vec<(Lit, u64)>, bound: usize| {
+            let mut terms = terms;
+            let mut bound = bound as u64;
+            if let Some(guard) = guard {
+                terms.push((guard, num as u64 - bound));
+                bound = num as u64;
+            }
+
+            (terms, bound)
+        };
+
+        if cardinality.max < num {
+            let terms = cardinality.lits.iter().map(|lit| (*lit, 1)).collect();
+            let (terms, bound) = guarded(terms, cardinality.max);
+            self.add_linear(&terms, bound);
+        }
+
+        if cardinality.min > 0 {
+            let terms = cardinality.lits.iter().map(|lit| (!*lit, 1)).collect();
+            let (terms, bound) = guarded(terms, num - cardinality.min);
+            self.add_linear(&terms, bound);
+        }
+    }
+
+    /// Adds a constraint that the weights of the true literals sum to at most a bound.
+    pub fn add_linear(&mut self, terms: &[(Lit, u64)], bound: u64) {
+        self.backtrack(0);
+
+        if self.is_unsat {
+            return;
+        }
+
+        for (lit, _weight) in terms {
+            self.reserve_vars(lit.var() + 1);
+        }
+
+        let terms: Vec<_> = terms
+            .iter()
+            .copied()
+            .filter(|(_lit, weight)| *weight > 0)
+            .collect();
+
+        let idx = self.linears.len();
+        let mut sum = 0;
+        for (lit, weight) in terms.iter() {
+            self.occurrences[lit.index()].push((idx, *weight));
+            if self.value(*lit) == Some(true) {
+                sum += *weight as u128;
+            }
+        }
+
+        self.linears.push(Linear { terms, bound, sum });
+
+        if self.check_linear(idx).is_some() || self.propagate().is_some() {
+            self.is_unsat = true;
+        }
+    }
+
+    /// Searches for a satisfying assignment of every variable.
+    pub fn solve(&mut self) -> Option<Vec<bool>> {
+        self.solve_assuming(&[])
+    }
+
+    /// Searches for a satisfying assignment in which all of the given literals hold.
+    ///
+    /// The assumptions only apply to this search, unlike unit clauses.
+    ///
+    /// If no such assignment exists, [Solver::core] lists the assumptions
+    /// that are to blame.
+    pub fn solve_assuming(&mut self, assumptions: &[Lit]) -> Option<Vec<bool>> {
+        self.core.clear();
+
+        if self.is_unsat {
+            return None;
+        }
+
+        for lit in assumptions {
+            self.reserve_vars(lit.var() + 1);
+        }
+
+        let mut conflicts = 0;
+        let mut restarts = 0;
+        let mut restart_limit = luby(restarts) * 100;
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.decision_level() == 0 {
+                    self.is_unsat = true;
+                    return None;
+                }
+
+                let (learned, level) = self.analyze(conflict);
+                self.backtrack(level);
+                self.learn(learned);
+                self.decay_activity();
+
+                conflicts += 1;
+                if conflicts >= restart_limit {
+                    conflicts = 0;
+                    restarts += 1;
+                    restart_limit = luby(restarts) * 100;
+                    self.backtrack(0);
+                }
+
+                continue;
+            }
+
+            // decide each assumption at its own level before any other variable
+            let next = match assumptions.get(self.decision_level()) {
+                Some(lit) => match self.value(*lit) {
+                    Some(true) => None,
+                    Some(false) => {
+                        self.core = self.analyze_final(*lit);
+                        self.backtrack(0);
+                        return None;
+                    }
+                    None => Some(*lit),
+                },
+                None => match self.pick_branch() {
+                    Some(var) => Some(Lit::new(var, self.phases[var])),
+                    None => {
+                        let model = self.values.iter().map(|val| val.unwrap()).collect();
+                        self.backtrack(0);
+                        return Some(model);
+                    }
+                },
+            };
+
+            self.trail_lim.push(self.trail.len());
+
+            if let Some(lit) = next {
+                self.assign(lit, Reason::Decision);
+            }
+        }
+    }
+
+    /// The polarity that a variable is preferred to have when next decided.
+    ///
+    /// Each variable keeps the value it last had, so after a search the
+    /// phases of the variables are the model that was found.
+    pub fn phase(&self, var: Var) -> bool {
+        self.phases[var]
+    }
+
+    /// Sets the polarity that a variable is preferred to have when next decided.
+    pub fn set_phase(&mut self, var: Var, phase: bool) {
+        self.reserve_vars(var + 1);
+        self.phases[var] = phase;
+    }
+
+    /// Lists a subset of the assumptions whose conjunction is unsatisfiable
+    /// after the last failed search.
+    ///
+    /// The core is empty if the solver is unsatisfiable without assumptions.
+    pub fn core(&self) -> &[Lit] {
+        &self.core
+    }
+
+    /// Finds the assumptions that imply the negation of a false assumption.
+    ///
+    /// Every decision made so far is an assumption, so the core is the set of
+    /// decisions that the false assumption's negation was derived from.
+    fn analyze_final(&self, assumption: Lit) -> Vec<Lit> {
+        let mut core = vec![assumption];
+        if self.levels[assumption.var()] == 0 {
+            return core;
+        }
+
+        let mut seen = vec![false; self.num_vars()];
+        seen[assumption.var()] = true;
+
+        for position in (self.trail_lim[0]..self.trail.len()).rev() {
+            let lit = self.trail[position];
+            if !seen[lit.var()] {
+                continue;
+            }
+
+            match self.reasons[lit.var()] {
+                Reason::Decision => core.push(lit),
+                reason => {
+                    for cause in self.reason_clause(reason, Some(lit)).into_iter().skip(1) {
+                        if self.levels[cause.var()] > 0 {
+                            seen[cause.var()] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        core
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.values[lit.var()].map(|val| val == lit.is_positive())
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Reason) {
+        let var = lit.var();
+        self.values[var] = Some(lit.is_positive());
+        self.levels[var] = self.decision_level();
+        self.positions[var] = self.trail.len();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+
+        for (idx, weight) in self.occurrences[lit.index()].iter() {
+            self.linears[*idx].sum += *weight as u128;
+        }
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+
+        let start = self.trail_lim[level];
+        for lit in self.trail.drain(start..).rev() {
+            let var = lit.var();
+            self.values[var] = None;
+            self.phases[var] = lit.is_positive();
+            self.order.insert(var, &self.activity);
+
+            for (idx, weight) in self.occurrences[lit.index()].iter() {
+                self.linears[*idx].sum -= *weight as u128;
+            }
+        }
+
+        self.trail_lim.truncate(level);
+        self.queue_head = self.queue_head.min(start);
+    }
+
+    /// Propagates all pending assignments, returning a conflicting reason if any.
+    fn propagate(&mut self) -> Option<Reason> {
+        while self.queue_head < self.trail.len() {
+            let lit = self.trail[self.queue_head];
+            self.queue_head += 1;
+
+            if let Some(conflict) = self.propagate_clauses(!lit) {
+                return Some(conflict);
+            }
+
+            for occurrence in 0..self.occurrences[lit.index()].len() {
+                let (idx, _weight) = self.occurrences[lit.index()][occurrence];
+                if let Some(conflict) = self.check_linear(idx) {
+                    return Some(conflict);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Visits the clauses watching a literal that has become false.
+    fn propagate_clauses(&mut self, false_lit: Lit) -> Option<Reason> {
+        let mut watchers = std::mem::take(&mut self.watches[false_lit.index()]);
+        let mut conflict = None;
+        let mut idx = 0;
+
+        'watchers: while idx < watchers.len() {
+            let clause_idx = watchers[idx];
+            let clause = &mut self.clauses[clause_idx];
+
+            // keep the false literal in the second watch position
+            if clause[0] == false_lit {
+                clause.swap(0, 1);
+            }
+
+            let first = clause[0];
+            if self.values[first.var()] == Some(first.is_positive()) {
+                idx += 1;
+                continue;
+            }
+
+            // look for a new literal to watch
+            for other in 2..clause.len() {
+                let lit = clause[other];
+                if self.values[lit.var()] != Some(!lit.is_positive()) {
+                    clause.swap(1, other);
+                    self.watches[lit.index()].push(clause_idx);
+                    watchers.swap_remove(idx);
+                    continue 'watchers;
+                }
+            }
+
+            idx += 1;
+
+            if self.value(first) == Some(false) {
+                conflict = Some(Reason::Clause(clause_idx));
+                break;
+            }
+
+            self.assign(first, Reason::Clause(clause_idx));
+        }
+
+        self.watches[false_lit.index()].append(&mut watchers);
+        conflict
+    }
+
+    /// Checks a linear constraint for conflicts and implied literals.
+    fn check_linear(&mut self, idx: usize) -> Option<Reason> {
+        let linear = &self.linears[idx];
+        if linear.sum > linear.bound as u128 {
+            return Some(Reason::Linear(idx));
+        }
+
+        let slack = (linear.bound as u128 - linear.sum) as u64;
+        let implied: Vec<_> = linear
+            .terms
+            .iter()
+            .filter(|(lit, weight)| *weight > slack && self.value(*lit).is_none())
+            .map(|(lit, _weight)| !*lit)
+            .collect();
+
+        for lit in implied {
+            if self.value(lit).is_none() {
+                self.assign(lit, Reason::Linear(idx));
+            }
+        }
+
+        None
+    }
+
+    /// Lists the literals of a reason as a clause, with any implied literal first.
+    fn reason_clause(&self, reason: Reason, implied: Option<Lit>) -> Vec<Lit> {
+        match reason {
+            Reason::Decision => implied.into_iter().collect(),
+            Reason::Clause(idx) => self.clauses[idx].clone(),
+            Reason::Linear(idx) => {
+                // the true literals assigned before the implied literal caused it
+                let limit = implied.map(|lit| self.positions[lit.var()]);
+                let causes = self.linears[idx].terms.iter().filter_map(|(lit, _weight)| {
+                    let is_cause = self.value(*lit) == Some(true)
+                        && limit.is_none_or(|limit| self.positions[lit.var()] < limit);
+
+                    is_cause.then_some(!*lit)
+                });
+
+                implied.into_iter().chain(causes).collect()
+            }
+        }
+    }
+
+    /// Learns a clause from a conflict, returning it and the level to backtrack to.
+    fn analyze(&mut self, conflict: Reason) -> (Vec<Lit>, usize) {
+        let mut seen = vec![false; self.num_vars()];
+        let mut learned = vec![];
+        let mut pending = 0;
+        let mut clause = self.reason_clause(conflict, None);
+        let mut position = self.trail.len();
+        let level = self.decision_level();
+
+        loop {
+            for lit in clause.iter() {
+                let var = lit.var();
+                if seen[var] || self.levels[var] == 0 {
+                    continue;
+                }
+
+                seen[var] = true;
+                self.bump_activity(var);
+
+                if self.levels[var] == level {
+                    pending += 1;
+                } else {
+                    learned.push(*lit);
+                }
+            }
+
+            // find the next literal of the current level to resolve on
+            let lit = loop {
+                position -= 1;
+                let lit = self.trail[position];
+                if seen[lit.var()] {
+                    break lit;
+                }
+            };
+
+            pending -= 1;
+            if pending == 0 {
+                learned.insert(0, !lit);
+                break;
+            }
+
+            let reason = self.reasons[lit.var()];
+            clause = self.reason_clause(reason, Some(lit));
+            clause.remove(0);
+        }
+
+        let backjump = learned[1..]
+            .iter()
+            .map(|lit| self.levels[lit.var()])
+            .max()
+            .unwrap_or(0);
+
+        (learned, backjump)
+    }
+
+    /// Adds a learned clause and assigns its asserting literal.
+    fn learn(&mut self, mut learned: Vec<Lit>) {
+        if learned.len() == 1 {
+            self.assign(learned[0], Reason::Decision);
+            return;
+        }
+
+        // watch the literal with the highest level second
+        let (second, _) = learned[1..]
+            .iter()
+            .enumerate()
+            .max_by_key(|(_idx, lit)| self.levels[lit.var()])
+            .unwrap();
+
+        learned.swap(1, second + 1);
+
+        let idx = self.clauses.len();
+        self.watches[learned[0].index()].push(idx);
+        self.watches[learned[1].index()].push(idx);
+        let asserting = learned[0];
+        self.clauses.push(learned);
+        self.assign(asserting, Reason::Clause(idx));
+    }
+
+    fn pick_branch(&mut self) -> Option<Var> {
+        while let Some(var) = self.order.pop(&self.activity) {
+            if self.values[var].is_none() {
+                return Some(var);
+            }
+        }
+
+        None
+    }
+
+    fn bump_activity(&mut self, var: Var) {
+        self.activity[var] += self.activity_inc;
+
+        if self.activity[var] > 1e100 {
+            for activity in self.activity.iter_mut() {
+                *activity *= 1e-100;
+            }
+
+            self.activity_inc *= 1e-100;
+        }
+
+        self.order.update(var, &self.activity);
+    }
+
+    fn decay_activity(&mut self) {
+        self.activity_inc /= 0.95;
+    }
+}
+
+/// The Luby sequence (1, 1, 2, 1, 1, 2, 4, ...) used to space out restarts.
+fn luby(mut idx: usize) -> usize {
+    let mut size = 1;
+    let mut seq = 0;
+    while size < idx + 1 {
+        seq += 1;
+        size = 2 * size + 1;
+    }
+
+    while size - 1 != idx {
+        size = (size - 1) / 2;
+        seq -= 1;
+        idx %= size;
+    }
+
+    1 << seq
+}
+
+/// A binary max-heap of variables ordered by activity.
+#[derive(Clone, Debug, Default)]
+struct VarHeap {
+    heap: Vec<Var>,
+
+    /// The position of each variable in the heap, if present.
+    indices: Vec<Option<usize>>,
+}
+
+impl VarHeap {
+    fn insert(&mut self, var: Var, activity: &[f64]) {
+        if self.indices.len() <= var {
+            self.indices.resize(var + 1, None);
+        }
+
+        if self.indices[var].is_some() {
+            return;
+        }
+
+        self.indices[var] = Some(self.heap.len());
+        self.heap.push(var);
+        self.sift_up(self.heap.len() - 1, activity);
+    }
+
+    fn update(&mut self, var: Var, activity: &[f64]) {
+        if let Some(Some(idx)) = self.indices.get(var) {
+            self.sift_up(*idx, activity);
+        }
+    }
+
+    fn pop(&mut self, activity: &[f64]) -> Option<Var> {
+        let top = *self.heap.first()?;
+        let last = self.heap.pop().unwrap();
+        self.indices[top] = None;
+
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.indices[last] = Some(0);
+            self.sift_down(0, activity);
+        }
+
+        Some(top)
+    }
+
+    fn sift_up(&mut self, mut idx: usize, activity: &[f64]) {
+        while idx > 0 {
+            let parent = (idx - 1) / 2;
+            if activity[self.heap[parent]] >= activity[self.heap[idx]] {
+                break;
+            }
+
+            self.swap(idx, parent);
+            idx = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut idx: usize, activity: &[f64]) {
+        loop {
+            let left = idx * 2 + 1;
+            let right = left + 1;
+            let mut largest = idx;
+
+            if left < self.heap.len() && activity[self.heap[left]] > activity[self.heap[largest]] {
+                largest = left;
+            }
+
+            if right < self.heap.len() && activity[self.heap[right]] > activity[self.heap[largest]]
+            {
+                largest = right;
+            }
+
+            if largest == idx {
+                break;
+            }
+
+            self.swap(idx, largest);
+            idx = largest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.indices[self.heap[a]] = Some(a);
+        self.indices[self.heap[b]] = Some(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lits(dimacs: &[i64]) -> Vec<Lit> {
+        dimacs
+            .iter()
+            .map(|lit| Lit::from_dimacs(*lit).unwrap())
+            .collect()
+    }
+
+    fn lit(dimacs: i64) -> Lit {
+        Lit::from_dimacs(dimacs).unwrap()
+    }
+
+    fn count(model: &[bool], vars: std::ops::Range<usize>) -> usize {
+        vars.filter(|var| model[*var]).count()
+    }
+
+    #[test]
+    fn propagates_units() {
+        let mut solver = Solver::new();
+        solver.add_clause(&lits(&[1]));
+        solver.add_clause(&lits(&[-1, 2]));
+        solver.add_clause(&lits(&[-2, -3]));
+
+        // implied at the top level without making any decisions
+        assert_eq!(solver.value(lit(2)), Some(true));
+        assert_eq!(solver.value(lit(3)), Some(false));
+        assert_eq!(solver.decision_level(), 0);
+    }
+
+    #[test]
+    fn learns_from_conflicts() {
+        // every assignment of 2 and 3 conflicts unless 1 holds
+        let mut solver = Solver::new();
+        solver.add_clause(&lits(&[1, 2, 3]));
+        solver.add_clause(&lits(&[1, -2, 3]));
+        solver.add_clause(&lits(&[1, 2, -3]));
+        solver.add_clause(&lits(&[1, -2, -3]));
+        solver.add_clause(&lits(&[-1, 4, 5]));
+
+        // assuming -4 and -5 implies -1 two levels down, so the search must
+        // learn that 1 holds and backjump all the way to the top level
+        assert_eq!(solver.solve_assuming(&lits(&[-4, -5])), None);
+        assert_eq!(solver.value(lit(1)), Some(true));
+
+        let mut core = solver.core().to_vec();
+        core.sort();
+        assert_eq!(core, lits(&[-4, -5]));
+        assert!(!solver.is_unsat);
+
+        let model = solver.solve().unwrap();
+        assert!(model[0] && (model[3] || model[4]));
+    }
+
+    #[test]
+    fn refutes_pigeonhole() {
+        // three pigeons in two holes, with pigeon p in hole h as var 2p+h
+        let var = |pigeon: i64, hole: i64| 2 * pigeon + hole + 1;
+        let mut solver = Solver::new();
+
+        for pigeon in 0..3 {
+            solver.add_clause(&lits(&[var(pigeon, 0), var(pigeon, 1)]));
+        }
+
+        for hole in 0..2 {
+            for a in 0..3 {
+                for b in (a + 1)..3 {
+                    solver.add_clause(&lits(&[-var(a, hole), -var(b, hole)]));
+                }
+            }
+        }
+
+        assert_eq!(solver.solve(), None);
+        assert!(solver.is_unsat);
+    }
+
+    #[test]
+    fn bounds_cardinality() {
+        let mut solver = Solver::new();
+        solver.add_cardinality(&Cardinality {
+            guard: None,
+            lits: lits(&[1, 2, 3, 4]),
+            min: 2,
+            max: 3,
+        });
+
+        let model = solver.solve().unwrap();
+        assert!((2..=3).contains(&count(&model, 0..4)));
+
+        // reaching the maximum forces the rest to be false
+        solver.add_clause(&lits(&[1]));
+        solver.add_clause(&lits(&[2]));
+        solver.add_clause(&lits(&[3]));
+        assert_eq!(solver.value(lit(4)), Some(false));
+
+        // and falling below the minimum is a conflict
+        let mut solver = Solver::new();
+        solver.add_cardinality(&Cardinality {
+            guard: None,
+            lits: lits(&[1, 2, 3, 4]),
+            min: 2,
+            max: 4,
+        });
+
+        solver.add_clause(&lits(&[-1]));
+        solver.add_clause(&lits(&[-2]));
+        solver.add_clause(&lits(&[-3]));
+        assert_eq!(solver.solve(), None);
+    }
+
+    #[test]
+    fn guards_cardinality() {
+        let mut solver = Solver::new();
+        solver.add_cardinality(&Cardinality {
+            guard: Some(lit(4)),
+            lits: lits(&[1, 2, 3]),
+            min: 0,
+            max: 1,
+        });
+
+        // the bound only applies while the guard holds
+        let model = solver.solve_assuming(&lits(&[1, 2, -4])).unwrap();
+        assert_eq!(count(&model, 0..3), 2);
+        assert_eq!(solver.solve_assuming(&lits(&[1, 2, 4])), None);
+    }
+
+    #[test]
+    fn fails_unreachable_minimum() {
+        let mut solver = Solver::new();
+        solver.add_cardinality(&Cardinality {
+            guard: Some(lit(1)),
+            lits: vec![],
+            min: 1,
+            max: 1,
+        });
+
+        assert_eq!(solver.value(lit(1)), Some(false));
+
+        let mut solver = Solver::new();
+        solver.add_cardinality(&Cardinality {
+            guard: None,
+            lits: lits(&[1]),
+            min: 2,
+            max: 2,
+        });
+
+        assert_eq!(solver.solve(), None);
+    }
+
+    #[test]
+    fn finds_assumption_core() {
+        let mut solver = Solver::new();
+        solver.add_clause(&lits(&[-1, -2]));
+
+        assert_eq!(solver.solve_assuming(&lits(&[1, 3, 2])), None);
+
+        let mut core = solver.core().to_vec();
+        core.sort();
+        assert_eq!(core, lits(&[1, 2]));
+
+        // the failed assumptions do not stick
+        assert!(solver.solve().is_some());
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Enumeration of distinct models with blocking clauses.
+
+use crate::solver::{
+    backend::{Backend, BackendError},
+    maxsat::Optimum,
+    types::*,
+};
+
+/// Finds models of a formula that differ on a projection of its variables,
+/// in order of increasing penalty.
+///
+/// After each model is found, a clause blocking its assignment of the
+/// projected variables is added to the formula, so models that only differ
+/// outside of the projection are never both found.
+pub struct Enumerator<'a> {
+    backend: &'a mut dyn Backend,
+    formula: Formula,
+    objective: Vec<(Lit, u64)>,
+    projection: Vec<Var>,
+    is_done: bool,
+}
+
+impl<'a> Enumerator<'a> {
+    pub fn new(
+        backend: &'a mut dyn Backend,
+        formula: Formula,
+        objective: Vec<(Lit, u64)>,
+        projection: Vec<Var>,
+    ) -> Self {
+        Self {
+            backend,
+            formula,
+            objective,
+            projection,
+            is_done: false,
+        }
+    }
+}
+
+impl Iterator for Enumerator<'_> {
+    type Item = Result<Optimum, BackendError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        let optimum = match self.backend.minimize(&self.formula, &self.objective) {
+            Ok(Some(optimum)) => optimum,
+            Ok(None) => {
+                self.is_done = true;
+                return None;
+            }
+            Err(err) => {
+                self.is_done = true;
+                return Some(Err(err));
+            }
+        };
+
+        // with nothing to project onto, every model is the same
+        self.is_done = self.projection.is_empty();
+
+        let blocking = self
+            .projection
+            .iter()
+            .map(|var| Lit::new(*var, !optimum.model[*var]))
+            .collect();
+
+        self.formula.clauses.push(blocking);
+
+        Some(Ok(optimum))
+    }
+}
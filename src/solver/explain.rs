@@ -0,0 +1,100 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Minimal explanations of unsatisfiable assumptions.
+
+use crate::{
+    frontend::diagnostics::Diagnostic,
+    solver::{
+        cdcl::Solver,
+        ground::{Pin, Program},
+        types::*,
+    },
+};
+
+/// Explains why a program's decisions have no solution, by blaming a minimal
+/// set of its hard constraints and facts.
+///
+/// Returns nothing if a solution exists, or if no constraint or fact is to
+/// blame.
+pub fn explain<S: Clone + PartialEq>(program: &Program<S>) -> Option<Diagnostic<S>> {
+    explain_pinned(program, &[])?.0
+}
+
+/// Explains why a program's decisions have no solution when some of them are
+/// pinned, by blaming a minimal set of its hard constraints, facts, and pins.
+///
+/// Returns nothing if a solution exists. Otherwise, returns a diagnostic
+/// blaming the constraints and facts, if any are to blame, along with the
+/// indices of the blamed pins. Pins of tuples that can never hold are
+/// ignored.
+pub fn explain_pinned<S: Clone + PartialEq>(
+    program: &Program<S>,
+    pins: &[Pin],
+) -> Option<(Option<Diagnostic<S>>, Vec<usize>)> {
+    let (problem, _diagnostics) = program.ground_retractable();
+    let pin_lits: Vec<_> = pins.iter().map(|pin| problem.pin_lit(pin)).collect();
+
+    let mut assumptions = problem.selectors();
+    assumptions.extend(pin_lits.iter().flatten());
+    assumptions.sort();
+    assumptions.dedup();
+
+    let mut solver = Solver::from_formula(&problem.relaxed_formula());
+    let core = minimal_core(&mut solver, &assumptions)?;
+
+    let blamed = pin_lits
+        .iter()
+        .enumerate()
+        .filter(|(_, lit)| lit.is_some_and(|lit| core.contains(&lit)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    Some((problem.explain(&core), blamed))
+}
+
+/// Finds a minimal subset of the assumptions that cannot all hold together.
+///
+/// Returns nothing if every assumption can hold at once. Otherwise, each
+/// assumption of the core is dropped in turn, and kept only if the rest of
+/// the core is satisfiable without it. Whenever the rest is unsatisfiable,
+/// the core shrinks to the solver's own core of the rest.
+pub fn minimal_core(solver: &mut Solver, assumptions: &[Lit]) -> Option<Vec<Lit>> {
+    if solver.solve_assuming(assumptions).is_some() {
+        return None;
+    }
+
+    let mut core = solver.core().to_vec();
+    let mut idx = 0;
+    while idx < core.len() {
+        let mut rest = core.clone();
+        rest.remove(idx);
+
+        if solver.solve_assuming(&rest).is_some() {
+            idx += 1;
+            continue;
+        }
+
+        // the assumptions known to be needed are kept by every smaller core
+        let smaller = solver.core();
+        rest.retain(|lit| smaller.contains(lit));
+        core = rest;
+    }
+
+    Some(core)
+}
@@ -0,0 +1,291 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Writers for the interchange formats of other solvers.
+//!
+//! Variables are numbered from one in every format, so variable `n` of a
+//! written problem is [Var] `n - 1` of its formula.
+
+use std::io::{Error, ErrorKind, Result, Write};
+
+use indexmap::IndexMap;
+
+use crate::solver::{
+    ground::{Problem, Variable},
+    types::*,
+};
+
+/// Writes the hard part of a formula in DIMACS CNF.
+///
+/// Cardinality constraints are expanded into clauses first. Nothing in the
+/// formula is optimized, so soft constraints may go unenforced. Returns the
+/// number of variables written.
+pub fn write_dimacs(formula: &Formula, out: &mut impl Write) -> Result<usize> {
+    let cnf = formula.to_cnf();
+    writeln!(out, "c soft constraints are not enforced")?;
+    writeln!(out, "p cnf {} {}", cnf.num_vars, cnf.clauses.len())?;
+
+    for clause in cnf.clauses.iter() {
+        write_clause(out, clause)?;
+    }
+
+    Ok(cnf.num_vars)
+}
+
+/// Writes a formula and an objective to minimize in weighted DIMACS CNF.
+///
+/// Every clause of the formula is hard, and each penalized literal of the
+/// objective becomes a soft unit clause of its negation. Returns the number of
+/// variables written, or fails if the weights do not fit under a top weight.
+pub fn write_wcnf(
+    formula: &Formula,
+    objective: &[(Lit, u64)],
+    out: &mut impl Write,
+) -> Result<usize> {
+    let cnf = formula.to_cnf();
+    let top = objective
+        .iter()
+        .try_fold(1u64, |top, (_, weight)| top.checked_add(*weight))
+        .ok_or_else(too_large)?;
+
+    let num_clauses = cnf.clauses.len() + objective.len();
+    writeln!(out, "p wcnf {} {num_clauses} {top}", cnf.num_vars)?;
+
+    for clause in cnf.clauses.iter() {
+        write!(out, "{top} ")?;
+        write_clause(out, clause)?;
+    }
+
+    for (lit, weight) in objective.iter() {
+        write!(out, "{weight} ")?;
+        write_clause(out, &[!*lit])?;
+    }
+
+    Ok(cnf.num_vars)
+}
+
+/// Writes a formula and an objective to minimize as a linear pseudo-boolean
+/// optimization problem.
+///
+/// Cardinality constraints are kept as native constraints. Negated literals
+/// are rewritten in terms of their variables, so the minimum of the written
+/// objective is offset from the true cost by a constant noted in a comment.
+/// Returns the number of variables written, or fails if a weight or the offset
+/// does not fit in a signed coefficient.
+pub fn write_opb(
+    formula: &Formula,
+    objective: &[(Lit, u64)],
+    out: &mut impl Write,
+) -> Result<usize> {
+    let mut constraints = Vec::new();
+
+    for clause in formula.clauses.iter() {
+        let terms: Vec<_> = clause.iter().map(|lit| (1, *lit)).collect();
+        constraints.push(Linear::at_least(&terms, 1)?);
+    }
+
+    for cardinality in formula.cardinalities.iter() {
+        let num = coefficient(cardinality.lits.len())?;
+        let min = coefficient(cardinality.min)?;
+        let max = coefficient(cardinality.max)?;
+
+        if min > 0 {
+            let mut terms: Vec<_> = cardinality.lits.iter().map(|lit| (1, *lit)).collect();
+            terms.extend(cardinality.guard.map(|guard| (min, !guard)));
+            constraints.push(Linear::at_least(&terms, min)?);
+        }
+
+        if max < num {
+            let mut terms: Vec<_> = cardinality.lits.iter().map(|lit| (1, !*lit)).collect();
+            terms.extend(cardinality.guard.map(|guard| (num - max, !guard)));
+            constraints.push(Linear::at_least(&terms, num - max)?);
+        }
+    }
+
+    // merged literals may cancel out, leaving constraints that always hold
+    constraints.retain(|constraint| !constraint.terms.is_empty() || constraint.bound > 0);
+
+    let weighted = objective
+        .iter()
+        .map(|(lit, weight)| Ok((coefficient(*weight)?, *lit)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let objective = Linear::at_least(&weighted, 0)?;
+
+    writeln!(
+        out,
+        "* #variable= {} #constraint= {}",
+        formula.num_vars,
+        constraints.len()
+    )?;
+
+    let offset = objective.bound.checked_neg().ok_or_else(too_large)?;
+    writeln!(out, "* objective offset: {offset}")?;
+
+    if !objective.terms.is_empty() {
+        writeln!(out, "min: {} ;", objective.terms_to_string())?;
+    }
+
+    for constraint in constraints {
+        writeln!(
+            out,
+            "{} >= {} ;",
+            constraint.terms_to_string(),
+            constraint.bound
+        )?;
+    }
+
+    Ok(formula.num_vars)
+}
+
+/// Writes a tab-separated table of what each variable of a problem stands for.
+///
+/// Each row holds a variable's number, its kind, its relation or constraint,
+/// its tuple or captured values, and its location in the source, formatted
+/// by `location`. Variables beyond the problem's own, like those introduced by
+/// [Formula::to_cnf], are listed as auxiliary.
+pub fn write_mapping<S>(
+    problem: &Problem<S>,
+    num_vars: usize,
+    location: impl Fn(&S) -> String,
+    out: &mut impl Write,
+) -> Result<()> {
+    writeln!(out, "variable\tkind\tname\tvalues\tlocation")?;
+
+    for var in 0..num_vars.max(problem.variables.len()) {
+        let (kind, name, values) = match problem.variables.get(var) {
+            Some(Variable::Tuple(relation, tuple)) => {
+                let kind = if problem.decisions.contains(relation) {
+                    "decision"
+                } else {
+                    "derived"
+                };
+
+                (kind, relation.to_string(), format_values(tuple))
+            }
+            Some(Variable::Conjunction(lits)) => {
+                let lits: Vec<_> = lits.iter().map(Lit::to_dimacs).collect();
+                ("conjunction", "-".to_string(), format_values(&lits))
+            }
+            Some(Variable::Possibility) => ("possibility", "-".to_string(), "-".to_string()),
+            Some(Variable::AtLeast(lits, count)) => {
+                let lits: Vec<_> = lits.iter().map(Lit::to_dimacs).collect();
+                let name = format!("at least {count}");
+                ("threshold", name, format_values(&lits))
+            }
+            Some(Variable::Level(relation, tuple, bit)) => {
+                let name = format!("level bit {bit} of {relation}");
+                ("level", name, format_values(tuple))
+            }
+            Some(Variable::Below(lower, upper)) => {
+                let lits: Vec<_> = lower.iter().chain(upper).map(Lit::to_dimacs).collect();
+                ("below", "-".to_string(), format_values(&lits))
+            }
+            Some(Variable::Activation(idx)) => {
+                let constraint = &problem.constraints[*idx];
+                let kind = if constraint.weight.is_some() {
+                    "soft"
+                } else {
+                    "hard"
+                };
+
+                let name = format!("constraint {idx}");
+                (kind, name, format_values(&constraint.captures))
+            }
+            None => ("auxiliary", "-".to_string(), "-".to_string()),
+        };
+
+        let location = match problem.spans.get(var) {
+            Some(Some(span)) => location(span),
+            _ => "-".to_string(),
+        };
+
+        writeln!(out, "{}\t{kind}\t{name}\t{values}\t{location}", var + 1)?;
+    }
+
+    Ok(())
+}
+
+fn write_clause(out: &mut impl Write, clause: &[Lit]) -> Result<()> {
+    for lit in clause.iter() {
+        write!(out, "{} ", lit.to_dimacs())?;
+    }
+
+    writeln!(out, "0")
+}
+
+/// Converts a count or weight to a coefficient, unless it is too large.
+fn coefficient<T: TryInto<i64>>(value: T) -> Result<i64> {
+    value.try_into().map_err(|_| too_large())
+}
+
+fn too_large() -> Error {
+    Error::new(ErrorKind::InvalidData, "weights are too large to write")
+}
+
+fn format_values(values: &[impl ToString]) -> String {
+    let values: Vec<_> = values.iter().map(ToString::to_string).collect();
+    format!("({})", values.join(", "))
+}
+
+/// A linear pseudo-boolean constraint over positive literals only.
+struct Linear {
+    terms: Vec<(i64, Var)>,
+    bound: i64,
+}
+
+impl Linear {
+    /// Creates the constraint `sum coeff * lit >= bound`.
+    ///
+    /// Each negated literal `~x` is replaced with `1 - x`, and the coefficients
+    /// of each variable are merged into one term, since some solvers reject
+    /// constraints that repeat a variable. Fails if a coefficient or the bound
+    /// goes out of range.
+    fn at_least(terms: &[(i64, Lit)], mut bound: i64) -> Result<Self> {
+        let mut merged: IndexMap<Var, i64> = IndexMap::new();
+        for (coeff, lit) in terms.iter() {
+            let coeff = if lit.is_positive() {
+                *coeff
+            } else {
+                bound = bound.checked_sub(*coeff).ok_or_else(too_large)?;
+                coeff.checked_neg().ok_or_else(too_large)?
+            };
+
+            let sum = merged.entry(lit.var()).or_default();
+            *sum = sum.checked_add(coeff).ok_or_else(too_large)?;
+        }
+
+        let terms = merged
+            .into_iter()
+            .filter(|(_var, coeff)| *coeff != 0)
+            .map(|(var, coeff)| (coeff, var))
+            .collect();
+
+        Ok(Self { terms, bound })
+    }
+
+    fn terms_to_string(&self) -> String {
+        let terms: Vec<_> = self
+            .terms
+            .iter()
+            .map(|(coeff, var)| format!("{coeff:+} x{}", var + 1))
+            .collect();
+
+        terms.join(" ")
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reading and writing the tuples of relations as files of Fulcrum facts.
+//!
+//! Solutions are saved as one fact per decision tuple, so that they can be
+//! loaded again to compare against or to build on.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Result, Write},
+};
+
+use crate::{
+    backend::types::{ResourceId, Tuple},
+    frontend::{
+        diagnostics::{Diagnostic, DiagnosticKind},
+        parser::Module,
+        span::{Span, Spanned},
+        types::*,
+    },
+};
+
+/// Parses a file of facts into the name of each fact's relation and its tuple.
+///
+/// Every item of the file must be a rule without a body or variables.
+pub fn read_facts(src: &str) -> (Vec<Spanned<Span, (String, Tuple)>>, Vec<Diagnostic<Span>>) {
+    let mut facts = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for item in Module::new(src).items() {
+        let (span, head) = match item {
+            ModuleItem::Diagnostic(d) => {
+                diagnostics.push(d);
+                continue;
+            }
+            ModuleItem::Rule(rule) if rule.body.is_empty() => (rule.head.span, rule.head.inner),
+            ModuleItem::Rule(rule) | ModuleItem::Decision(Decision(rule)) => {
+                diagnostics.push(not_a_fact(rule.head.span));
+                continue;
+            }
+            ModuleItem::Constraint(constraint) => {
+                diagnostics.push(not_a_fact(constraint.kind.span));
+                continue;
+            }
+            ModuleItem::Objective(objective) => {
+                diagnostics.push(not_a_fact(objective.sense.span));
+                continue;
+            }
+            ModuleItem::Type(decl) => {
+                diagnostics.push(not_a_fact(decl.relation.span));
+                continue;
+            }
+            ModuleItem::Enum(decl) => {
+                diagnostics.push(not_a_fact(decl.name.span));
+                continue;
+            }
+            ModuleItem::Import(import) => {
+                if let Some(module) = import.module.first() {
+                    diagnostics.push(not_a_fact(module.span));
+                }
+
+                continue;
+            }
+        };
+
+        let mut variables = Vec::new();
+        let leaves = head
+            .pattern
+            .map_leaves(&mut |span, term| match term {
+                Term::Value(value) => Some(value),
+                Term::Variable(_) => {
+                    variables.push(*span);
+                    None
+                }
+            })
+            .into_leaves();
+
+        if !variables.is_empty() {
+            diagnostics.push(Diagnostic {
+                span,
+                kind: DiagnosticKind::Error,
+                message: "Facts cannot contain variables".to_string(),
+                labels: variables
+                    .into_iter()
+                    .map(|span| Spanned {
+                        span,
+                        inner: "Variable used here.".to_string(),
+                    })
+                    .collect(),
+            });
+
+            continue;
+        }
+
+        facts.push(Spanned {
+            span,
+            inner: (head.relation.inner, leaves.into_iter().flatten().collect()),
+        });
+    }
+
+    (facts, diagnostics)
+}
+
+fn not_a_fact(span: Span) -> Diagnostic<Span> {
+    Diagnostic {
+        span,
+        kind: DiagnosticKind::Error,
+        message: "Expected a fact".to_string(),
+        labels: vec![Spanned {
+            span,
+            inner: "Only facts can be read from this file.".to_string(),
+        }],
+    }
+}
+
+/// Resolves the relation of each fact among the given ones.
+///
+/// Facts of any other relation are reported.
+pub fn resolve_facts(
+    facts: Vec<Spanned<Span, (String, Tuple)>>,
+    relations: &BTreeSet<ResourceId>,
+) -> (
+    Vec<Spanned<Span, (ResourceId, Tuple)>>,
+    Vec<Diagnostic<Span>>,
+) {
+    let by_name: BTreeMap<_, _> = relations
+        .iter()
+        .map(|relation| (relation.to_string(), relation))
+        .collect();
+
+    let mut resolved = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for Spanned {
+        span,
+        inner: (name, tuple),
+    } in facts
+    {
+        let Some(relation) = by_name.get(&name) else {
+            diagnostics.push(Diagnostic {
+                span,
+                kind: DiagnosticKind::Error,
+                message: format!("{name} is not a decision relation"),
+                labels: vec![Spanned {
+                    span,
+                    inner: "Fact of an unknown decision.".to_string(),
+                }],
+            });
+
+            continue;
+        };
+
+        resolved.push(Spanned {
+            span,
+            inner: ((*relation).clone(), tuple),
+        });
+    }
+
+    (resolved, diagnostics)
+}
+
+/// Groups the tuples of facts by their relation.
+pub fn group_facts(
+    facts: impl IntoIterator<Item = Spanned<Span, (ResourceId, Tuple)>>,
+) -> BTreeMap<ResourceId, BTreeSet<Tuple>> {
+    let mut grouped: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+    for fact in facts {
+        let (relation, tuple) = fact.inner;
+        grouped.entry(relation).or_default().insert(tuple);
+    }
+
+    grouped
+}
+
+/// Formats a tuple of a relation as the head of a fact.
+pub fn format_fact(relation: &ResourceId, tuple: &Tuple) -> String {
+    match tuple.as_ref() {
+        [value] => format!("{relation} {value}"),
+        values => {
+            let values: Vec<_> = values.iter().map(ToString::to_string).collect();
+            format!("{relation}({})", values.join(", "))
+        }
+    }
+}
+
+/// Writes the tuples of relations as facts, one per line.
+pub fn write_facts(
+    relations: &BTreeMap<ResourceId, BTreeSet<Tuple>>,
+    out: &mut impl Write,
+) -> Result<()> {
+    for (relation, tuples) in relations.iter() {
+        for tuple in tuples.iter() {
+            writeln!(out, "{}.", format_fact(relation, tuple))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1980 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Grounds the decisions and constraints of a program into a [Formula].
+//!
+//! Every tuple of a decision relation, and of any relation that depends on a
+//! decision, becomes a boolean variable. Rules over those relations become
+//! implications between their body and head tuples, and each instance of a
+//! constraint becomes a requirement over the tuples its body matched. Tuples
+//! that recursively depend on each other are also given levels, so that each
+//! is only derived from tuples at lower levels and no cycle supports itself.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{
+    backend::types::{Reducer, Tuple},
+    frontend::{span::MapSpan, types::*},
+    solver::types::*,
+};
+
+/// The resolved items of a program along with the evaluated contents of its relations.
+#[derive(Clone, Debug)]
+pub struct Program<S> {
+    pub items: Vec<IndexedItem<S, ResourceId>>,
+    pub relations: BTreeMap<ResourceId, BTreeSet<Tuple>>,
+}
+
+impl<S: Clone + Ord> Program<S> {
+    /// Creates a program from items resolved by the frontend, ordering them
+    /// by where they first appear in their sources.
+    pub fn new(items: impl IntoIterator<Item = IndexedItem<S, ResourceId>>) -> Self {
+        let mut items: Vec<_> = items.into_iter().collect();
+        items.sort_by_cached_key(|item| {
+            let mut first: Option<S> = None;
+            item.clone().map_span(&mut |span| {
+                if first.as_ref().is_none_or(|first| span < *first) {
+                    first = Some(span);
+                }
+            });
+
+            first
+        });
+
+        Self {
+            items,
+            relations: BTreeMap::new(),
+        }
+    }
+}
+
+impl<S: Clone> Program<S> {
+    /// Grounds the decisions and constraints of this program.
+    pub fn ground(&self) -> (Problem<S>, Vec<Diagnostic<S>>) {
+        let mut grounder = Grounder::new(self, false);
+        grounder.derive_possible();
+        let problem = grounder.problem();
+        (problem, grounder.diagnostics)
+    }
+
+    /// Grounds this program like [Program::ground], but with a variable for
+    /// each fact that may be retracted to explain why no solution exists.
+    ///
+    /// Facts are only retractable if they belong to relations made of nothing
+    /// but facts, and are only used directly by decisions, constraints, and
+    /// rules that depend on decisions.
+    pub fn ground_retractable(&self) -> (Problem<S>, Vec<Diagnostic<S>>) {
+        let mut grounder = Grounder::new(self, true);
+        grounder.derive_possible();
+        let problem = grounder.problem();
+        (problem, grounder.diagnostics)
+    }
+}
+
+/// A grounded decision problem.
+#[derive(Clone, Debug)]
+pub struct Problem<S> {
+    /// What each variable stands for.
+    pub variables: Vec<Variable>,
+
+    /// The variable of each tuple of a decision-dependent relation.
+    pub tuples: HashMap<(ResourceId, Tuple), Var>,
+
+    /// Where each variable originates in the source, if anywhere.
+    pub spans: Vec<Option<S>>,
+
+    /// The relations whose tuples are decided.
+    pub decisions: BTreeSet<ResourceId>,
+
+    /// The relations that depend on decisions, including the decisions themselves.
+    pub conditional: BTreeSet<ResourceId>,
+
+    /// Clauses relating the tuples of decision-dependent relations.
+    pub clauses: Vec<Vec<Lit>>,
+
+    /// The grounded instances of every constraint.
+    pub constraints: Vec<GroundConstraint<S>>,
+
+    /// The variable of each retractable fact and the span of its head.
+    pub facts: Vec<(Var, S)>,
+
+    /// The grounded instances of every objective.
+    pub objectives: Vec<GroundObjective<S>>,
+
+    /// The factor that the weights of soft constraints are scaled by, so that
+    /// objectives of a higher priority outweigh them.
+    pub soft_scale: u64,
+}
+
+/// The meaning of a variable in a grounded problem.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Variable {
+    /// Holds when a tuple of a decision-dependent relation is true.
+    Tuple(ResourceId, Tuple),
+
+    /// Holds when every one of the given literals holds.
+    Conjunction(Vec<Lit>),
+
+    /// Holds when the constraint at the given index is enforced.
+    Activation(usize),
+
+    /// Holds when a tuple or constraint instance may exist, given which
+    /// retractable facts hold.
+    Possibility,
+
+    /// Holds when at least the given number of the given literals hold.
+    AtLeast(Vec<Lit>, usize),
+
+    /// Holds when the given bit of a recursive tuple's level is set.
+    ///
+    /// Each tuple that holds in a recursive cycle must be derived from
+    /// tuples of the cycle at lower levels, so that no cycle supports itself.
+    Level(ResourceId, Tuple, usize),
+
+    /// Only holds if the first level is less than the second, given their
+    /// bits from the least significant.
+    Below(Vec<Lit>, Vec<Lit>),
+}
+
+/// A decision tuple that is fixed to hold or not to hold, regardless of
+/// what the program would otherwise decide.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pin {
+    /// The relation of the tuple.
+    pub relation: ResourceId,
+
+    /// The pinned tuple.
+    pub tuple: Tuple,
+
+    /// Whether the tuple holds, or else is forbidden.
+    pub value: bool,
+}
+
+/// The instance of a constraint for one set of captured values.
+#[derive(Clone, Debug)]
+pub struct GroundConstraint<S> {
+    /// The span of the constraint's kind.
+    pub span: S,
+
+    /// The values of the constraint's captured variables.
+    pub captures: Vec<Value>,
+
+    /// The kind of the constraint.
+    pub kind: ConstraintKind,
+
+    /// The penalty for violating this constraint, if it is soft.
+    pub weight: Option<u64>,
+
+    /// The variable that enforces this constraint when it holds.
+    pub activation: Var,
+
+    /// The literal that enforces this constraint, which differs from its
+    /// activation when retracting facts could remove this instance.
+    pub guard: Lit,
+
+    /// Each body instance that depends on decisions.
+    pub lits: Vec<Lit>,
+
+    /// The number of body instances that always hold.
+    pub fixed: usize,
+}
+
+/// An objective grounded into the instances of its body.
+#[derive(Clone, Debug)]
+pub struct GroundObjective<S> {
+    /// The span of the objective's aggregate.
+    pub span: S,
+
+    pub sense: ObjectiveSense,
+    pub kind: AggregateKind,
+    pub priority: i64,
+
+    /// The literal of each instance that depends on decisions, or nothing
+    /// if it always holds, along with the value that it aggregates.
+    pub instances: Vec<(Option<Lit>, i64)>,
+
+    /// The literals whose truth worsens this objective, and by how much.
+    pub penalties: Vec<(Lit, u64)>,
+
+    /// The factor that the penalties are scaled by, so that objectives of a
+    /// higher priority outweigh those of a lower priority.
+    pub scale: u64,
+}
+
+impl<S> GroundObjective<S> {
+    /// Evaluates the aggregate of this objective in a model.
+    ///
+    /// Returns nothing for the minimum or maximum of no instances, or for a
+    /// sum that overflows an `i64`.
+    pub fn value(&self, model: &[bool]) -> Option<i64> {
+        let mut values = self
+            .instances
+            .iter()
+            .filter(|(lit, _)| lit.is_none_or(|lit| model[lit.var()] == lit.is_positive()))
+            .map(|(_, value)| *value);
+
+        match self.kind {
+            AggregateKind::Count => Some(values.count() as i64),
+            AggregateKind::Sum => values.try_fold(0i64, i64::checked_add),
+            AggregateKind::Min => values.min(),
+            AggregateKind::Max => values.max(),
+        }
+    }
+}
+
+impl<S> Problem<S> {
+    /// Encodes this problem into a formula, enforcing every hard constraint
+    /// and retractable fact.
+    pub fn formula(&self) -> Formula {
+        let mut formula = self.relaxed_formula();
+
+        for selector in self.selectors() {
+            formula.clauses.push(vec![selector]);
+        }
+
+        formula
+    }
+
+    /// Encodes this problem into a formula that only enforces its hard
+    /// constraints and retractable facts when its [Problem::selectors] hold.
+    pub fn relaxed_formula(&self) -> Formula {
+        let mut formula = Formula {
+            num_vars: self.variables.len(),
+            clauses: self.clauses.clone(),
+            cardinalities: vec![],
+        };
+
+        for constraint in self.constraints.iter() {
+            constraint.encode(&mut formula);
+        }
+
+        for (var, variable) in self.variables.iter().enumerate() {
+            let Variable::AtLeast(lits, count) = variable else {
+                continue;
+            };
+
+            let lit = Lit::positive(var);
+            formula.cardinalities.push(Cardinality {
+                guard: Some(lit),
+                lits: lits.clone(),
+                min: *count,
+                max: lits.len(),
+            });
+
+            formula.cardinalities.push(Cardinality {
+                guard: Some(!lit),
+                lits: lits.clone(),
+                min: 0,
+                max: count - 1,
+            });
+        }
+
+        formula
+    }
+
+    /// Lists the literals that enforce each hard constraint and retractable fact.
+    pub fn selectors(&self) -> Vec<Lit> {
+        let constraints = self
+            .constraints
+            .iter()
+            .filter(|constraint| constraint.weight.is_none())
+            .map(|constraint| constraint.activation);
+
+        let facts = self.facts.iter().map(|(var, _span)| *var);
+
+        let mut selectors: Vec<_> = constraints.chain(facts).map(Lit::positive).collect();
+        selectors.sort();
+        selectors.dedup();
+        selectors
+    }
+
+    /// Lists the literals whose truth incurs a penalty, and the penalty of each.
+    ///
+    /// Each soft constraint is penalized when it is not enforced, and each
+    /// objective when it is worsened. Penalties are scaled so that those of
+    /// a higher priority outweigh every penalty of a lower priority.
+    pub fn objective(&self) -> Vec<(Lit, u64)> {
+        let soft = self.constraints.iter().filter_map(|constraint| {
+            let weight = constraint.weight?;
+            Some((
+                Lit::negative(constraint.activation),
+                weight * self.soft_scale,
+            ))
+        });
+
+        let objectives = self.objectives.iter().flat_map(|objective| {
+            objective
+                .penalties
+                .iter()
+                .map(|(lit, weight)| (*lit, weight * objective.scale))
+        });
+
+        soft.chain(objectives).collect()
+    }
+
+    /// Sums the weights of the soft constraints that a model violates.
+    pub fn penalty(&self, model: &[bool]) -> u64 {
+        self.constraints
+            .iter()
+            .filter(|constraint| !constraint.is_satisfied(model))
+            .filter_map(|constraint| constraint.weight)
+            .sum()
+    }
+
+    /// Lists the literals that incur a penalty for departing from a prior
+    /// solution, each with the same weight.
+    ///
+    /// A decision tuple is penalized when it is false but in the prior
+    /// solution, or when it is true but not in it.
+    pub fn deviations(
+        &self,
+        prior: &BTreeMap<ResourceId, BTreeSet<Tuple>>,
+        weight: u64,
+    ) -> Vec<(Lit, u64)> {
+        self.decision_vars(|_| true)
+            .into_iter()
+            .map(|var| {
+                let (relation, tuple) = self.tuple(var).unwrap();
+                let in_prior = prior
+                    .get(relation)
+                    .is_some_and(|tuples| tuples.contains(tuple));
+
+                let lit = Lit::positive(var);
+                (if in_prior { !lit } else { lit }, weight)
+            })
+            .collect()
+    }
+
+    /// Maps a model of this problem's formula to the contents of every
+    /// decision-dependent relation.
+    pub fn solution(&self, model: &[bool]) -> BTreeMap<ResourceId, BTreeSet<Tuple>> {
+        let mut relations: BTreeMap<_, BTreeSet<_>> = self
+            .conditional
+            .iter()
+            .map(|relation| (relation.clone(), BTreeSet::new()))
+            .collect();
+
+        for (var, value) in model.iter().enumerate() {
+            if let (true, Some((relation, tuple))) = (value, self.tuple(var)) {
+                relations
+                    .entry(relation.clone())
+                    .or_default()
+                    .insert(tuple.clone());
+            }
+        }
+
+        relations
+    }
+
+    /// Lists the variables of every tuple of the decision relations that
+    /// pass a filter.
+    pub fn decision_vars(&self, filter: impl Fn(&ResourceId) -> bool) -> Vec<Var> {
+        (0..self.variables.len())
+            .filter(|var| match self.tuple(*var) {
+                Some((relation, _)) => self.decisions.contains(relation) && filter(relation),
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Finds the variable of a tuple of a decision-dependent relation.
+    pub fn tuple_var(&self, relation: &ResourceId, tuple: &Tuple) -> Option<Var> {
+        self.tuples.get(&(relation.clone(), tuple.clone())).copied()
+    }
+
+    /// Finds the literal that holds when a pin is respected.
+    ///
+    /// Returns nothing if the pinned tuple can never hold.
+    pub fn pin_lit(&self, pin: &Pin) -> Option<Lit> {
+        let var = self.tuple_var(&pin.relation, &pin.tuple)?;
+        let lit = Lit::positive(var);
+        Some(if pin.value { lit } else { !lit })
+    }
+
+    /// Fixes a decision tuple with a unit clause.
+    ///
+    /// Returns false if the tuple is pinned to hold but can never hold.
+    /// Forbidding such a tuple needs no clause.
+    pub fn pin(&mut self, pin: &Pin) -> bool {
+        match self.pin_lit(pin) {
+            Some(lit) => {
+                self.clauses.push(vec![lit]);
+                true
+            }
+            None => !pin.value,
+        }
+    }
+
+    /// Finds the tuple of each variable.
+    pub fn tuple(&self, var: Var) -> Option<(&ResourceId, &Tuple)> {
+        match self.variables.get(var)? {
+            Variable::Tuple(relation, tuple) => Some((relation, tuple)),
+            _ => None,
+        }
+    }
+}
+
+impl<S: Clone + PartialEq> Problem<S> {
+    /// Explains why no solution exists, given an unsatisfiable subset of
+    /// this problem's [Problem::selectors].
+    ///
+    /// Returns nothing if the subset is empty, as then no constraint or fact
+    /// is to blame.
+    pub fn explain(&self, core: &[Lit]) -> Option<Diagnostic<S>> {
+        let mut labels: Vec<(S, Vec<String>)> = Vec::new();
+        let mut add_label = |span: &S, label: Option<String>| {
+            let idx = match labels.iter().position(|(other, _)| other == span) {
+                Some(idx) => idx,
+                None => {
+                    labels.push((span.clone(), vec![]));
+                    labels.len() - 1
+                }
+            };
+
+            labels[idx].1.extend(label);
+        };
+
+        let mut core = core.to_vec();
+        core.sort();
+
+        for lit in core.iter() {
+            match self.variables.get(lit.var()) {
+                Some(Variable::Activation(idx)) => {
+                    let constraint = &self.constraints[*idx];
+                    let captures = (!constraint.captures.is_empty()).then(|| {
+                        let captures: Vec<_> = constraint
+                            .captures
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect();
+
+                        format!("({})", captures.join(", "))
+                    });
+
+                    add_label(&constraint.span, captures);
+                }
+                Some(Variable::Tuple(_, _)) => {
+                    for (_var, span) in self.facts.iter().filter(|(var, _)| *var == lit.var()) {
+                        add_label(span, None);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let is_fact = |span: &S| self.facts.iter().any(|(_, fact)| fact == span);
+
+        // prefer to point at a constraint over a fact
+        let (span, _) = labels
+            .iter()
+            .find(|(span, _)| !is_fact(span))
+            .or(labels.first())?;
+
+        let span = span.clone();
+
+        let labels = labels
+            .into_iter()
+            .map(|(span, captures)| {
+                let is_fact = is_fact(&span);
+                let inner = match (is_fact, captures.is_empty()) {
+                    (true, _) => "Stated here.".to_string(),
+                    (false, true) => "Required here.".to_string(),
+                    (false, false) => format!("Required for {}.", captures.join(", ")),
+                };
+
+                Spanned { span, inner }
+            })
+            .collect();
+
+        Some(Diagnostic {
+            span,
+            kind: DiagnosticKind::Error,
+            message: "No solution satisfies these constraints and facts together".to_string(),
+            labels,
+        })
+    }
+}
+
+impl<S: Clone> Problem<S> {
+    /// Reports each constraint that a model violates, with the cost of each
+    /// soft one.
+    pub fn violations(&self, model: &[bool]) -> Vec<Diagnostic<S>> {
+        self.constraints
+            .iter()
+            .filter(|constraint| !constraint.is_satisfied(model))
+            .map(|constraint| {
+                let label = if constraint.captures.is_empty() {
+                    "Violated here.".to_string()
+                } else {
+                    let captures: Vec<_> = constraint
+                        .captures
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect();
+
+                    format!("Violated for ({}).", captures.join(", "))
+                };
+
+                let (kind, message) = match constraint.weight {
+                    Some(weight) => (
+                        DiagnosticKind::Warning,
+                        format!("Soft constraint violated at a cost of {weight}"),
+                    ),
+                    None => (
+                        DiagnosticKind::Error,
+                        "Hard constraint violated".to_string(),
+                    ),
+                };
+
+                Diagnostic {
+                    span: constraint.span.clone(),
+                    kind,
+                    message,
+                    labels: vec![Spanned {
+                        span: constraint.span.clone(),
+                        inner: label,
+                    }],
+                }
+            })
+            .collect()
+    }
+}
+
+impl<S> GroundConstraint<S> {
+    /// Tests if a model satisfies this constraint, regardless of its activation.
+    pub fn is_satisfied(&self, model: &[bool]) -> bool {
+        let holds = |lit: &Lit| model[lit.var()] == lit.is_positive();
+        let num = self.fixed + self.lits.iter().filter(|lit| holds(lit)).count();
+
+        match self.kind {
+            ConstraintKind::Uniform => num == 0 || num == self.fixed + self.lits.len(),
+            ConstraintKind::Cardinality(kind, bound) => {
+                use CardinalityConstraintKind::*;
+                let num = num as i64;
+                match kind {
+                    Only => num == bound,
+                    AtMost => num <= bound,
+                    AtLeast => num >= bound,
+                }
+            }
+        }
+    }
+
+    /// Adds the requirements of this constraint, guarded by its activation, to a formula.
+    pub fn encode(&self, formula: &mut Formula) {
+        let guard = self.guard;
+
+        match self.kind {
+            // every instance holds or none of them do
+            ConstraintKind::Uniform if self.fixed > 0 => {
+                for lit in self.lits.iter() {
+                    formula.clauses.push(vec![!guard, *lit]);
+                }
+            }
+            ConstraintKind::Uniform => {
+                for (idx, lit) in self.lits.iter().enumerate() {
+                    let next = self.lits[(idx + 1) % self.lits.len()];
+                    formula.clauses.push(vec![!guard, !*lit, next]);
+                }
+            }
+            ConstraintKind::Cardinality(kind, bound) => {
+                use CardinalityConstraintKind::*;
+                let bound = bound - self.fixed as i64;
+                let num = self.lits.len() as i64;
+
+                let (min, max) = match kind {
+                    Only => (bound, bound),
+                    AtMost => (0, bound),
+                    AtLeast => (bound, num),
+                };
+
+                if max < 0 || min > num {
+                    formula.clauses.push(vec![!guard]);
+                    return;
+                }
+
+                let min = min.max(0) as usize;
+                let max = max.min(num) as usize;
+
+                if min == 0 && max == self.lits.len() {
+                    return;
+                }
+
+                formula.cardinalities.push(Cardinality {
+                    guard: Some(guard),
+                    lits: self.lits.clone(),
+                    min,
+                    max,
+                });
+            }
+        }
+    }
+}
+
+/// A requirement on decision-dependent tuples for a match to hold.
+#[derive(Clone, Debug)]
+enum Condition {
+    /// A tuple, and whether it must hold or not.
+    Tuple(bool, ResourceId, Tuple),
+
+    /// Exactly this many of some tuples of a relation must hold.
+    Count(ResourceId, Vec<Tuple>, usize),
+}
+
+impl Condition {
+    /// Gets the tuple of this condition if it must hold.
+    fn positive(&self) -> Option<(&ResourceId, &Tuple)> {
+        match self {
+            Condition::Tuple(true, relation, tuple) => Some((relation, tuple)),
+            _ => None,
+        }
+    }
+}
+
+/// A partial evaluation of an item's body.
+#[derive(Clone, Debug)]
+struct Match {
+    values: Vec<Option<Value>>,
+    conditions: Vec<Condition>,
+}
+
+impl Match {
+    /// Binds the terms of a pattern to a tuple, returning false on mismatch.
+    fn unify(&mut self, terms: &[Term<usize>], tuple: &[Value]) -> bool {
+        if terms.len() != tuple.len() {
+            return false;
+        }
+
+        for (term, val) in terms.iter().zip(tuple.iter()) {
+            match term {
+                AnyTerm::Value(expected) if expected != val => return false,
+                AnyTerm::Value(_) => {}
+                AnyTerm::Variable(var) => match &self.values[*var] {
+                    Some(bound) if bound != val => return false,
+                    Some(_) => {}
+                    None => self.values[*var] = Some(val.clone()),
+                },
+            }
+        }
+
+        true
+    }
+
+    /// Substitutes the bound values of a pattern, if all of its variables are bound.
+    fn substitute(&self, terms: &[Term<usize>]) -> Option<Tuple> {
+        terms
+            .iter()
+            .map(|term| match term {
+                AnyTerm::Variable(var) => self.values[*var].clone(),
+                AnyTerm::Value(val) => Some(val.clone()),
+            })
+            .collect()
+    }
+}
+
+/// The tuples of a relation grouped by the values of some of their terms.
+struct Index<'a> {
+    /// The indices of the terms that tuples are grouped by.
+    columns: Vec<usize>,
+
+    /// The tuples of each group.
+    groups: HashMap<Vec<Value>, Vec<&'a Tuple>>,
+}
+
+impl Index<'_> {
+    /// Finds the tuples that agree with the bound terms of a pattern.
+    fn get(&self, m: &Match, terms: &[Term<usize>]) -> Vec<&Tuple> {
+        let key = self
+            .columns
+            .iter()
+            .map(|col| match &terms[*col] {
+                AnyTerm::Variable(var) => m.values[*var].clone(),
+                AnyTerm::Value(val) => Some(val.clone()),
+            })
+            .collect::<Option<Vec<_>>>();
+
+        key.and_then(|key| self.groups.get(&key))
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+/// A single grounded rule instance, supporting its head tuple.
+struct Support<S> {
+    /// The head of the rule or decision that derived this instance.
+    span: S,
+
+    /// Whether the head must hold when its body does, rather than only may.
+    forced: bool,
+
+    /// The conditions of the instance's body.
+    conditions: Vec<Condition>,
+}
+
+/// The instances of a constraint for one set of captured values.
+#[derive(Default)]
+struct Group {
+    /// Each instance that depends on decisions.
+    lits: Vec<Lit>,
+
+    /// The number of instances that always hold.
+    fixed: usize,
+
+    /// Each literal under which an instance may exist, given which
+    /// retractable facts hold.
+    possibilities: Vec<Lit>,
+
+    /// Whether an instance exists regardless of which facts are retracted.
+    is_certain: bool,
+}
+
+struct Grounder<'a, S> {
+    program: &'a Program<S>,
+    conditional: BTreeSet<ResourceId>,
+
+    /// Relations whose facts each have a variable, so that they may be retracted.
+    retractable: BTreeSet<ResourceId>,
+
+    decisions: BTreeSet<ResourceId>,
+    possible: BTreeMap<ResourceId, BTreeSet<Tuple>>,
+    supports: BTreeMap<(ResourceId, Tuple), Vec<Support<S>>>,
+    diagnostics: Vec<Diagnostic<S>>,
+}
+
+impl<'a, S: Clone> Grounder<'a, S> {
+    fn new(program: &'a Program<S>, retract: bool) -> Self {
+        let decisions: BTreeSet<_> = program
+            .items
+            .iter()
+            .filter_map(|item| match &item.inner {
+                ModuleItem::Decision(Decision(rule)) => Some(rule.head.relation.inner.clone()),
+                _ => None,
+            })
+            .collect();
+
+        // find every relation that transitively depends on a decision
+        let mut conditional = decisions.clone();
+        loop {
+            let len = conditional.len();
+            for item in program.items.iter() {
+                for (head, body, _stratified) in item.dependencies() {
+                    if conditional.contains(&body) {
+                        conditional.insert(head);
+                    }
+                }
+            }
+
+            if conditional.len() == len {
+                break;
+            }
+        }
+
+        let retractable = if retract {
+            Self::retractable(program, &conditional)
+        } else {
+            BTreeSet::new()
+        };
+
+        Self {
+            program,
+            conditional,
+            retractable,
+            decisions,
+            possible: BTreeMap::new(),
+            supports: BTreeMap::new(),
+            diagnostics: vec![],
+        }
+    }
+
+    /// Finds the relations whose facts can be retracted without re-evaluating
+    /// any relation that does not depend on decisions.
+    fn retractable(
+        program: &Program<S>,
+        conditional: &BTreeSet<ResourceId>,
+    ) -> BTreeSet<ResourceId> {
+        let mut facts = BTreeSet::new();
+        let mut derived = BTreeSet::new();
+        for item in program.items.iter() {
+            if let Some(rule) = item.rule_or_decision() {
+                let relation = rule.head.inner.relation.inner;
+                if item.is_fact() {
+                    facts.insert(relation);
+                } else {
+                    derived.insert(relation);
+                }
+            }
+        }
+
+        // relations used outside of grounding are evaluated with every fact
+        let mut evaluated = BTreeSet::new();
+        for item in program.items.iter() {
+            let is_grounded = match &item.inner {
+                ModuleItem::Rule(rule) => conditional.contains(&rule.head.relation.inner),
+                ModuleItem::Decision(_) | ModuleItem::Constraint(_) => true,
+                _ => false,
+            };
+
+            for literal in item.body() {
+                let is_aggregate = matches!(literal.inner, Literal::Aggregate(_));
+                if !is_grounded || is_aggregate {
+                    evaluated.insert(literal.inner.atom().relation.inner.clone());
+                }
+            }
+        }
+
+        facts
+            .into_iter()
+            .filter(|relation| !derived.contains(relation) && !evaluated.contains(relation))
+            .collect()
+    }
+
+    /// Derives every tuple of decision-dependent relations that may possibly hold.
+    ///
+    /// Negated conditions are assumed to hold, so that the possible tuples
+    /// over-approximate every outcome of the decisions.
+    fn derive_possible(&mut self) {
+        let rules: Vec<_> = self
+            .program
+            .items
+            .iter()
+            .filter(|item| {
+                item.rule_or_decision()
+                    .is_some_and(|rule| self.conditional.contains(&rule.head.relation.inner))
+            })
+            .collect();
+
+        loop {
+            let mut supports: BTreeMap<_, Vec<_>> = BTreeMap::new();
+            for item in rules.iter() {
+                let rule = item.rule_or_decision().unwrap();
+                let forced = matches!(item.inner, ModuleItem::Rule(_));
+                let head = rule.head.inner.pattern.into_leaves();
+                let relation = rule.head.inner.relation.inner;
+
+                // errors in rules are reported by their lowering
+                let Ok(matches) = self.eval_body(item) else {
+                    continue;
+                };
+
+                for m in matches {
+                    let Some(tuple) = m.substitute(&head) else {
+                        continue;
+                    };
+
+                    supports
+                        .entry((relation.clone(), tuple))
+                        .or_default()
+                        .push(Support {
+                            span: rule.head.span.clone(),
+                            forced,
+                            conditions: m.conditions,
+                        });
+                }
+            }
+
+            let mut possible: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+            for (relation, tuple) in supports.keys() {
+                possible
+                    .entry(relation.clone())
+                    .or_default()
+                    .insert(tuple.clone());
+            }
+
+            self.supports = supports;
+
+            if possible == self.possible {
+                break;
+            }
+
+            self.possible = possible;
+        }
+    }
+
+    /// Creates the grounded problem from the derived supports and constraints.
+    fn problem(&mut self) -> Problem<S> {
+        let mut builder = Builder::default();
+
+        // locate every derived tuple before any clauses refer to it
+        for ((relation, tuple), supports) in self.supports.iter() {
+            let head = builder.tuple(relation.clone(), tuple.clone());
+            builder.spans[head.var()] = Some(supports[0].span.clone());
+        }
+
+        let mut facts = Vec::new();
+        for item in self.program.items.iter() {
+            let Some(rule) = item.rule_or_decision() else {
+                continue;
+            };
+
+            let relation = rule.head.inner.relation.inner;
+            if !item.is_fact() || !self.retractable.contains(&relation) {
+                continue;
+            }
+
+            let m = Match {
+                values: vec![None; item.variables.len()],
+                conditions: vec![],
+            };
+
+            let Some(tuple) = m.substitute(&rule.head.inner.pattern.into_leaves()) else {
+                continue;
+            };
+
+            let fact = builder.tuple(relation, tuple);
+            builder.spans[fact.var()] = Some(rule.head.span.clone());
+            facts.push((fact.var(), rule.head.span));
+        }
+
+        let levels = self.levels(&mut builder);
+
+        for ((relation, tuple), supports) in self.supports.iter() {
+            let key = (relation.clone(), tuple.clone());
+            let head = builder.tuple(key.0.clone(), key.1.clone());
+
+            let mut any = vec![!head];
+            let mut is_supported = false;
+            for support in supports {
+                let body = builder.conjunction(&support.conditions);
+
+                match (support.forced, body) {
+                    (true, None) => builder.clauses.push(vec![head]),
+                    (true, Some(body)) => builder.clauses.push(vec![!body, head]),
+                    (false, _) => {}
+                }
+
+                // instances only support a recursive tuple through tuples of its
+                // cycle at lower levels
+                let mut lits: Vec<_> = body.into_iter().collect();
+                if let Some((component, upper)) = levels.get(&key) {
+                    for (relation, tuple) in
+                        support.conditions.iter().filter_map(Condition::positive)
+                    {
+                        let key = (relation.clone(), tuple.clone());
+                        if let Some((other, lower)) = levels.get(&key) {
+                            if other == component {
+                                lits.push(builder.below(lower, upper));
+                            }
+                        }
+                    }
+                }
+
+                match builder.and(lits) {
+                    Some(body) => any.push(body),
+                    None => is_supported = true,
+                }
+            }
+
+            // tuples only hold if at least one of their instances does
+            if !is_supported {
+                builder.clauses.push(any);
+            }
+        }
+
+        let mut constraints = Vec::new();
+        for item in self.program.items.iter() {
+            let ModuleItem::Constraint(constraint) = &item.inner else {
+                continue;
+            };
+
+            let matches = match self.eval_body(item) {
+                Ok(matches) => matches,
+                Err(d) => {
+                    self.diagnostics.push(d);
+                    continue;
+                }
+            };
+
+            let mut groups: BTreeMap<Vec<Value>, Group> = BTreeMap::new();
+            for m in matches {
+                let captures = constraint
+                    .captures
+                    .iter()
+                    .map(|var| m.values[var.inner].clone())
+                    .collect::<Option<_>>();
+
+                let Some(captures) = captures else {
+                    continue;
+                };
+
+                let group = groups.entry(captures).or_default();
+                match builder.conjunction(&m.conditions) {
+                    Some(lit) => group.lits.push(lit),
+                    None => group.fixed += 1,
+                }
+
+                if self.retractable.is_empty() || group.is_certain {
+                    group.is_certain = true;
+                    continue;
+                }
+
+                let possibilities = m
+                    .conditions
+                    .iter()
+                    .filter_map(Condition::positive)
+                    .filter_map(|(relation, tuple)| self.possibility(&mut builder, relation, tuple))
+                    .collect();
+
+                match builder.and(possibilities) {
+                    Some(lit) => group.possibilities.push(lit),
+                    None => group.is_certain = true,
+                }
+            }
+
+            let weight = match &constraint.soft {
+                None => None,
+                Some(soft) if soft.inner > 0 => Some(soft.inner as u64),
+                Some(soft) => {
+                    self.diagnostics.push(Diagnostic {
+                        span: soft.span.clone(),
+                        kind: DiagnosticKind::Error,
+                        message: "Soft constraint weights must be positive".to_string(),
+                        labels: vec![soft.clone().map(|_| "Weighted here.".to_string())],
+                    });
+
+                    continue;
+                }
+            };
+
+            for (captures, group) in groups {
+                let activation = builder.var(Variable::Activation(constraints.len()));
+                builder.spans[activation] = Some(constraint.kind.span.clone());
+
+                let mut guard = Lit::positive(activation);
+                if !group.is_certain {
+                    let exists = Lit::positive(builder.var(Variable::Possibility));
+                    builder.define_any(exists, group.possibilities);
+                    guard = builder.and(vec![guard, exists]).unwrap();
+                }
+
+                constraints.push(GroundConstraint {
+                    span: constraint.kind.span.clone(),
+                    captures,
+                    kind: constraint.kind.inner,
+                    weight,
+                    activation,
+                    guard,
+                    lits: group.lits,
+                    fixed: group.fixed,
+                });
+            }
+        }
+
+        let mut objectives = Vec::new();
+        for item in self.program.items.iter() {
+            let ModuleItem::Objective(objective) = &item.inner else {
+                continue;
+            };
+
+            match self.eval_body(item) {
+                Ok(matches) => objectives.push(Self::objective(&mut builder, objective, matches)),
+                Err(d) => self.diagnostics.push(d),
+            }
+        }
+
+        let soft_scale = self.scale(&constraints, &mut objectives);
+
+        Problem {
+            variables: builder.variables,
+            tuples: builder.tuples,
+            spans: builder.spans,
+            decisions: self.decisions.clone(),
+            conditional: self.conditional.clone(),
+            clauses: builder.clauses,
+            constraints,
+            facts,
+            objectives,
+            soft_scale,
+        }
+    }
+
+    /// Creates the level bits of every tuple in a recursive cycle of derived
+    /// tuples, along with the index of the cycle that it belongs to.
+    ///
+    /// Each level has enough bits to order every tuple of its cycle.
+    fn levels(&self, builder: &mut Builder<S>) -> HashMap<(ResourceId, Tuple), (usize, Vec<Lit>)> {
+        let keys: Vec<_> = self.supports.keys().collect();
+        let indices: HashMap<_, _> = keys
+            .iter()
+            .enumerate()
+            .map(|(idx, key)| (*key, idx))
+            .collect();
+
+        let edges: Vec<Vec<usize>> = self
+            .supports
+            .values()
+            .map(|supports| {
+                supports
+                    .iter()
+                    .flat_map(|support| support.conditions.iter())
+                    .filter_map(Condition::positive)
+                    .filter_map(|(relation, tuple)| {
+                        indices.get(&(relation.clone(), tuple.clone())).copied()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let components = components(&edges);
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for component in components.iter() {
+            *sizes.entry(*component).or_default() += 1;
+        }
+
+        let mut levels = HashMap::new();
+        for (idx, key) in keys.into_iter().enumerate() {
+            let component = components[idx];
+            let size = sizes[&component];
+            let is_cyclic = size > 1 || edges[idx].contains(&idx);
+            if !is_cyclic {
+                continue;
+            }
+
+            // enough bits to count from zero to one less than the size
+            let bits = ((usize::BITS - (size - 1).leading_zeros()) as usize).max(1);
+            let lits = (0..bits)
+                .map(|bit| {
+                    let variable = Variable::Level(key.0.clone(), key.1.clone(), bit);
+                    Lit::positive(builder.var(variable))
+                })
+                .collect();
+
+            levels.insert(key.clone(), (component, lits));
+        }
+
+        levels
+    }
+
+    /// Grounds an objective from the matches of its body.
+    fn objective(
+        builder: &mut Builder<S>,
+        objective: &Objective<S, ResourceId, usize>,
+        matches: Vec<Match>,
+    ) -> GroundObjective<S> {
+        let mut instances = Vec::new();
+        for m in matches {
+            let value = match &objective.target {
+                None => 1,
+                Some(target) => match &m.values[target.inner] {
+                    Some(Value::Integer(value)) => *value,
+                    // non-integer targets are reported by typing
+                    _ => continue,
+                },
+            };
+
+            instances.push((builder.conjunction(&m.conditions), value));
+        }
+
+        let sense = objective.sense.inner;
+        let kind = objective.kind.inner;
+        let minimize = sense == ObjectiveSense::Minimize;
+        let mut penalties = Vec::new();
+
+        match kind {
+            AggregateKind::Count | AggregateKind::Sum => {
+                for (lit, value) in instances.iter() {
+                    let Some(lit) = lit else {
+                        continue;
+                    };
+
+                    let value = *value as i128;
+                    let value = if minimize { value } else { -value };
+                    let weight = value.unsigned_abs() as u64;
+
+                    match value.signum() {
+                        1 => penalties.push((*lit, weight)),
+                        -1 => penalties.push((!*lit, weight)),
+                        _ => {}
+                    }
+                }
+            }
+            AggregateKind::Min | AggregateKind::Max => {
+                let mut values: Vec<_> = instances.iter().map(|(_, value)| *value).collect();
+                values.sort();
+                values.dedup();
+
+                // penalize each step between consecutive values by its size
+                for step in values.windows(2) {
+                    let (low, high) = (step[0], step[1]);
+                    let weight = (high as i128 - low as i128) as u64;
+
+                    // whether any instance reaches past the step, and if that is worse
+                    let (reached, is_worse): (Vec<_>, _) = match kind {
+                        AggregateKind::Max => (
+                            instances
+                                .iter()
+                                .filter(|(_, value)| *value >= high)
+                                .collect(),
+                            minimize,
+                        ),
+                        _ => (
+                            instances
+                                .iter()
+                                .filter(|(_, value)| *value <= low)
+                                .collect(),
+                            !minimize,
+                        ),
+                    };
+
+                    // instances that always hold always reach past the step
+                    let lits: Option<Vec<_>> = reached.iter().map(|(lit, _)| *lit).collect();
+                    let Some(lits) = lits else {
+                        continue;
+                    };
+
+                    let lit = builder.at_least(lits, 1).unwrap();
+                    penalties.push((if is_worse { lit } else { !lit }, weight));
+                }
+            }
+        }
+
+        GroundObjective {
+            span: objective.kind.span.clone(),
+            sense,
+            kind,
+            priority: objective
+                .priority
+                .as_ref()
+                .map_or(0, |priority| priority.inner),
+            instances,
+            penalties,
+            scale: 1,
+        }
+    }
+
+    /// Scales the penalties of each priority so that they outweigh the sum
+    /// of every penalty of a lower priority.
+    ///
+    /// Returns the scale of the soft constraints, which have a priority of zero.
+    fn scale(
+        &mut self,
+        constraints: &[GroundConstraint<S>],
+        objectives: &mut [GroundObjective<S>],
+    ) -> u64 {
+        let mut totals: BTreeMap<i64, Option<u64>> = BTreeMap::new();
+        let soft = constraints
+            .iter()
+            .filter_map(|constraint| constraint.weight)
+            .try_fold(0u64, u64::checked_add);
+
+        totals.insert(0, soft);
+
+        for objective in objectives.iter() {
+            let total = objective
+                .penalties
+                .iter()
+                .map(|(_, weight)| *weight)
+                .try_fold(0u64, u64::checked_add);
+
+            let sum = totals.entry(objective.priority).or_insert(Some(0));
+            *sum = sum
+                .zip(total)
+                .and_then(|(sum, total)| sum.checked_add(total));
+        }
+
+        let mut scales = BTreeMap::new();
+        let mut scale = Some(1u64);
+        for (priority, total) in totals {
+            scales.insert(priority, scale.unwrap_or(1));
+            scale = scale
+                .zip(total.and_then(|total| total.checked_add(1)))
+                .and_then(|(scale, total)| scale.checked_mul(total));
+        }
+
+        if scale.is_none() {
+            if let Some(objective) = objectives.iter().max_by_key(|objective| objective.priority) {
+                self.diagnostics.push(Diagnostic {
+                    span: objective.span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: "Objectives are too large to optimize in order of priority"
+                        .to_string(),
+                    labels: vec![Spanned {
+                        span: objective.span.clone(),
+                        inner: "Optimized first.".to_string(),
+                    }],
+                });
+            } else if let Some(constraint) = constraints.iter().max_by_key(|c| c.weight) {
+                self.diagnostics.push(Diagnostic {
+                    span: constraint.span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message: "Soft constraints are too heavy to optimize".to_string(),
+                    labels: vec![Spanned {
+                        span: constraint.span.clone(),
+                        inner: "Heaviest soft constraint.".to_string(),
+                    }],
+                });
+            }
+        }
+
+        for objective in objectives.iter_mut() {
+            objective.scale = scales[&objective.priority];
+        }
+
+        scales[&0]
+    }
+
+    /// Evaluates every match of an item's body.
+    fn eval_body(&self, item: &IndexedItem<S, ResourceId>) -> DiagnosticResult<S, Vec<Match>> {
+        let body = item.body();
+        let mut matches = vec![Match {
+            values: vec![None; item.variables.len()],
+            conditions: vec![],
+        }];
+
+        let mut bound = BTreeSet::new();
+        let mut remaining: Vec<usize> = (0..body.len()).collect();
+        while !remaining.is_empty() {
+            // prefer literals that only filter matches, then literals that bind
+            let is_bound = |terms: &[Term<usize>]| {
+                terms.iter().all(|term| match term {
+                    AnyTerm::Variable(var) => bound.contains(var),
+                    AnyTerm::Value(_) => true,
+                })
+            };
+
+            let filter = remaining.iter().position(|idx| match &body[*idx].inner {
+                Literal::Atom(Atom {
+                    relation:
+                        Spanned {
+                            inner: ResourceId::Builtin(builtin),
+                            ..
+                        },
+                    pattern,
+                }) => {
+                    let bound: Vec<_> = pattern
+                        .clone()
+                        .into_leaves()
+                        .iter()
+                        .map(|term| is_bound(std::slice::from_ref(term)))
+                        .collect();
+
+                    builtin.can_eval(&bound)
+                }
+                Literal::Negated(atom) => is_bound(&atom.pattern.clone().into_leaves()),
+                _ => false,
+            });
+
+            let binding = || {
+                remaining.iter().position(|idx| match &body[*idx].inner {
+                    Literal::Atom(atom) => !matches!(atom.relation.inner, ResourceId::Builtin(_)),
+                    Literal::Aggregate(_) => true,
+                    Literal::Negated(_) => false,
+                })
+            };
+
+            let Some(next) = filter.or_else(binding) else {
+                let literal = &body[remaining[0]];
+                let message = match &literal.inner {
+                    Literal::Atom(atom) => {
+                        format!("Not enough bound terms to evaluate {}", atom.relation.inner)
+                    }
+                    _ => "Negated terms are not bound".to_string(),
+                };
+
+                return Err(Diagnostic {
+                    span: literal.span.clone(),
+                    kind: DiagnosticKind::Error,
+                    message,
+                    labels: vec![Spanned {
+                        span: literal.span.clone(),
+                        inner: "Bind more of these terms in other atoms.".to_string(),
+                    }],
+                });
+            };
+
+            let idx = remaining.remove(next);
+            let literal = &body[idx];
+            let outer = item.outer_variables(idx);
+            let index = self.index(literal, &bound);
+
+            let mut next = Vec::new();
+            for m in matches {
+                self.eval_literal(m, literal, &outer, index.as_ref(), &mut next)?;
+            }
+
+            matches = next;
+
+            literal.inner.clone().map_variables(&mut |_span, var| {
+                bound.insert(var);
+            });
+        }
+
+        Ok(matches)
+    }
+
+    /// Indexes the tuples of a positive atom by the terms that are bound
+    /// before it is evaluated, so that each match only visits the tuples
+    /// that agree with it.
+    fn index(
+        &self,
+        literal: &Spanned<S, Literal<S, ResourceId, Term<usize>>>,
+        bound: &BTreeSet<usize>,
+    ) -> Option<Index<'_>> {
+        let Literal::Atom(atom) = &literal.inner else {
+            return None;
+        };
+
+        if let ResourceId::Builtin(_) = atom.relation.inner {
+            return None;
+        }
+
+        let terms = atom.pattern.clone().into_leaves();
+        let columns: Vec<_> = terms
+            .iter()
+            .enumerate()
+            .filter(|(_idx, term)| match term {
+                AnyTerm::Variable(var) => bound.contains(var),
+                AnyTerm::Value(_) => true,
+            })
+            .map(|(idx, _term)| idx)
+            .collect();
+
+        let mut groups: HashMap<_, Vec<_>> = HashMap::new();
+        for tuple in self.tuples(&atom.relation.inner) {
+            if tuple.len() == terms.len() {
+                let key: Vec<_> = columns.iter().map(|col| tuple[*col].clone()).collect();
+                groups.entry(key).or_default().push(tuple);
+            }
+        }
+
+        Some(Index { columns, groups })
+    }
+
+    /// Evaluates a single body literal against a match, using the index of
+    /// the literal's tuples if it has one.
+    fn eval_literal(
+        &self,
+        m: Match,
+        literal: &Spanned<S, Literal<S, ResourceId, Term<usize>>>,
+        outer: &BTreeSet<usize>,
+        index: Option<&Index>,
+        output: &mut Vec<Match>,
+    ) -> DiagnosticResult<S, ()> {
+        match &literal.inner {
+            Literal::Atom(atom) => {
+                let terms = atom.pattern.clone().into_leaves();
+                if let ResourceId::Builtin(builtin) = &atom.relation.inner {
+                    let args: Vec<_> = terms
+                        .iter()
+                        .map(|term| match term {
+                            AnyTerm::Variable(var) => m.values[*var].clone(),
+                            AnyTerm::Value(val) => Some(val.clone()),
+                        })
+                        .collect();
+
+                    if let Some(d) = builtin.limit_diagnostic(literal.span.clone(), &args) {
+                        return Err(d);
+                    }
+
+                    for row in builtin.eval(&args) {
+                        let row: Vec<_> = row.into_iter().map(Value::Integer).collect();
+                        let mut m = m.clone();
+                        if m.unify(&terms, &row) {
+                            output.push(m);
+                        }
+                    }
+
+                    return Ok(());
+                }
+
+                let relation = &atom.relation.inner;
+                let is_conditional = self.conditional.contains(relation);
+                let tuples: Vec<&Tuple> = match index {
+                    Some(index) => index.get(&m, &terms),
+                    None => self.tuples(relation).iter().collect(),
+                };
+
+                for tuple in tuples {
+                    let mut m = m.clone();
+                    if !m.unify(&terms, tuple) {
+                        continue;
+                    }
+
+                    if is_conditional || self.retractable.contains(relation) {
+                        m.conditions
+                            .push(Condition::Tuple(true, relation.clone(), tuple.clone()));
+                    }
+
+                    output.push(m);
+                }
+            }
+            Literal::Negated(atom) => {
+                let relation = &atom.relation.inner;
+
+                if let ResourceId::Builtin(builtin) = relation {
+                    return Err(Diagnostic {
+                        span: literal.span.clone(),
+                        kind: DiagnosticKind::Error,
+                        message: format!("Built-in relation {builtin} cannot be negated"),
+                        labels: vec![Spanned {
+                            span: literal.span.clone(),
+                            inner: "Negated here.".to_string(),
+                        }],
+                    });
+                }
+
+                let Some(tuple) = m.substitute(&atom.pattern.clone().into_leaves()) else {
+                    return Ok(());
+                };
+
+                let is_conditional =
+                    self.conditional.contains(relation) || self.retractable.contains(relation);
+                let exists = self.tuples(relation).contains(&tuple);
+
+                let mut m = m;
+                match (is_conditional, exists) {
+                    (true, true) => {
+                        m.conditions
+                            .push(Condition::Tuple(false, relation.clone(), tuple))
+                    }
+                    (false, true) => return Ok(()),
+                    (_, false) => {}
+                }
+
+                output.push(m);
+            }
+            Literal::Aggregate(aggregate) => {
+                let relation = &aggregate.atom.relation.inner;
+                let is_conditional = self.conditional.contains(relation);
+
+                if is_conditional && aggregate.kind.inner != AggregateKind::Count {
+                    let verb = match aggregate.kind.inner {
+                        AggregateKind::Sum => "sum",
+                        AggregateKind::Min => "take the minimum of",
+                        _ => "take the maximum of",
+                    };
+
+                    return Err(Diagnostic {
+                        span: literal.span.clone(),
+                        kind: DiagnosticKind::Error,
+                        message: format!("Cannot {verb} {relation}, which depends on a decision"),
+                        labels: vec![Spanned {
+                            span: literal.span.clone(),
+                            inner: "Only counts of decision-dependent relations are supported."
+                                .to_string(),
+                        }],
+                    });
+                }
+
+                let terms = aggregate.atom.pattern.clone().into_leaves();
+                let mut vars = Vec::new();
+                for term in terms.iter() {
+                    if let AnyTerm::Variable(var) = term {
+                        if !vars.contains(var) {
+                            vars.push(*var);
+                        }
+                    }
+                }
+
+                let (group, local): (Vec<usize>, Vec<usize>) =
+                    vars.into_iter().partition(|var| outer.contains(var));
+
+                let target = aggregate
+                    .target
+                    .as_ref()
+                    .and_then(|target| match target.inner {
+                        AnyTerm::Variable(var) => local.iter().position(|el| *el == var),
+                        AnyTerm::Value(_) => None,
+                    });
+
+                let reducer = match (aggregate.kind.inner, target) {
+                    (AggregateKind::Count, _) => Reducer::Count,
+                    (AggregateKind::Sum, Some(idx)) => Reducer::Sum(idx),
+                    (AggregateKind::Min, Some(idx)) => Reducer::Min(idx),
+                    (AggregateKind::Max, Some(idx)) => Reducer::Max(idx),
+                    // targets outside of the atom are reported by lowering
+                    (_, None) => return Ok(()),
+                };
+
+                let mut groups: BTreeMap<Vec<Value>, BTreeSet<Vec<Value>>> = BTreeMap::new();
+                let mut sources: BTreeMap<Vec<Value>, Vec<Tuple>> = BTreeMap::new();
+                for tuple in self.tuples(relation) {
+                    let mut matched = m.clone();
+                    if !matched.unify(&terms, tuple) {
+                        continue;
+                    }
+
+                    let values = |vars: &[usize]| -> Vec<Value> {
+                        vars.iter()
+                            .map(|var| matched.values[*var].clone().unwrap())
+                            .collect()
+                    };
+
+                    groups
+                        .entry(values(&group))
+                        .or_default()
+                        .insert(values(&local));
+
+                    sources
+                        .entry(values(&group))
+                        .or_default()
+                        .push(tuple.clone());
+                }
+
+                let AnyTerm::Variable(result) = aggregate.result.inner else {
+                    return Ok(());
+                };
+
+                let terms: Vec<_> = group
+                    .iter()
+                    .chain([&result])
+                    .map(|var| AnyTerm::Variable(*var))
+                    .collect();
+
+                // the count of a decision-dependent relation is any number of
+                // its possible tuples, as long as one holds
+                if is_conditional {
+                    for (values, tuples) in sources {
+                        for count in 1..=tuples.len() {
+                            let value = Value::Integer(count as i64);
+                            let tuple: Vec<_> = values.iter().cloned().chain([value]).collect();
+
+                            let mut m = m.clone();
+                            if m.unify(&terms, &tuple) {
+                                let count =
+                                    Condition::Count(relation.clone(), tuples.clone(), count);
+                                m.conditions.push(count);
+                                output.push(m);
+                            }
+                        }
+                    }
+
+                    return Ok(());
+                }
+
+                for (values, locals) in groups {
+                    let Some(value) = reducer.reduce(locals.iter().map(Vec::as_slice)) else {
+                        continue;
+                    };
+
+                    let mut m = m.clone();
+                    let tuple: Vec<_> = values.into_iter().chain([value]).collect();
+
+                    if m.unify(&terms, &tuple) {
+                        output.push(m);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a literal that holds when a tuple may hold given which
+    /// retractable facts hold, or nothing if it is always possible.
+    ///
+    /// Negated conditions are assumed to hold, as when deriving possible tuples.
+    fn possibility(
+        &self,
+        builder: &mut Builder<S>,
+        relation: &ResourceId,
+        tuple: &Tuple,
+    ) -> Option<Lit> {
+        if self.retractable.contains(relation) {
+            return Some(builder.tuple(relation.clone(), tuple.clone()));
+        }
+
+        if !self.conditional.contains(relation) {
+            return None;
+        }
+
+        let key = (relation.clone(), tuple.clone());
+        if let Some(var) = builder.possibilities.get(&key) {
+            return Some(Lit::positive(*var));
+        }
+
+        // allocate first so that recursive tuples refer back to this one
+        let possible = Lit::positive(builder.var(Variable::Possibility));
+        builder.possibilities.insert(key.clone(), possible.var());
+
+        let mut any = Vec::new();
+        for support in self.supports.get(&key).into_iter().flatten() {
+            let all = support
+                .conditions
+                .iter()
+                .filter_map(Condition::positive)
+                .filter_map(|(relation, tuple)| self.possibility(builder, relation, tuple))
+                .collect();
+
+            match builder.and(all) {
+                Some(lit) => any.push(lit),
+                None => {
+                    builder.clauses.push(vec![possible]);
+                    return Some(possible);
+                }
+            }
+        }
+
+        builder.define_any(possible, any);
+        Some(possible)
+    }
+
+    /// Gets the tuples of a relation, using the possible tuples if it depends on a decision.
+    fn tuples(&self, relation: &ResourceId) -> &BTreeSet<Tuple> {
+        static EMPTY: BTreeSet<Tuple> = BTreeSet::new();
+
+        let tuples = if self.conditional.contains(relation) {
+            self.possible.get(relation)
+        } else {
+            self.program.relations.get(relation)
+        };
+
+        tuples.unwrap_or(&EMPTY)
+    }
+}
+
+/// Allocates the variables and clauses of a grounded problem.
+struct Builder<S> {
+    variables: Vec<Variable>,
+    spans: Vec<Option<S>>,
+    tuples: HashMap<(ResourceId, Tuple), Var>,
+    possibilities: HashMap<(ResourceId, Tuple), Var>,
+    conjunctions: HashMap<Vec<Lit>, Var>,
+    at_least: HashMap<(Vec<Lit>, usize), Var>,
+    below: HashMap<(Vec<Lit>, Vec<Lit>), Var>,
+    clauses: Vec<Vec<Lit>>,
+}
+
+impl<S> Default for Builder<S> {
+    fn default() -> Self {
+        Self {
+            variables: vec![],
+            spans: vec![],
+            tuples: HashMap::new(),
+            possibilities: HashMap::new(),
+            conjunctions: HashMap::new(),
+            at_least: HashMap::new(),
+            below: HashMap::new(),
+            clauses: vec![],
+        }
+    }
+}
+
+impl<S> Builder<S> {
+    fn var(&mut self, variable: Variable) -> Var {
+        let var = self.variables.len();
+        self.variables.push(variable);
+        self.spans.push(None);
+        var
+    }
+
+    fn tuple(&mut self, relation: ResourceId, tuple: Tuple) -> Lit {
+        let key = (relation, tuple);
+        if let Some(var) = self.tuples.get(&key) {
+            return Lit::positive(*var);
+        }
+
+        let var = self.var(Variable::Tuple(key.0.clone(), key.1.clone()));
+        self.tuples.insert(key, var);
+        Lit::positive(var)
+    }
+
+    /// Creates a literal that holds when all conditions do, or nothing if there are none.
+    fn conjunction(&mut self, conditions: &[Condition]) -> Option<Lit> {
+        let mut lits = Vec::new();
+        for condition in conditions {
+            match condition {
+                Condition::Tuple(positive, relation, tuple) => {
+                    let lit = self.tuple(relation.clone(), tuple.clone());
+                    lits.push(if *positive { lit } else { !lit });
+                }
+                Condition::Count(relation, tuples, count) => {
+                    let tuples: Vec<_> = tuples
+                        .iter()
+                        .map(|tuple| self.tuple(relation.clone(), tuple.clone()))
+                        .collect();
+
+                    lits.extend(self.at_least(tuples.clone(), *count));
+
+                    if *count < tuples.len() {
+                        lits.extend(self.at_least(tuples, count + 1).map(|lit| !lit));
+                    }
+                }
+            }
+        }
+
+        self.and(lits)
+    }
+
+    /// Creates a literal that holds when at least a number of literals do,
+    /// or nothing if the number is zero.
+    fn at_least(&mut self, mut lits: Vec<Lit>, count: usize) -> Option<Lit> {
+        lits.sort();
+        lits.dedup();
+
+        if count == 0 {
+            return None;
+        }
+
+        if let ([lit], 1) = (lits.as_slice(), count) {
+            return Some(*lit);
+        }
+
+        let key = (lits, count);
+        if let Some(var) = self.at_least.get(&key) {
+            return Some(Lit::positive(*var));
+        }
+
+        let var = self.var(Variable::AtLeast(key.0.clone(), count));
+        self.at_least.insert(key, var);
+        Some(Lit::positive(var))
+    }
+
+    /// Creates a literal that holds when all literals do, or nothing if there are none.
+    fn and(&mut self, mut lits: Vec<Lit>) -> Option<Lit> {
+        lits.sort();
+        lits.dedup();
+
+        match lits.as_slice() {
+            [] => return None,
+            [lit] => return Some(*lit),
+            _ => {}
+        }
+
+        if let Some(var) = self.conjunctions.get(&lits) {
+            return Some(Lit::positive(*var));
+        }
+
+        let conj = Lit::positive(self.var(Variable::Conjunction(lits.clone())));
+
+        for lit in lits.iter() {
+            self.clauses.push(vec![!conj, *lit]);
+        }
+
+        self.clauses.push(
+            std::iter::once(conj)
+                .chain(lits.iter().map(|lit| !*lit))
+                .collect(),
+        );
+
+        self.conjunctions.insert(lits, conj.var());
+        Some(conj)
+    }
+
+    /// Creates a literal that only holds if one level is less than another,
+    /// given the bits of both from the least significant.
+    fn below(&mut self, lower: &[Lit], upper: &[Lit]) -> Lit {
+        let mut below: Option<Lit> = None;
+        for bit in 0..lower.len() {
+            let key = (lower[..=bit].to_vec(), upper[..=bit].to_vec());
+            let lit = match self.below.get(&key) {
+                Some(var) => Lit::positive(*var),
+                None => {
+                    let lit =
+                        Lit::positive(self.var(Variable::Below(key.0.clone(), key.1.clone())));
+                    self.below.insert(key, lit.var());
+
+                    // this bit is no greater, and is less unless the bits
+                    // beneath it are
+                    let (l, u) = (lower[bit], upper[bit]);
+                    match below {
+                        Some(below) => {
+                            self.clauses.push(vec![!lit, !l, u]);
+                            self.clauses.push(vec![!lit, !l, below]);
+                            self.clauses.push(vec![!lit, u, below]);
+                        }
+                        None => {
+                            self.clauses.push(vec![!lit, !l]);
+                            self.clauses.push(vec![!lit, u]);
+                        }
+                    }
+
+                    lit
+                }
+            };
+
+            below = Some(lit);
+        }
+
+        below.expect("levels have at least one bit")
+    }
+
+    /// Adds clauses so that a literal holds exactly when any of some others do.
+    fn define_any(&mut self, lit: Lit, any: Vec<Lit>) {
+        for other in any.iter() {
+            self.clauses.push(vec![!*other, lit]);
+        }
+
+        self.clauses
+            .push(std::iter::once(!lit).chain(any).collect());
+    }
+}
+
+/// Finds the strongly connected components of a graph given the successors
+/// of each node, returning the index of the component of each node.
+fn components(edges: &[Vec<usize>]) -> Vec<usize> {
+    let num = edges.len();
+    let mut indices: Vec<Option<usize>> = vec![None; num];
+    let mut lowlinks = vec![0; num];
+    let mut on_stack = vec![false; num];
+    let mut stack = Vec::new();
+    let mut components = vec![0; num];
+    let mut next_index = 0;
+    let mut next_component = 0;
+
+    for root in 0..num {
+        if indices[root].is_some() {
+            continue;
+        }
+
+        // each frame is a node and the position of its next successor
+        let mut frames = vec![(root, 0)];
+        while let Some((node, edge)) = frames.pop() {
+            if edge == 0 {
+                indices[node] = Some(next_index);
+                lowlinks[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&next) = edges[node].get(edge) {
+                frames.push((node, edge + 1));
+                match indices[next] {
+                    None => frames.push((next, 0)),
+                    Some(index) if on_stack[next] => {
+                        lowlinks[node] = lowlinks[node].min(index);
+                    }
+                    Some(_) => {}
+                }
+
+                continue;
+            }
+
+            if Some(lowlinks[node]) == indices[node] {
+                loop {
+                    let member = stack.pop().unwrap();
+                    on_stack[member] = false;
+                    components[member] = next_component;
+                    if member == node {
+                        break;
+                    }
+                }
+
+                next_component += 1;
+            }
+
+            if let Some((parent, _)) = frames.last() {
+                lowlinks[*parent] = lowlinks[*parent].min(lowlinks[node]);
+            }
+        }
+    }
+
+    components
+}
@@ -0,0 +1,374 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Re-solving problems that change over time without starting from scratch.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    backend::types::{ResourceId, Tuple},
+    frontend::types::{ConstraintKind, Value},
+    solver::{
+        cdcl::Solver,
+        ground::{Problem, Variable},
+        maxsat::{self, Optimum},
+        types::*,
+    },
+};
+
+/// What a solver variable stands for, independently of any one grounding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Key {
+    Tuple(ResourceId, Tuple),
+    Conjunction(Vec<Lit>),
+    AtLeast(Vec<Lit>, usize),
+    Level(ResourceId, Tuple, usize),
+    Below(Vec<Lit>, Vec<Lit>),
+    Activation {
+        kind: ConstraintKind,
+        weight: Option<u64>,
+        captures: Vec<Value>,
+        lits: Vec<Lit>,
+        fixed: usize,
+    },
+}
+
+impl Key {
+    /// Renames the solver variables that this key refers to, unless one of
+    /// them has no new name.
+    fn rename(&self, rename: &impl Fn(Lit) -> Option<Lit>) -> Option<Self> {
+        let lits =
+            |lits: &[Lit]| -> Option<Vec<Lit>> { lits.iter().map(|lit| rename(*lit)).collect() };
+        Some(match self {
+            Key::Tuple(..) | Key::Level(..) => self.clone(),
+            Key::Conjunction(conjunction) => Key::Conjunction(lits(conjunction)?),
+            Key::AtLeast(at_least, count) => Key::AtLeast(lits(at_least)?, *count),
+            Key::Below(lower, upper) => Key::Below(lits(lower)?, lits(upper)?),
+            Key::Activation {
+                kind,
+                weight,
+                captures,
+                lits: activation,
+                fixed,
+            } => Key::Activation {
+                kind: *kind,
+                weight: *weight,
+                captures: captures.clone(),
+                lits: lits(activation)?,
+                fixed: *fixed,
+            },
+        })
+    }
+}
+
+/// A clause or cardinality constraint over the solver's variables.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Encoded {
+    Clause(Vec<Lit>),
+    Cardinality(Cardinality),
+}
+
+/// A solver that keeps what it has learned across a changing problem.
+///
+/// Variables are identified by what they stand for, so that each grounding
+/// of the problem reuses the variables of the groundings before it. Clauses
+/// and cardinality constraints are added in generations, each only enforced
+/// while a selector literal is assumed. Once a generation contains anything
+/// that is no longer part of the problem, its selector is negated for good
+/// and the rest of it is added again in a new generation.
+///
+/// Learned clauses depend on the selectors of the generations they were
+/// learned from, so they stay valid while those generations live. The saved
+/// phase of each variable also starts every search from the previous model.
+///
+/// Variables that do not stand for anything lasting, like selectors and the
+/// bounds of each search, are spent along with retired generations. Once most
+/// of the solver is spent, it is rebuilt from the variables that are left.
+#[derive(Clone, Debug, Default)]
+pub struct Incremental {
+    solver: Solver,
+    vars: HashMap<Key, Var>,
+
+    /// The generation of each enforced clause and cardinality constraint.
+    active: HashMap<Encoded, usize>,
+
+    /// The selector of each generation, or nothing once it is retired.
+    generations: Vec<Option<Lit>>,
+
+    /// The number of clauses and cardinality constraints in retired generations.
+    retired: usize,
+}
+
+impl Incremental {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of variables in the solver, including spent ones.
+    pub fn num_vars(&self) -> usize {
+        self.solver.num_vars()
+    }
+
+    /// Finds a model of a problem that minimizes the penalty of its objective.
+    ///
+    /// The model is over the variables of the given problem. Returns nothing
+    /// if the problem has no solution.
+    pub fn minimize<S>(&mut self, problem: &Problem<S>) -> Option<Optimum> {
+        // every variable without a key is spent by the time of the next
+        // problem, including each bound that the last search left behind
+        let spent = self.retired + (self.solver.num_vars() - self.vars.len());
+        if spent > (self.active.len() + self.vars.len()).max(1000) {
+            self.rebuild();
+        }
+
+        let vars = self.map_vars(problem);
+        let map = |lit: &Lit| Lit::new(vars[lit.var()], lit.is_positive());
+
+        let formula = problem.formula();
+        let mut wanted = HashSet::new();
+        for clause in formula.clauses.iter() {
+            let mut clause: Vec<_> = clause.iter().map(map).collect();
+            clause.sort();
+            clause.dedup();
+            wanted.insert(Encoded::Clause(clause));
+        }
+
+        for cardinality in formula.cardinalities.iter() {
+            wanted.insert(Encoded::Cardinality(Cardinality {
+                guard: cardinality.guard.as_ref().map(map),
+                lits: cardinality.lits.iter().map(map).collect(),
+                min: cardinality.min,
+                max: cardinality.max,
+            }));
+        }
+
+        self.update(wanted);
+
+        let assumptions: Vec<_> = self.generations.iter().flatten().copied().collect();
+
+        let objective: Vec<_> = problem
+            .objective()
+            .iter()
+            .map(|(lit, weight)| (map(lit), *weight))
+            .collect();
+
+        let optimum = maxsat::minimize_assuming(&mut self.solver, &assumptions, &objective)?;
+
+        Some(Optimum {
+            model: vars.iter().map(|var| optimum.model[*var]).collect(),
+            cost: optimum.cost,
+        })
+    }
+
+    /// Maps each variable of a problem to a variable of the solver.
+    fn map_vars<S>(&mut self, problem: &Problem<S>) -> Vec<Var> {
+        let mut vars: Vec<Var> = Vec::with_capacity(problem.variables.len());
+        let map = |vars: &[Var], lits: &[Lit]| -> Vec<Lit> {
+            let mut lits: Vec<_> = lits
+                .iter()
+                .map(|lit| Lit::new(vars[lit.var()], lit.is_positive()))
+                .collect();
+
+            lits.sort();
+            lits
+        };
+
+        for variable in problem.variables.iter() {
+            // variables are only ever defined in terms of earlier ones
+            let key = match variable {
+                Variable::Tuple(relation, tuple) => {
+                    Some(Key::Tuple(relation.clone(), tuple.clone()))
+                }
+                Variable::Conjunction(lits) => Some(Key::Conjunction(map(&vars, lits))),
+                Variable::AtLeast(lits, count) => Some(Key::AtLeast(map(&vars, lits), *count)),
+                Variable::Level(relation, tuple, bit) => {
+                    Some(Key::Level(relation.clone(), tuple.clone(), *bit))
+                }
+                Variable::Below(lower, upper) => {
+                    // the bits of levels are ordered, so they are not sorted
+                    let bits = |lits: &[Lit]| -> Vec<Lit> {
+                        lits.iter()
+                            .map(|lit| Lit::new(vars[lit.var()], lit.is_positive()))
+                            .collect()
+                    };
+
+                    Some(Key::Below(bits(lower), bits(upper)))
+                }
+                Variable::Activation(idx) => {
+                    let constraint = &problem.constraints[*idx];
+                    Some(Key::Activation {
+                        kind: constraint.kind,
+                        weight: constraint.weight,
+                        captures: constraint.captures.clone(),
+                        lits: map(&vars, &constraint.lits),
+                        fixed: constraint.fixed,
+                    })
+                }
+                Variable::Possibility => None,
+            };
+
+            let var = match key {
+                Some(key) => match self.vars.get(&key) {
+                    Some(var) => *var,
+                    None => {
+                        let var = self.new_var();
+                        self.vars.insert(key, var);
+                        var
+                    }
+                },
+                None => self.new_var(),
+            };
+
+            vars.push(var);
+        }
+
+        vars
+    }
+
+    /// Enforces exactly the given clauses and cardinality constraints.
+    fn update(&mut self, wanted: HashSet<Encoded>) {
+        let stale: BTreeSet<usize> = self
+            .active
+            .iter()
+            .filter(|(encoded, _)| !wanted.contains(encoded))
+            .map(|(_, generation)| *generation)
+            .collect();
+
+        for generation in stale.iter() {
+            if let Some(selector) = self.generations[*generation].take() {
+                self.solver.add_clause(&[!selector]);
+            }
+        }
+
+        let before = self.active.len();
+        self.active
+            .retain(|_, generation| !stale.contains(generation));
+        self.retired += before - self.active.len();
+
+        let added: Vec<_> = wanted
+            .into_iter()
+            .filter(|encoded| !self.active.contains_key(encoded))
+            .collect();
+
+        self.add_generation(added);
+    }
+
+    /// Enforces clauses and cardinality constraints under a new selector.
+    fn add_generation(&mut self, added: Vec<Encoded>) {
+        if added.is_empty() {
+            return;
+        }
+
+        let generation = self.generations.len();
+        let selector = Lit::positive(self.new_var());
+        self.generations.push(Some(selector));
+
+        for encoded in added {
+            match &encoded {
+                Encoded::Clause(clause) => {
+                    let mut clause = clause.clone();
+                    clause.push(!selector);
+                    self.solver.add_clause(&clause);
+                }
+                Encoded::Cardinality(cardinality) => {
+                    let guard = match cardinality.guard {
+                        None => selector,
+                        Some(guard) => {
+                            // only enforced when both the guard and selector hold
+                            let both = Lit::positive(self.new_var());
+                            self.solver.add_clause(&[!both, guard]);
+                            self.solver.add_clause(&[!both, selector]);
+                            self.solver.add_clause(&[both, !guard, !selector]);
+                            both
+                        }
+                    };
+
+                    self.solver.add_cardinality(&Cardinality {
+                        guard: Some(guard),
+                        ..cardinality.clone()
+                    });
+                }
+            }
+
+            self.active.insert(encoded, generation);
+        }
+    }
+
+    /// Replaces the solver with one only containing the keyed variables and
+    /// the active generations over them, keeping the saved phase of each
+    /// variable.
+    fn rebuild(&mut self) {
+        // keys only refer to earlier variables, so they are renamed in order
+        let mut keyed: Vec<_> = std::mem::take(&mut self.vars).into_iter().collect();
+        keyed.sort_by_key(|(_key, var)| *var);
+
+        let rename = |renamed: &HashMap<Var, Var>, lit: Lit| {
+            let var = renamed.get(&lit.var())?;
+            Some(Lit::new(*var, lit.is_positive()))
+        };
+
+        let mut solver = Solver::new();
+        let mut renamed = HashMap::new();
+        for (key, var) in keyed {
+            // keys over spent variables can never be wanted again
+            let Some(key) = key.rename(&|lit| rename(&renamed, lit)) else {
+                continue;
+            };
+
+            let new = solver.num_vars();
+            solver.reserve_vars(new + 1);
+            solver.set_phase(new, self.solver.phase(var));
+            renamed.insert(var, new);
+            self.vars.insert(key, new);
+        }
+
+        let rename = |lit: &Lit| rename(&renamed, *lit);
+        let active: Vec<_> = std::mem::take(&mut self.active)
+            .into_keys()
+            .filter_map(|encoded| match encoded {
+                Encoded::Clause(clause) => {
+                    let clause = clause.iter().map(rename).collect::<Option<_>>()?;
+                    Some(Encoded::Clause(clause))
+                }
+                Encoded::Cardinality(cardinality) => {
+                    let guard = match cardinality.guard.as_ref() {
+                        Some(guard) => Some(rename(guard)?),
+                        None => None,
+                    };
+
+                    Some(Encoded::Cardinality(Cardinality {
+                        guard,
+                        lits: cardinality.lits.iter().map(rename).collect::<Option<_>>()?,
+                        ..cardinality
+                    }))
+                }
+            })
+            .collect();
+
+        self.solver = solver;
+        self.generations.clear();
+        self.retired = 0;
+        self.add_generation(active);
+    }
+
+    fn new_var(&mut self) -> Var {
+        let var = self.solver.num_vars();
+        self.solver.reserve_vars(var + 1);
+        var
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weighted MaxSAT optimization on top of the [Solver].
+
+use crate::solver::{cdcl::Solver, types::*};
+
+/// An optimal model and its total penalty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Optimum {
+    pub model: Vec<bool>,
+    pub cost: u64,
+}
+
+/// Computes the total penalty of the true literals of an objective.
+pub fn cost(objective: &[(Lit, u64)], model: &[bool]) -> u64 {
+    objective
+        .iter()
+        .filter(|(lit, _weight)| model[lit.var()] == lit.is_positive())
+        .map(|(_lit, weight)| weight)
+        .sum()
+}
+
+/// Sums the weights of an objective, unless they are too large to optimize.
+pub fn total(objective: &[(Lit, u64)]) -> Option<u64> {
+    objective
+        .iter()
+        .try_fold(0u64, |total, (_lit, weight)| total.checked_add(*weight))
+}
+
+/// Finds a model that minimizes the total penalty of an objective's true literals.
+///
+/// Each improved model bounds the penalty of the next search until no better
+/// model exists. The bounds are only assumed during the search, so the solver
+/// is left as it was apart from its learned clauses.
+pub fn minimize(solver: &mut Solver, objective: &[(Lit, u64)]) -> Option<Optimum> {
+    minimize_assuming(solver, &[], objective)
+}
+
+/// Finds a model in which all of the given literals hold that minimizes the
+/// total penalty of an objective's true literals.
+///
+/// Panics if the objective's weights do not have a [total].
+pub fn minimize_assuming(
+    solver: &mut Solver,
+    assumptions: &[Lit],
+    objective: &[(Lit, u64)],
+) -> Option<Optimum> {
+    let model = solver.solve_assuming(assumptions)?;
+    let mut best = Optimum {
+        cost: cost(objective, &model),
+        model,
+    };
+
+    let total = total(objective).expect("objective is too large to optimize");
+
+    while best.cost > 0 {
+        // bound the penalty only while the bound's literal holds
+        let bound = Lit::positive(solver.num_vars());
+        let mut terms = objective.to_vec();
+        terms.push((bound, total - (best.cost - 1)));
+        solver.add_linear(&terms, total);
+
+        let mut bounded = assumptions.to_vec();
+        bounded.push(bound);
+        let model = solver.solve_assuming(&bounded);
+
+        // retire the bound for good
+        solver.add_clause(&[!bound]);
+
+        let Some(model) = model else {
+            break;
+        };
+
+        best = Optimum {
+            cost: cost(objective, &model),
+            model,
+        };
+    }
+
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lits(dimacs: &[i64]) -> Vec<Lit> {
+        dimacs
+            .iter()
+            .map(|lit| Lit::from_dimacs(*lit).unwrap())
+            .collect()
+    }
+
+    fn objective(weights: &[(i64, u64)]) -> Vec<(Lit, u64)> {
+        weights
+            .iter()
+            .map(|(lit, weight)| (Lit::from_dimacs(*lit).unwrap(), *weight))
+            .collect()
+    }
+
+    /// Either 2 holds or both 1 and 3 do.
+    fn cover() -> Solver {
+        let mut solver = Solver::new();
+        solver.add_clause(&lits(&[1, 2]));
+        solver.add_clause(&lits(&[2, 3]));
+        solver
+    }
+
+    #[test]
+    fn finds_weighted_optimum() {
+        let mut solver = cover();
+        let objective = objective(&[(1, 3), (2, 5), (3, 1)]);
+        let optimum = minimize(&mut solver, &objective).unwrap();
+
+        assert_eq!(optimum.cost, 4);
+
+        // the model also assigns the variables of the retired bounds
+        assert_eq!(optimum.model[..3], [true, false, true]);
+        assert_eq!(cost(&objective, &optimum.model), optimum.cost);
+
+        // the bounds do not outlive the search
+        assert!(solver.solve_assuming(&lits(&[2, -1, -3])).is_some());
+    }
+
+    #[test]
+    fn minimizes_under_assumptions() {
+        let mut solver = cover();
+        let objective = objective(&[(1, 3), (2, 5), (3, 1)]);
+        let optimum = minimize_assuming(&mut solver, &lits(&[-1]), &objective).unwrap();
+
+        assert_eq!(optimum.cost, 5);
+        assert_eq!(optimum.model[..3], [false, true, false]);
+    }
+
+    #[test]
+    fn penalizes_negative_literals() {
+        let mut solver = cover();
+        solver.add_clause(&lits(&[-1, -3]));
+
+        let objective = objective(&[(-1, 2), (-3, 3)]);
+        let optimum = minimize(&mut solver, &objective).unwrap();
+
+        assert_eq!(optimum.cost, 2);
+        assert_eq!(optimum.model[..3], [false, true, true]);
+    }
+
+    #[test]
+    fn minimizes_the_largest_weights() {
+        // the bounds' own weights push the linear sums far past the weights
+        let half = u64::MAX / 2;
+        let objective = objective(&[(1, half), (2, half), (3, 1)]);
+        assert_eq!(total(&objective), Some(u64::MAX));
+
+        let mut solver = cover();
+        let optimum = minimize(&mut solver, &objective).unwrap();
+        assert_eq!(optimum.cost, half);
+        assert_eq!(optimum.model[..3], [false, true, false]);
+    }
+
+    #[test]
+    fn rejects_unsatisfiable() {
+        let mut solver = cover();
+        solver.add_clause(&lits(&[-2]));
+        solver.add_clause(&lits(&[-1]));
+
+        assert!(minimize(&mut solver, &objective(&[(1, 1)])).is_none());
+    }
+}
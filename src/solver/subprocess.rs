@@ -0,0 +1,218 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! A [Backend] that runs an external solver as a subprocess.
+//!
+//! The problem is written to the solver's standard input in DIMACS or
+//! weighted DIMACS CNF, and its answer is read from its standard output in
+//! the format of the SAT and MaxSAT competitions:
+//!
+//! - `s` lines give the status, like `s SATISFIABLE` or `s OPTIMUM FOUND`.
+//! - `v` lines give the model, as either signed literals ending with `0` or
+//!   a single string of `0`s and `1`s.
+//! - `o` lines give the cost of each model found, the last of which is final.
+//!
+//! Every other line is ignored.
+
+use std::{
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use crate::solver::{
+    backend::{Backend, BackendError},
+    export,
+    maxsat::{self, Optimum},
+    types::*,
+};
+
+/// The format of problems given to a [Subprocess].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// DIMACS CNF, for plain SAT solvers.
+    ///
+    /// Soft constraints are not given to the solver, so the models found
+    /// satisfy every hard constraint but may not be optimal.
+    Dimacs,
+
+    /// Weighted DIMACS CNF, for MaxSAT solvers.
+    Wcnf,
+}
+
+/// An external solver, run once for each problem.
+#[derive(Clone, Debug)]
+pub struct Subprocess {
+    /// The solver's executable.
+    pub program: PathBuf,
+
+    /// Arguments passed to the solver.
+    pub args: Vec<String>,
+
+    /// The format that the solver reads.
+    pub format: Format,
+}
+
+impl Subprocess {
+    pub fn new(program: impl Into<PathBuf>, format: Format) -> Self {
+        Self {
+            program: program.into(),
+            args: vec![],
+            format,
+        }
+    }
+
+    /// Runs the solver on a problem and parses its answer.
+    fn run(&self, formula: &Formula, objective: &[(Lit, u64)]) -> Result<Answer, BackendError> {
+        let mut input = Vec::new();
+        let num_vars = match self.format {
+            Format::Dimacs => export::write_dimacs(formula, &mut input)?,
+            Format::Wcnf => export::write_wcnf(formula, objective, &mut input)?,
+        };
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        // feed the problem concurrently so that neither pipe fills up
+        let mut stdin = child.stdin.take().expect("child stdin is piped");
+        let output = std::thread::scope(|scope| {
+            let writer = scope.spawn(move || stdin.write_all(&input));
+            let output = child.wait_with_output();
+
+            // solvers may stop reading once they have an answer
+            match writer.join().expect("stdin writer panicked") {
+                Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => Err(err),
+                _ => output,
+            }
+        })?;
+
+        let output = String::from_utf8(output.stdout)
+            .map_err(|_| BackendError::Protocol("output is not valid UTF-8".to_string()))?;
+
+        Answer::parse(&output, num_vars)
+    }
+}
+
+impl Backend for Subprocess {
+    fn minimize(
+        &mut self,
+        formula: &Formula,
+        objective: &[(Lit, u64)],
+    ) -> Result<Option<Optimum>, BackendError> {
+        let answer = self.run(formula, objective)?;
+
+        let model = match answer.status.as_str() {
+            "UNSATISFIABLE" => return Ok(None),
+            "SATISFIABLE" | "OPTIMUM FOUND" => answer.model,
+            status => {
+                let msg = format!("solver finished with status {status:?}");
+                return Err(BackendError::Protocol(msg));
+            }
+        };
+
+        let Some(mut model) = model else {
+            let msg = "solver found a model but did not print it".to_string();
+            return Err(BackendError::Protocol(msg));
+        };
+
+        // drop any auxiliary variables of the written problem
+        model.resize(formula.num_vars, false);
+
+        if !formula.is_satisfied(&model) {
+            let msg = "solver printed a model that violates the problem".to_string();
+            return Err(BackendError::Protocol(msg));
+        }
+
+        let cost = maxsat::cost(objective, &model);
+        if let (Format::Wcnf, Some(reported)) = (self.format, answer.cost) {
+            if reported != cost {
+                let msg =
+                    format!("solver reported a cost of {reported} for a model costing {cost}");
+                return Err(BackendError::Protocol(msg));
+            }
+        }
+
+        Ok(Some(Optimum { model, cost }))
+    }
+}
+
+/// The parsed output of a solver.
+struct Answer {
+    status: String,
+    model: Option<Vec<bool>>,
+    cost: Option<u64>,
+}
+
+impl Answer {
+    /// Parses the output of a solver given a problem with `num_vars` variables.
+    fn parse(output: &str, num_vars: usize) -> Result<Self, BackendError> {
+        let malformed = |line: &str| BackendError::Protocol(format!("malformed line {line:?}"));
+
+        let mut status = None;
+        let mut model: Option<Vec<bool>> = None;
+        let mut cost = None;
+
+        for line in output.lines() {
+            let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+
+            match kind {
+                "s" => status = Some(rest.to_string()),
+                "o" => cost = Some(rest.parse().map_err(|_| malformed(line))?),
+                // a model string has exactly one digit per variable
+                "v" if rest.len() == num_vars && rest.chars().all(|c| c == '0' || c == '1') => {
+                    model = Some(rest.chars().map(|c| c == '1').collect());
+                }
+                "v" => {
+                    let model = model.get_or_insert_with(Vec::new);
+                    for lit in rest.split_whitespace() {
+                        let lit: i64 = lit.parse().map_err(|_| malformed(line))?;
+
+                        // a zero terminates the model
+                        let Some(lit) = Lit::from_dimacs(lit) else {
+                            continue;
+                        };
+
+                        if model.len() <= lit.var() {
+                            model.resize(lit.var() + 1, false);
+                        }
+
+                        model[lit.var()] = lit.is_positive();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let Some(status) = status else {
+            return Err(BackendError::Protocol(
+                "solver printed no status".to_string(),
+            ));
+        };
+
+        Ok(Self {
+            status,
+            model,
+            cost,
+        })
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+
+/// The index of a boolean variable.
+pub type Var = usize;
+
+/// A boolean variable or its negation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct Lit(usize);
+
+impl Lit {
+    pub fn new(var: Var, positive: bool) -> Self {
+        Self(var * 2 + !positive as usize)
+    }
+
+    pub fn positive(var: Var) -> Self {
+        Self::new(var, true)
+    }
+
+    pub fn negative(var: Var) -> Self {
+        Self::new(var, false)
+    }
+
+    pub fn var(&self) -> Var {
+        self.0 / 2
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    /// A dense index unique to this literal, for use in per-literal tables.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// The signed, one-based integer for this literal used by DIMACS formats.
+    pub fn to_dimacs(&self) -> i64 {
+        let var = self.var() as i64 + 1;
+        if self.is_positive() {
+            var
+        } else {
+            -var
+        }
+    }
+
+    /// Parses a signed, one-based DIMACS literal.
+    pub fn from_dimacs(lit: i64) -> Option<Self> {
+        let var = lit.unsigned_abs().checked_sub(1)? as usize;
+        Some(Self::new(var, lit > 0))
+    }
+}
+
+impl Not for Lit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+/// A boolean satisfiability problem with native cardinality constraints.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Formula {
+    /// The number of variables in this formula.
+    pub num_vars: usize,
+
+    /// Disjunctions of literals that must each hold.
+    pub clauses: Vec<Vec<Lit>>,
+
+    /// Bounds on the number of literals that hold within groups of literals.
+    pub cardinalities: Vec<Cardinality>,
+}
+
+impl Formula {
+    /// Creates a new variable.
+    pub fn new_var(&mut self) -> Var {
+        let var = self.num_vars;
+        self.num_vars += 1;
+        var
+    }
+
+    /// Tests if a model satisfies every clause and cardinality constraint.
+    pub fn is_satisfied(&self, model: &[bool]) -> bool {
+        let holds = |lit: &Lit| model.get(lit.var()) == Some(&lit.is_positive());
+
+        let clauses = self.clauses.iter().all(|clause| clause.iter().any(holds));
+
+        let cardinalities = self.cardinalities.iter().all(|cardinality| {
+            let num = cardinality.lits.iter().filter(|lit| holds(lit)).count();
+            let is_guarded = cardinality.guard.is_some_and(|guard| !holds(&guard));
+            is_guarded || (cardinality.min..=cardinality.max).contains(&num)
+        });
+
+        clauses && cardinalities
+    }
+
+    /// Encodes every cardinality constraint as clauses, for solvers without
+    /// native support for them.
+    ///
+    /// Each constraint is expanded with a sequential counter whose auxiliary
+    /// variables are allocated after this formula's own variables.
+    pub fn to_cnf(&self) -> Formula {
+        let mut cnf = Formula {
+            num_vars: self.num_vars,
+            clauses: self.clauses.clone(),
+            cardinalities: vec![],
+        };
+
+        for cardinality in self.cardinalities.iter() {
+            cardinality.to_cnf(&mut cnf);
+        }
+
+        cnf
+    }
+}
+
+/// Bounds the number of literals that hold.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Cardinality {
+    /// If present, the bounds are only enforced when this literal holds.
+    pub guard: Option<Lit>,
+
+    /// The literals to count.
+    pub lits: Vec<Lit>,
+
+    /// The least number of literals that may hold.
+    pub min: usize,
+
+    /// The greatest number of literals that may hold.
+    pub max: usize,
+}
+
+impl Cardinality {
+    /// Adds clauses to a formula that enforce this constraint.
+    fn to_cnf(&self, formula: &mut Formula) {
+        let num = self.lits.len();
+        let unless: Vec<Lit> = self.guard.iter().map(|guard| !*guard).collect();
+        let limit = (self.max + 1).max(self.min).min(num);
+
+        // counts[j] holds when at least j of the literals so far hold
+        let mut counts: Vec<Option<Lit>> = vec![None; limit + 1];
+        for lit in self.lits.iter().copied() {
+            let mut next = vec![None; limit + 1];
+            for (j, count) in next.iter_mut().enumerate().skip(1) {
+                let stay = counts[j];
+                let carry = counts[j - 1];
+                if j > 1 && stay.is_none() && carry.is_none() {
+                    continue;
+                }
+
+                let var = Lit::positive(formula.new_var());
+                *count = Some(var);
+
+                if let Some(stay) = stay {
+                    formula.clauses.push(vec![!stay, var]);
+                }
+
+                match (j, carry) {
+                    (1, _) => formula.clauses.push(vec![!lit, var]),
+                    (_, Some(carry)) => formula.clauses.push(vec![!carry, !lit, var]),
+                    (_, None) => {}
+                }
+
+                if j > 1 {
+                    let clause = std::iter::once(!var).chain(stay).chain(carry).collect();
+                    formula.clauses.push(clause);
+                }
+
+                formula
+                    .clauses
+                    .push(std::iter::once(!var).chain(stay).chain([lit]).collect());
+            }
+
+            counts = next;
+        }
+
+        if self.max < num {
+            if let Some(count) = counts[self.max + 1] {
+                let clause = unless.iter().copied().chain([!count]).collect();
+                formula.clauses.push(clause);
+            }
+        }
+
+        if self.min > 0 {
+            let clause = unless
+                .iter()
+                .copied()
+                .chain(counts.get(self.min).copied().flatten())
+                .collect();
+            formula.clauses.push(clause);
+        }
+    }
+}
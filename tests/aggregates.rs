@@ -0,0 +1,115 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that aggregates in rule bodies are evaluated per group.
+
+mod common;
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use archimedes::frontend::types::{ResourceId, Value};
+use url::Url;
+
+const CHORES: &str = "
+Member Walter. Member Donny. Member Maude.
+Effort(Dishes, 2). Effort(Laundry, 3). Effort(Trash, 1).
+Assigned(Walter, Dishes). Assigned(Walter, Laundry). Assigned(Donny, Trash).
+";
+
+/// Evaluates a rule over the chores and maps each member to their value.
+fn check(rule: &str) -> BTreeMap<String, i64> {
+    let program = common::load(&format!("{CHORES}{rule}"));
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let relation = ResourceId::SourceSymbol(url, "Result".to_string());
+
+    program
+        .relations
+        .get(&relation)
+        .into_iter()
+        .flatten()
+        .map(|tuple| match &tuple[..] {
+            [Value::Symbol(member), Value::Integer(n)] => (member.clone(), *n),
+            _ => panic!("unexpected tuple: {tuple:?}"),
+        })
+        .collect()
+}
+
+fn expected(values: &[(&str, i64)]) -> BTreeMap<String, i64> {
+    values
+        .iter()
+        .map(|(member, n)| (member.to_string(), *n))
+        .collect()
+}
+
+#[test]
+fn counts_groups() {
+    let counts = check("Result(member, n) if Member member, n = count Assigned(member, chore).");
+
+    // groups without any tuples are left out, so Maude has no count
+    assert_eq!(counts, expected(&[("Walter", 2), ("Donny", 1)]));
+}
+
+#[test]
+fn sums_groups() {
+    let rule = "
+    Load(member, effort) if Assigned(member, chore), Effort(chore, effort).
+    Result(member, n) if Member member, n = sum(effort) Load(member, effort).
+    ";
+
+    assert_eq!(check(rule), expected(&[("Walter", 5), ("Donny", 1)]));
+}
+
+#[test]
+fn drops_overflowing_sums() {
+    let rule = "
+    Load(Walter, 9223372036854775807). Load(Walter, 1). Load(Donny, 1).
+    Result(member, n) if Member member, n = sum(effort) Load(member, effort).
+    ";
+
+    // Walter's sum does not fit, so it is left out instead of wrapping
+    assert_eq!(check(rule), expected(&[("Donny", 1)]));
+}
+
+#[test]
+fn bounds_groups() {
+    let rule = "
+    Load(member, effort) if Assigned(member, chore), Effort(chore, effort).
+    Result(member, n) if Member member, n = min(effort) Load(member, effort).
+    ";
+
+    // likewise, Maude has no minimum or maximum
+    assert_eq!(check(rule), expected(&[("Walter", 2), ("Donny", 1)]));
+
+    let rule = "
+    Load(member, effort) if Assigned(member, chore), Effort(chore, effort).
+    Result(member, n) if Member member, n = max(effort) Load(member, effort).
+    ";
+
+    assert_eq!(check(rule), expected(&[("Walter", 3), ("Donny", 1)]));
+}
+
+#[test]
+fn reports_non_integer_sums() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        &format!("{CHORES}Result(n) if n = sum(chore) Assigned(Walter, chore)."),
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, ["Pattern expects Symbol but got Integer"]);
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+//! Checks that the backend worker evaluates changes to a lowered program.
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use archimedes::{
+    backend::{
+        dataflow::{backend_worker, BackendResult, BackendUpdate},
+        types::{Node, ResourceId, Tuple, Value},
+    },
+    utils::{run_dataflow, Key},
+};
+use url::Url;
+
+fn relation(name: &str) -> ResourceId {
+    let url = Url::parse("file:///test.ful").unwrap();
+    ResourceId::SourceSymbol(Arc::new(url), name.to_string())
+}
+
+fn edge(from: i64, to: i64, add: bool) -> BackendUpdate {
+    let tuple = Tuple::from([Value::Integer(from), Value::Integer(to)]);
+    BackendUpdate::Fact(relation("Edge"), tuple, add)
+}
+
+/// Lists the tuples of a relation in a result, as pairs of integers.
+fn pairs(results: &[BackendResult], name: &str) -> BTreeSet<(i64, i64)> {
+    results
+        .iter()
+        .filter_map(|result| match result {
+            BackendResult::Tuple(other, tuple) if *other == relation(name) => Some(tuple),
+            _ => None,
+        })
+        .map(|tuple| match tuple[..] {
+            [Value::Integer(from), Value::Integer(to)] => (from, to),
+            _ => panic!("unexpected tuple {tuple:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn evaluates_program_updates() {
+    let (update_tx, update_rx) = flume::unbounded();
+    let result_rx = run_dataflow(update_rx, backend_worker);
+
+    // Reversed(to, from) if Edge(from, to).
+    let load = Node::LoadRelation {
+        resource: relation("Edge"),
+    };
+
+    let store = Node::StoreRelation {
+        src: Key::new(&load),
+        dst: relation("Reversed"),
+        map: Arc::from([1, 0]),
+    };
+
+    update_tx
+        .send(vec![
+            BackendUpdate::Node(load, true),
+            BackendUpdate::Node(store, true),
+            edge(1, 2, true),
+            edge(2, 3, true),
+        ])
+        .unwrap();
+
+    let results = result_rx.recv().unwrap();
+    assert_eq!(
+        pairs(&results, "Reversed"),
+        BTreeSet::from([(2, 1), (3, 2)])
+    );
+
+    // retracted facts retract what was derived from them
+    update_tx
+        .send(vec![edge(1, 2, false), edge(3, 4, true)])
+        .unwrap();
+
+    let results = result_rx.recv().unwrap();
+    assert_eq!(pairs(&results, "Edge"), BTreeSet::from([(2, 3), (3, 4)]));
+    assert_eq!(
+        pairs(&results, "Reversed"),
+        BTreeSet::from([(3, 2), (4, 3)])
+    );
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+//! Checks that built-in relations are typed and bounded.
+
+mod common;
+
+#[test]
+fn reports_oversized_ranges() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        import Math.(Range)
+        Day day if Range(0, 1000000000, day).
+        Small day if Range(0, 3, day).
+        Bound 5000000000.
+        Known(x) if Bound x, Range(0, 1000000000, x).
+        ",
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        ["Range of 1000000000 values exceeds the limit of 1048576"]
+    );
+}
+
+#[test]
+fn reports_non_integer_arguments() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        import Math.(Range)
+        Day day if Range(Monday, 3, day).
+        ",
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, ["Expected Integer, got Symbol"]);
+}
+
+#[test]
+fn reports_oversized_ranges_bounded_during_evaluation() {
+    let (program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        import Math.(Range)
+        Bound 5000000000.
+        Big x if Bound n, Range(0, n, x).
+        Limit 3.
+        Small x if Limit n, Range(0, n, x).
+        ",
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        ["Range of 5000000000 values exceeds the limit of 1048576"]
+    );
+
+    // the diagnostic points at the range itself
+    let (url, span) = &diagnostics[0].span;
+    assert_eq!(url.path(), "/main.ful");
+    assert_eq!(span.start.row, 3);
+
+    let small = program
+        .relations
+        .iter()
+        .find(|(relation, _tuples)| relation.to_string() == "Small")
+        .map(|(_relation, tuples)| tuples.len());
+
+    assert_eq!(small, Some(3));
+}
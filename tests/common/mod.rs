@@ -0,0 +1,125 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers shared between integration tests.
+
+// not every test uses every helper
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, HashMap};
+
+use archimedes::{
+    backend::{
+        builtins::Builtin,
+        dataflow::{backend_worker, BackendResult, BackendUpdate},
+    },
+    frontend::{
+        dataflow::{frontend_worker, FrontendResultKind, FrontendUpdate},
+        diagnostics::Diagnostic,
+        parser::Module,
+        span::{MapSpan, Span},
+    },
+    solver::ground::Program,
+    utils::run_dataflow,
+};
+use url::Url;
+
+/// A span within one of the files of a project.
+pub type FileSpan = (Url, Span);
+
+/// Evaluates the source of a module with the frontend, panicking on errors.
+pub fn load(src: &str) -> Program<FileSpan> {
+    let (program, diagnostics) = load_project(&[("test.ful", src)]);
+    if let Some(d) = diagnostics.first() {
+        panic!("unexpected diagnostic: {d:?}");
+    }
+
+    program
+}
+
+/// Evaluates the files of a project with the frontend and then the backend,
+/// returning any errors.
+///
+/// Each file is given by its path relative to the project root.
+pub fn load_project(files: &[(&str, &str)]) -> (Program<FileSpan>, Vec<Diagnostic<FileSpan>>) {
+    let root = Url::parse("file:///").unwrap();
+    let (update_tx, update_rx) = flume::unbounded();
+    let result_rx = run_dataflow(update_rx, frontend_worker);
+
+    let mut items = Vec::new();
+    for (path, src) in files {
+        let url = root.join(path).unwrap();
+        for item in Module::new(src).items() {
+            let item = item.map_span(&mut |span| (url.clone(), span));
+            items.push((url.clone(), item));
+        }
+    }
+
+    let mut updates = vec![FrontendUpdate::Root(root.clone(), true)];
+    updates.extend(
+        items
+            .iter()
+            .map(|(url, item)| FrontendUpdate::Item(url.clone(), Box::new(item.clone()), true)),
+    );
+
+    update_tx.send(updates).unwrap();
+
+    let mut resolved = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut lowered = Vec::new();
+    let mut ranges = HashMap::new();
+    for (url, result) in result_rx.recv().unwrap() {
+        match result {
+            FrontendResultKind::Diagnostic(d) => diagnostics.push(d),
+            FrontendResultKind::Item(item) => resolved.push(*item),
+            FrontendResultKind::Node(node) => lowered.push(BackendUpdate::Node(node, true)),
+            FrontendResultKind::Range(node, span) => {
+                ranges.insert(node, (url, span));
+            }
+            FrontendResultKind::Fact(relation, tuple) => {
+                lowered.push(BackendUpdate::Fact(relation, tuple, true))
+            }
+            _ => {}
+        }
+    }
+
+    let (update_tx, update_rx) = flume::unbounded();
+    let relation_rx = run_dataflow(update_rx, backend_worker);
+    update_tx.send(lowered).unwrap();
+
+    let mut program = Program::new(resolved);
+    let mut oversized = BTreeMap::new();
+    for result in relation_rx.recv().unwrap() {
+        match result {
+            BackendResult::Tuple(relation, tuple) => {
+                program.relations.entry(relation).or_default().insert(tuple);
+            }
+            BackendResult::Oversized(node, len) => {
+                let max = oversized.entry(node).or_insert(len);
+                *max = len.max(*max);
+            }
+        }
+    }
+
+    diagnostics.extend(oversized.into_iter().filter_map(|(node, len)| {
+        let span = ranges.get(&node)?.clone();
+        Some(Builtin::oversized_diagnostic(span, len))
+    }));
+
+    (program, diagnostics)
+}
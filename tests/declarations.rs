@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+//! Checks that declared relation types are used and checked against inference.
+
+mod common;
+
+#[test]
+fn declared_types_need_no_facts() {
+    let program = common::load(
+        "
+        type Member Symbol.
+        type Chore {Name: Symbol, Minutes: Integer}.
+        decide Assignment(chore, member) if Chore {Name: chore, Minutes: t}, Member member.
+        ",
+    );
+
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    assert!(problem.decision_vars(|_| true).is_empty());
+}
+
+#[test]
+fn reports_mismatched_declarations() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        type Member Symbol.
+        type Busy(Symbol, Symbol).
+        type Pet Animal.
+        Member 3.
+        Chore Dishes.
+        Busy(chore, 10) if Chore chore.
+        ",
+    )]);
+
+    let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "Expected (Symbol, Symbol), got (Symbol, Integer)",
+            "Expected Symbol, got Integer",
+            "Unknown type Animal",
+        ]
+    );
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+//! Checks that enum types catch mixed-up symbols and serve as domains.
+
+mod common;
+
+const CHORES: &str = "
+    type Day = Monday | Tuesday.
+    type Member = Walter | Donny.
+    type Chore = Dishes | Trash.
+    type Assignment(Day, Chore, Member).
+    decide Assignment(day, chore, member) if Day day, Chore chore, Member member.
+";
+
+#[test]
+fn enums_are_domains() {
+    let program = common::load(CHORES);
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    assert_eq!(problem.decision_vars(|_| true).len(), 8);
+}
+
+#[test]
+fn reports_mixed_up_symbols() {
+    let src = format!(
+        "{CHORES}
+        Busy member if Assignment(Monday, member, Dishes).
+        Likes(Dishes, Walter).
+        Likes(Walter, Dishes).
+        type Weekend = Saturday | Monday.
+        "
+    );
+
+    let (_program, diagnostics) = common::load_project(&[("main.ful", &src)]);
+
+    let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "Expected (Chore, Member), got (Member, Chore)",
+            "Expected Member, got Chore",
+            "Monday is a variant of both Day and Weekend",
+        ]
+    );
+}
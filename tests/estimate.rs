@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that grounding sizes are estimated from relation sizes.
+
+mod common;
+
+use archimedes::frontend::estimate::{estimate, Thresholds};
+
+#[test]
+fn estimates_decisions_and_constraints() {
+    let program = common::load(
+        "
+        decide Assignment(day, chore, member) if Day day, Chore chore, Member member.
+        constrain(day, chore) cardinality to only 1 Assignment(day, chore, member).
+        constrain cardinality to at most 0 Assignment(0, chore, member), Lazy(member).
+        Day 0. Day 1. Day 2. Chore Dishes. Chore Trash. Member Walter. Member Donny.
+        Lazy(Donny).
+        ",
+    );
+
+    let estimates = estimate(&program.items, &program.relations, &Thresholds::default());
+    let sizes: Vec<_> = estimates
+        .iter()
+        .map(|e| (e.instances, e.variables, e.clauses))
+        .collect();
+
+    // one variable per decision tuple, then one activation per instance
+    assert_eq!(sizes, vec![(12, 12, 0), (6, 6, 18), (1, 1, 3)]);
+
+    let thresholds = Thresholds {
+        variables: 10,
+        clauses: 100,
+    };
+
+    let warned: Vec<_> = estimates
+        .iter()
+        .filter_map(|e| e.diagnostic(&thresholds))
+        .collect();
+
+    assert_eq!(warned.len(), 1);
+    assert!(warned[0].message.starts_with("Assignment grounds into"));
+}
+
+#[test]
+fn keeps_filtered_domains() {
+    let program = common::load(
+        "
+        Day 0. Day 1. Day 2. Member Walter. Member Donny.
+        decide Pick(d, m) if Day d, Member m.
+        decide Filtered(d, m) if Day d, Member m, d < 100.
+        decide Shifted(n, m) if Day d, Member m, n = d + 1.
+        ",
+    );
+
+    // filters and computed terms never add matches, nor take any away
+    let estimates = estimate(&program.items, &program.relations, &Thresholds::default());
+    let sizes: Vec<_> = estimates.iter().map(|e| e.instances).collect();
+    assert_eq!(sizes, vec![6, 6, 6]);
+}
+
+#[test]
+fn estimates_ranges_by_their_span() {
+    let program = common::load(
+        "
+        import Math.(Range)
+        Bound 5.
+        decide Slot(x) if Range(0, 40, x).
+        decide Shift(x) if Bound n, Range(0, n, x).
+        ",
+    );
+
+    let thresholds = Thresholds {
+        variables: 300,
+        clauses: 1000,
+    };
+
+    // ranges with bounds from elsewhere fall back to the variable threshold
+    let estimates = estimate(&program.items, &program.relations, &thresholds);
+    let sizes: Vec<_> = estimates.iter().map(|e| e.instances).collect();
+    assert_eq!(sizes, vec![40, 300]);
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that unsatisfiable assumptions are explained minimally.
+
+mod common;
+
+use std::sync::Arc;
+
+use archimedes::{
+    frontend::types::Value,
+    solver::{
+        cdcl::Solver,
+        explain::{explain_pinned, minimal_core},
+        ground::Pin,
+        types::*,
+    },
+};
+
+#[test]
+fn cores_are_minimal() {
+    let [a, b, c, d] = [0, 1, 2, 3].map(Lit::positive);
+
+    // at most one of a, b, and c, while d is unrelated
+    let formula = Formula {
+        num_vars: 4,
+        clauses: vec![vec![!d, a, b]],
+        cardinalities: vec![Cardinality {
+            guard: None,
+            lits: vec![a, b, c],
+            min: 0,
+            max: 1,
+        }],
+    };
+
+    let mut solver = Solver::from_formula(&formula);
+    let mut core = minimal_core(&mut solver, &[d, a, c, b]).unwrap();
+    core.sort();
+    assert!(core == vec![a, b] || core == vec![a, c] || core == vec![b, c]);
+
+    // d needs a or b, which c excludes
+    let mut core = minimal_core(&mut solver, &[a, d, c]).unwrap();
+    core.sort();
+    assert!(core == vec![a, c] || core == vec![c, d]);
+
+    assert_eq!(minimal_core(&mut solver, &[a, d]), None);
+}
+
+#[test]
+fn blames_conflicting_pins() {
+    let program = common::load(
+        "
+        decide Assignment(chore, member) if Chore chore, Member member.
+        constrain(member) cardinality to at most 1 Assignment(chore, member).
+        Chore Dishes. Chore Trash. Member Walter. Member Donny.
+        ",
+    );
+
+    let (problem, _) = program.ground();
+    let relation = problem.decisions.iter().next().unwrap().clone();
+    let pin = |chore: &str, member: &str, value| Pin {
+        relation: relation.clone(),
+        tuple: Arc::from([
+            Value::Symbol(chore.to_string()),
+            Value::Symbol(member.to_string()),
+        ]),
+        value,
+    };
+
+    // only the pins that overload Walter are to blame
+    let pins = [
+        pin("Dishes", "Walter", true),
+        pin("Dishes", "Donny", false),
+        pin("Trash", "Walter", true),
+    ];
+
+    let (d, blamed) = explain_pinned(&program, &pins).unwrap();
+    assert!(d.is_some());
+    assert_eq!(blamed, vec![0, 2]);
+
+    // contradictory pins are to blame on their own
+    let pins = [pin("Trash", "Donny", true), pin("Trash", "Donny", false)];
+    let (d, blamed) = explain_pinned(&program, &pins).unwrap();
+    assert!(d.is_none());
+    assert_eq!(blamed, vec![0, 1]);
+
+    assert!(explain_pinned(&program, &pins[..1]).is_none());
+}
@@ -0,0 +1,178 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that grounded problems are exported faithfully.
+
+mod common;
+
+use archimedes::solver::{export::*, maxsat, types::*};
+
+/// Two variables of which at least one holds.
+fn formula() -> Formula {
+    Formula {
+        num_vars: 2,
+        clauses: vec![vec![Lit::positive(0), Lit::positive(1)]],
+        cardinalities: vec![],
+    }
+}
+
+fn export(write: impl FnOnce(&mut Vec<u8>)) -> Vec<String> {
+    let mut out = Vec::new();
+    write(&mut out);
+    String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn offsets_negated_opb_objectives() {
+    let objective = vec![(Lit::negative(0), 3), (Lit::positive(1), 2)];
+    let lines = export(|out| {
+        write_opb(&formula(), &objective, out).unwrap();
+    });
+
+    assert_eq!(
+        lines,
+        [
+            "* #variable= 2 #constraint= 1",
+            "* objective offset: 3",
+            "min: -3 x1 +2 x2 ;",
+            "+1 x1 +1 x2 >= 1 ;",
+        ]
+    );
+
+    // the written objective plus its offset is the true cost of every model
+    for model in [[false, true], [true, false], [true, true]] {
+        let written = -3 * model[0] as i64 + 2 * model[1] as i64;
+        assert_eq!(written + 3, maxsat::cost(&objective, &model) as i64);
+    }
+}
+
+#[test]
+fn merges_repeated_opb_literals() {
+    let formula = Formula {
+        num_vars: 2,
+        clauses: vec![
+            vec![Lit::positive(0), Lit::positive(0), Lit::negative(1)],
+            vec![Lit::positive(1), Lit::negative(1)],
+        ],
+        cardinalities: vec![Cardinality {
+            guard: None,
+            lits: vec![Lit::positive(0), Lit::positive(0), Lit::positive(1)],
+            min: 0,
+            max: 1,
+        }],
+    };
+
+    let objective = vec![(Lit::positive(0), 3), (Lit::positive(0), 2)];
+    let lines = export(|out| {
+        write_opb(&formula, &objective, out).unwrap();
+    });
+
+    // the tautology cancels out entirely and is left out
+    assert_eq!(
+        lines,
+        [
+            "* #variable= 2 #constraint= 2",
+            "* objective offset: 0",
+            "min: +5 x1 ;",
+            "+2 x1 -1 x2 >= 0 ;",
+            "-2 x1 -1 x2 >= -1 ;",
+        ]
+    );
+}
+
+#[test]
+fn writes_soft_wcnf_units() {
+    let objective = vec![(Lit::positive(0), 3), (Lit::positive(1), 2)];
+    let lines = export(|out| {
+        write_wcnf(&formula(), &objective, out).unwrap();
+    });
+
+    assert_eq!(lines, ["p wcnf 2 3 6", "6 1 2 0", "3 -1 0", "2 -2 0"]);
+}
+
+#[test]
+fn rejects_overflowing_weights() {
+    let mut out = Vec::new();
+
+    // the top weight must exceed the sum of every soft weight
+    let objective = vec![(Lit::positive(0), u64::MAX), (Lit::positive(1), 1)];
+    assert!(write_wcnf(&formula(), &objective, &mut out).is_err());
+
+    // coefficients are signed
+    let objective = vec![(Lit::positive(0), u64::MAX)];
+    assert!(write_opb(&formula(), &objective, &mut out).is_err());
+
+    // and so is the offset of negated literals
+    let objective = vec![(Lit::negative(0), i64::MAX as u64), (Lit::negative(1), 2)];
+    assert!(write_opb(&formula(), &objective, &mut out).is_err());
+}
+
+#[test]
+fn expands_dimacs_cardinalities() {
+    let mut formula = formula();
+    formula.cardinalities.push(Cardinality {
+        guard: None,
+        lits: vec![Lit::positive(0), Lit::positive(1)],
+        min: 0,
+        max: 1,
+    });
+
+    let mut num_vars = 0;
+    let lines = export(|out| {
+        num_vars = write_dimacs(&formula, out).unwrap();
+    });
+
+    let cnf = formula.to_cnf();
+    assert_eq!(num_vars, cnf.num_vars);
+    assert_eq!(lines[0], "c soft constraints are not enforced");
+    assert_eq!(
+        lines[1],
+        format!("p cnf {} {}", cnf.num_vars, cnf.clauses.len())
+    );
+    assert_eq!(lines.len(), cnf.clauses.len() + 2);
+}
+
+#[test]
+fn maps_variables_to_sources() {
+    let program = common::load(
+        "
+        decide Assignment(day) if Day day.
+        Day 0.
+        constrain soft(2) cardinality to at least 1 Assignment(day).
+        ",
+    );
+
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+
+    let num_vars = problem.formula().num_vars;
+    let lines = export(|out| {
+        let location = |(_url, span): &common::FileSpan| format!("line {}", span.start.row + 1);
+        write_mapping(&problem, num_vars, location, out).unwrap();
+    });
+
+    assert_eq!(lines[0], "variable\tkind\tname\tvalues\tlocation");
+    assert!(lines.contains(&"1\tdecision\tAssignment\t(0)\tline 2".to_string()));
+    assert!(lines
+        .iter()
+        .any(|line| line.contains("\tsoft\tconstraint 0\t()\tline 4")));
+}
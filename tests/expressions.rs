@@ -0,0 +1,129 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that arithmetic and comparisons evaluate as their built-in relations.
+
+mod common;
+
+use std::sync::Arc;
+
+use archimedes::frontend::{
+    parser::Module,
+    types::{ModuleItem, ResourceId, Term, Value},
+};
+use url::Url;
+
+#[test]
+fn evaluates_expressions_in_bodies() {
+    let program = common::load(
+        "
+        Day 1. Day 2. Day 3. Day 4.
+        Window(start, end) if Day day, start = day - 1, end = (day + 1) * 2, day % 2 != 0.
+        decide Swap(day, next) if Day day, Day next, day < next - 1.
+        ",
+    );
+
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let window = &program.relations[&ResourceId::SourceSymbol(url, "Window".to_string())];
+    let expected: Vec<Arc<[Value]>> = vec![
+        Arc::from([Value::Integer(0), Value::Integer(4)]),
+        Arc::from([Value::Integer(2), Value::Integer(8)]),
+    ];
+    assert_eq!(
+        window.iter().collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>()
+    );
+
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    assert_eq!(problem.decision_vars(|_| true).len(), 3);
+}
+
+#[test]
+fn reports_non_integer_operands() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        Member Walter. Member Donny.
+        Next(member, n) if Member member, n = member + 1.
+        Pair(a, b) if Member a, Member b, a < b.
+        Count(n) if n = count Member(member + 1).
+        ",
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages
+            .iter()
+            .filter(|message| **message == "Pattern expects Integer but got Symbol")
+            .count(),
+        3,
+        "{messages:?}"
+    );
+    assert!(messages.contains(&"Expressions are not allowed within aggregates"));
+}
+
+#[test]
+fn type_checks_symbol_operands() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        Member Walter. Member Donny.
+        Named(m) if Member m, m = Walter.
+        Shifted(m) if Member m, 1 < Donny + 1.
+        ",
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(!messages.contains(&"Syntax error"), "{messages:?}");
+    assert_eq!(
+        messages
+            .iter()
+            .filter(|message| **message == "Expected Integer, got Symbol")
+            .count(),
+        2,
+        "{messages:?}"
+    );
+}
+
+#[test]
+fn names_expressions_by_their_source() {
+    let src = "Next(n) if Day d, n = (d+1) *2.";
+    let items = Module::new(src).items();
+    let ModuleItem::Rule(rule) = &items[0] else {
+        panic!("expected a rule, got {:?}", items[0]);
+    };
+
+    let mut names = Vec::new();
+    for literal in rule.body.clone() {
+        literal
+            .inner
+            .into_atom()
+            .pattern
+            .map_leaves(&mut |span, term| {
+                if let Term::Variable(name) = term {
+                    assert_eq!(src[span.start.col..span.end.col], name);
+                    names.push(name);
+                }
+            });
+    }
+
+    names.sort();
+    names.dedup();
+    assert_eq!(names, ["(d+1) *2", "d", "d+1", "n"]);
+}
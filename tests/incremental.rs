@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that re-solving a changing problem agrees with solving it afresh.
+
+mod common;
+
+use archimedes::solver::{cdcl::Solver, incremental::Incremental, maxsat};
+
+const PLANNER: &str = "
+decide Assignment(day, chore, member) if Day day, Chore chore, Member member.
+
+constrain(day, chore) cardinality to only 1 Assignment(day, chore, member).
+constrain(day, member) cardinality to at most 1 Assignment(day, chore, member).
+
+Likes(Walter, Dishes).
+constrain soft(2) (member) cardinality to at least 2 Assignment(day, Dishes, member), Likes(member, Dishes).
+";
+
+/// Solves a planner over the given facts both incrementally and afresh.
+fn check(solver: &mut Incremental, facts: &str) -> Option<u64> {
+    let program = common::load(&format!("{PLANNER}{facts}"));
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+
+    let mut fresh = Solver::from_formula(&problem.formula());
+    let expected = maxsat::minimize(&mut fresh, &problem.objective());
+    let optimum = solver.minimize(&problem);
+
+    if let Some(optimum) = optimum.as_ref() {
+        assert!(problem.formula().is_satisfied(&optimum.model));
+        assert_eq!(
+            maxsat::cost(&problem.objective(), &optimum.model),
+            optimum.cost
+        );
+    }
+
+    let cost = optimum.map(|optimum| optimum.cost);
+    assert_eq!(cost, expected.map(|optimum| optimum.cost));
+    cost
+}
+
+#[test]
+fn resolves_after_edits() {
+    let mut solver = Incremental::new();
+
+    let two = "Day 0. Day 1. Chore Dishes. Chore Trash. Member Walter. Member Donny.";
+    assert_eq!(check(&mut solver, two), Some(0));
+
+    // a third chore needs a third member
+    let mop = "Day 0. Day 1. Chore Dishes. Chore Trash. Chore Mop. Member Walter. Member Donny.";
+    assert_eq!(check(&mut solver, mop), None);
+
+    let maude = format!("{mop} Member Maude.");
+    assert_eq!(check(&mut solver, &maude), Some(0));
+
+    // Walter can only do dishes on the one day left
+    let one_day =
+        "Day 0. Chore Dishes. Chore Trash. Chore Mop. Member Walter. Member Donny. Member Maude.";
+    assert_eq!(check(&mut solver, one_day), Some(2));
+
+    assert_eq!(check(&mut solver, two), Some(0));
+}
+
+#[test]
+fn reclaims_spent_variables() {
+    let facts =
+        "Day 0. Chore Dishes. Chore Trash. Chore Mop. Member Walter. Member Donny. Member Maude.";
+    let program = common::load(&format!("{PLANNER}{facts}"));
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+
+    let mut solver = Incremental::new();
+    assert_eq!(solver.minimize(&problem).unwrap().cost, 2);
+    let live = solver.num_vars();
+
+    // every search leaves bounds behind, which are reclaimed by rebuilding
+    for _ in 0..2000 {
+        assert_eq!(solver.minimize(&problem).unwrap().cost, 2);
+        assert!(solver.num_vars() <= 2 * live + 1000);
+    }
+}
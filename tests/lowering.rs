@@ -0,0 +1,89 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+//! Checks that rules lower into backend nodes that evaluate them.
+
+mod common;
+
+use std::sync::Arc;
+
+use archimedes::frontend::types::{ResourceId, Value};
+use url::Url;
+
+#[test]
+fn stores_constant_head_terms() {
+    let program = common::load(
+        "
+        Bar 1. Bar 2.
+        Foo(x, Monday) if Bar x.
+        ",
+    );
+
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let foo = &program.relations[&ResourceId::SourceSymbol(url, "Foo".to_string())];
+    let monday = Value::Symbol("Monday".to_string());
+    let expected: Vec<Arc<[Value]>> = vec![
+        Arc::from([Value::Integer(1), monday.clone()]),
+        Arc::from([Value::Integer(2), monday]),
+    ];
+    assert_eq!(
+        foo.iter().collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn reports_unsafe_negation_once() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        Member 1. Member 2.
+        Friends(1, 2).
+        Lonely(member) if Member member, not Friends(member, other).
+        ",
+    )]);
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, ["\"other\" only appears within negated atoms"]);
+}
+
+#[test]
+fn filters_constants_and_repeated_variables() {
+    let program = common::load(
+        "
+        Edge(1, 1). Edge(1, 2). Edge(2, 2). Edge(2, 3).
+        Fixed(Groceries, 3). Fixed(Laundry, 4).
+
+        Loop x if Edge(x, x).
+        Shopping interval if Fixed(Groceries, interval).
+        FromOne y if Edge(1, y).
+        ",
+    );
+
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let values = |name: &str| -> Vec<Value> {
+        let relation = ResourceId::SourceSymbol(url.clone(), name.to_string());
+        program.relations[&relation]
+            .iter()
+            .map(|tuple| tuple[0].clone())
+            .collect()
+    };
+
+    assert_eq!(values("Loop"), [Value::Integer(1), Value::Integer(2)]);
+    assert_eq!(values("Shopping"), [Value::Integer(3)]);
+    assert_eq!(values("FromOne"), [Value::Integer(1), Value::Integer(2)]);
+}
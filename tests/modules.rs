@@ -0,0 +1,71 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that imports resolve to relations defined in other files.
+
+mod common;
+
+const HOUSEHOLD: &str = "Member Walter. Member Donny. Chore Dishes.";
+
+#[test]
+fn imports_relations_across_files() {
+    let (program, diagnostics) = common::load_project(&[
+        ("Household.ful", HOUSEHOLD),
+        (
+            "Calendar/Days.ful",
+            "import Math.(Range) Day day if Range(0, 2, day).",
+        ),
+        (
+            "main.ful",
+            "
+            import Household.(Member, Chore)
+            import Calendar.Days.(Day)
+            decide Assignment(day, chore, member) if Day day, Chore chore, Member member.
+            constrain(day, chore) cardinality to only 1 Assignment(day, chore, member).
+            ",
+        ),
+    ]);
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+    assert_eq!(problem.decision_vars(|_| true).len(), 4);
+}
+
+#[test]
+fn reports_unresolved_imports() {
+    let (_program, diagnostics) = common::load_project(&[
+        ("Household.ful", HOUSEHOLD),
+        (
+            "main.ful",
+            "
+            import Household.(Member, Pet)
+            import Garden.(Plant)
+            Likes(member) if Member member.
+            ",
+        ),
+    ]);
+
+    let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        ["Household has no relation Pet", "Unknown module Garden"]
+    );
+}
@@ -0,0 +1,84 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that negated atoms are evaluated and that negation is stratified.
+
+mod common;
+
+use std::sync::Arc;
+
+use archimedes::frontend::types::{ResourceId, Value};
+use url::Url;
+
+#[test]
+fn evaluates_negated_atoms() {
+    let program = common::load(
+        "
+        Day 0. Day 1. Day 2.
+        Chore Dishes. Chore Trash.
+        Done(0, Dishes). Done(1, Trash). Done(2, Dishes). Done(2, Trash).
+
+        Pending(day, chore) if Day day, Chore chore, not Done(day, chore).
+        ",
+    );
+
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let pending = &program.relations[&ResourceId::SourceSymbol(url, "Pending".to_string())];
+    let expected: Vec<Arc<[Value]>> = vec![
+        Arc::from([Value::Integer(0), Value::Symbol("Trash".to_string())]),
+        Arc::from([Value::Integer(1), Value::Symbol("Dishes".to_string())]),
+    ];
+
+    assert_eq!(
+        pending.iter().collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn reports_negation_cycles() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        Move(1, 2). Move(2, 3).
+        Win x if Move(x, y), not Lose y.
+        Lose x if Move(x, y), not Win y.
+        ",
+    )]);
+
+    let mut labels: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.message.as_str(), d.labels[0].inner.as_str()))
+        .collect();
+
+    labels.sort();
+
+    assert_eq!(
+        labels,
+        [
+            (
+                "Dependency on Lose is not stratified",
+                "Win cannot negate or aggregate Lose, which depends on Win."
+            ),
+            (
+                "Dependency on Win is not stratified",
+                "Lose cannot negate or aggregate Win, which depends on Lose."
+            ),
+        ]
+    );
+}
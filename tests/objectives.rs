@@ -0,0 +1,109 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that objectives are optimized against every possible solution.
+
+mod common;
+
+use archimedes::{
+    frontend::types::{AggregateKind, ObjectiveSense},
+    solver::{
+        backend::Native, cdcl::Solver, enumerate::Enumerator, ground::GroundObjective, maxsat,
+        types::Lit,
+    },
+};
+
+const PLANNER: &str = "
+decide Assignment(day, member) if Day day, Member member.
+constrain(day) cardinality to only 1 Assignment(day, member).
+
+Day 0. Day 1. Day 2.
+Member Walter. Member Donny.
+
+Cost(0, Walter, 3). Cost(0, Donny, -2).
+Cost(1, Walter, -1). Cost(1, Donny, 4).
+Cost(2, Walter, 2). Cost(2, Donny, 2).
+
+Load(member, n) if Member member, n = count Assignment(day, member).
+";
+
+/// Finds the optimal value of an objective, and its value in every solution.
+fn check(objective: &str) -> (Option<i64>, Vec<Option<i64>>) {
+    let program = common::load(&format!("{PLANNER}{objective}"));
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+    let value = |model: &[bool]| problem.objectives[0].value(model);
+
+    let formula = problem.formula();
+    let mut solver = Solver::from_formula(&formula);
+    let optimum = maxsat::minimize(&mut solver, &problem.objective()).unwrap();
+
+    let projection = problem.decision_vars(|_| true);
+    let all: Vec<_> = Enumerator::new(&mut Native, formula, vec![], projection)
+        .map(|solution| value(&solution.unwrap().model))
+        .collect();
+
+    assert_eq!(all.len(), 8);
+    (value(&optimum.model), all)
+}
+
+#[test]
+fn optimizes_every_kind() {
+    let body = "Assignment(day, member), Cost(day, member, c).";
+    for kind in ["count", "sum(c)", "min(c)", "max(c)"] {
+        let (best, all) = check(&format!("minimize {kind} {body}"));
+        assert_eq!(best, all.iter().copied().flatten().min(), "minimize {kind}");
+
+        let (best, all) = check(&format!("maximize {kind} {body}"));
+        assert_eq!(best, all.iter().copied().flatten().max(), "maximize {kind}");
+    }
+
+    // the busiest member takes two days at best
+    let (best, _) = check("minimize max(n) Load(member, n).");
+    assert_eq!(best, Some(2));
+}
+
+#[test]
+fn respects_priorities() {
+    let balance = "minimize max(n) Load(member, n).";
+
+    // Walter may take every day once balance matters less
+    let walter = "maximize priority(-1) count Assignment(day, Walter).";
+    let (best, _) = check(&format!("{walter}\n{balance}"));
+    assert_eq!(best, Some(2));
+
+    let walter = "maximize priority(1) count Assignment(day, Walter).";
+    let (best, _) = check(&format!("{walter}\n{balance}"));
+    assert_eq!(best, Some(3));
+}
+
+#[test]
+fn leaves_overflowing_sums_without_a_value() {
+    let objective = GroundObjective {
+        span: (),
+        sense: ObjectiveSense::Maximize,
+        kind: AggregateKind::Sum,
+        priority: 0,
+        instances: vec![(None, i64::MAX), (Some(Lit::positive(0)), 1)],
+        penalties: vec![],
+        scale: 1,
+    };
+
+    assert_eq!(objective.value(&[false]), Some(i64::MAX));
+    assert_eq!(objective.value(&[true]), None);
+}
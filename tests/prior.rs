@@ -0,0 +1,102 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks re-planning against saved solutions and checking them.
+
+mod common;
+
+use archimedes::{
+    frontend::diagnostics::DiagnosticKind,
+    solver::{cdcl::Solver, facts, maxsat, types::Lit},
+};
+
+const PLANNER: &str = "
+decide Assignment(day, chore, member) if Day day, Chore chore, Member member.
+
+constrain(day, chore) cardinality to only 1 Assignment(day, chore, member).
+constrain(day, member) cardinality to at most 1 Assignment(day, chore, member).
+
+Day 0. Day 1. Chore Dishes. Chore Trash.
+";
+
+#[test]
+fn replans_with_fewest_changes() {
+    let program = common::load(&format!("{PLANNER} Member Walter. Member Donny."));
+    let (problem, _) = program.ground();
+    let mut solver = Solver::from_formula(&problem.formula());
+    let optimum = maxsat::minimize(&mut solver, &problem.objective()).unwrap();
+
+    // round-trip the solution through a saved file
+    let mut saved = Vec::new();
+    facts::write_facts(&problem.solution(&optimum.model), &mut saved).unwrap();
+    let (read, diagnostics) = facts::read_facts(&String::from_utf8(saved).unwrap());
+    assert!(diagnostics.is_empty());
+    let (prior, diagnostics) = facts::resolve_facts(read, &problem.decisions);
+    assert!(diagnostics.is_empty());
+    let prior = facts::group_facts(prior);
+    assert_eq!(prior, problem.solution(&optimum.model));
+
+    // with Donny gone, only Donny's chores move to Maude
+    let program = common::load(&format!("{PLANNER} Member Walter. Member Maude."));
+    let (problem, _) = program.ground();
+    let mut solver = Solver::from_formula(&problem.formula());
+    let objective = problem.deviations(&prior, 1);
+    let optimum = maxsat::minimize(&mut solver, &objective).unwrap();
+
+    let solution = problem.solution(&optimum.model);
+    let before = prior.values().next().unwrap();
+    let after = solution.values().next().unwrap();
+    assert_eq!(after.difference(before).count(), 2);
+    assert!(after
+        .difference(before)
+        .all(|tuple| tuple[2].to_string() == "Maude"));
+}
+
+#[test]
+fn checks_saved_solutions() {
+    let program = common::load(&format!("{PLANNER} Member Walter. Member Donny."));
+    let (problem, _) = program.ground();
+
+    // Walter takes every chore on both days
+    let (read, diagnostics) = facts::read_facts(
+        "Assignment(0, Dishes, Walter). Assignment(0, Trash, Walter).
+        Assignment(1, Dishes, Walter). Assignment(1, Trash, Walter).",
+    );
+    assert!(diagnostics.is_empty());
+    let (solution, diagnostics) = facts::resolve_facts(read, &problem.decisions);
+    assert!(diagnostics.is_empty());
+
+    let holds: Vec<_> = solution
+        .iter()
+        .map(|fact| problem.tuple_var(&fact.inner.0, &fact.inner.1).unwrap())
+        .collect();
+
+    let assumptions: Vec<_> = problem
+        .decision_vars(|_| true)
+        .into_iter()
+        .map(|var| Lit::new(var, holds.contains(&var)))
+        .collect();
+
+    let mut solver = Solver::from_formula(&problem.relaxed_formula());
+    let model = solver.solve_assuming(&assumptions).unwrap();
+
+    // only Walter's limit of one chore a day is broken, once for each day
+    let violations = problem.violations(&model);
+    assert_eq!(violations.len(), 2);
+    assert!(violations.iter().all(|d| d.kind == DiagnosticKind::Error));
+}
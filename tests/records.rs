@@ -0,0 +1,100 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+//! Checks that record patterns unify by field name.
+
+mod common;
+
+use std::sync::Arc;
+
+use archimedes::frontend::types::{ResourceId, Value};
+use url::Url;
+
+#[test]
+fn unifies_fields_in_any_order() {
+    let program = common::load(
+        "
+        Chore {Name: Dishes, Minutes: 10}.
+        Chore {Minutes: 30, Name: Laundry}.
+        Long chore if Chore {Minutes: 30, Name: chore}.
+        ",
+    );
+
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let long = &program.relations[&ResourceId::SourceSymbol(url, "Long".to_string())];
+    let laundry: Arc<[Value]> = Arc::from([Value::Symbol("Laundry".to_string())]);
+    assert_eq!(long.iter().collect::<Vec<_>>(), [&laundry]);
+}
+
+#[test]
+fn reports_mismatched_fields() {
+    let (_program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        Chore {Name: Dishes, Minutes: 10}.
+        Short chore if Chore {Name: chore}.
+        Odd chore if Chore {Name: chore, Minutes: 5, Days: 2}.
+        Flat chore if Chore(chore, 10).
+        ",
+    )]);
+
+    let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "Expected record {Minutes: Integer, Name: Symbol}, got tuple of arity 2",
+            "Missing field Minutes",
+            "Unknown field Days",
+            "Unknown type",
+        ]
+    );
+}
+
+#[test]
+fn skips_mismatched_rules() {
+    let (program, diagnostics) = common::load_project(&[(
+        "main.ful",
+        "
+        Chore {Name: Dishes, Minutes: 10}.
+        Partial n if Chore {Name: n}.
+        Flat n if Chore(n, 10).
+        Sorted n if Chore(10, n).
+        ",
+    )]);
+
+    let mut messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    messages.sort();
+    messages.dedup();
+    assert_eq!(
+        messages,
+        [
+            "Expected record {Minutes: Integer, Name: Symbol}, got tuple of arity 2",
+            "Missing field Minutes",
+            "Unknown type",
+        ]
+    );
+
+    // records never match by position, even in the order of their fields
+    let names: Vec<_> = program
+        .relations
+        .keys()
+        .map(|relation| relation.to_string())
+        .collect();
+
+    assert_eq!(names, ["Chore"]);
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that recursive relations over decisions are only derived from
+//! tuples that do not depend on themselves.
+
+mod common;
+
+use std::sync::Arc;
+
+use archimedes::{
+    backend::types::Tuple,
+    frontend::types::{ResourceId, Value},
+    solver::{
+        cdcl::Solver,
+        ground::Problem,
+        types::{Lit, Var},
+    },
+};
+use url::Url;
+
+const GRAPH: &str = "
+Node A. Node B. Node C.
+decide Edge(x, y) if Node x, Node y.
+";
+
+/// Finds the variable of a tuple of symbols in a grounded problem.
+fn var<S>(problem: &Problem<S>, name: &str, symbols: &[&str]) -> Var {
+    let url = Arc::new(Url::parse("file:///test.ful").unwrap());
+    let relation = ResourceId::SourceSymbol(url, name.to_string());
+    let tuple: Tuple = symbols
+        .iter()
+        .map(|symbol| Value::Symbol(symbol.to_string()))
+        .collect();
+
+    problem.tuple_var(&relation, &tuple).unwrap()
+}
+
+#[test]
+fn rejects_self_supporting_cycles() {
+    let program = common::load(&format!(
+        "{GRAPH}
+        Reach(x, y) if Edge(x, y).
+        Reach(x, y) if Reach(y, x).
+        constrain cardinality to at most 0 Edge(x, y).
+        "
+    ));
+
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+
+    // without any edges, no tuple of the cycle can support the others
+    let mut solver = Solver::from_formula(&problem.formula());
+    for (from, to) in [("A", "A"), ("A", "B"), ("B", "A")] {
+        let reach = Lit::positive(var(&problem, "Reach", &[from, to]));
+        assert_eq!(solver.solve_assuming(&[reach]), None);
+    }
+
+    assert!(solver.solve().is_some());
+}
+
+#[test]
+fn derives_well_founded_cycles() {
+    let program = common::load(&format!(
+        "{GRAPH}
+        Reach(x, y) if Edge(x, y).
+        Reach(x, z) if Reach(x, y), Edge(y, z).
+        "
+    ));
+
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+
+    // pick a cycle from A through B back to A, and nothing else
+    let edges = [("A", "B"), ("B", "A")];
+    let mut assumptions = Vec::new();
+    for from in ["A", "B", "C"] {
+        for to in ["A", "B", "C"] {
+            let edge = var(&problem, "Edge", &[from, to]);
+            assumptions.push(Lit::new(edge, edges.contains(&(from, to))));
+        }
+    }
+
+    let mut solver = Solver::from_formula(&problem.formula());
+    let model = solver.solve_assuming(&assumptions).unwrap();
+    let reach = |from, to| model[var(&problem, "Reach", &[from, to])];
+
+    assert!(reach("A", "A") && reach("A", "B") && reach("B", "A") && reach("B", "B"));
+    assert!(!reach("A", "C") && !reach("C", "A") && !reach("C", "C"));
+}
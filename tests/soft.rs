@@ -0,0 +1,106 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checks that soft constraints are traded off by their weights.
+
+mod common;
+
+use archimedes::{
+    frontend::diagnostics::DiagnosticKind,
+    solver::{cdcl::Solver, maxsat},
+};
+
+const PLANNER: &str = "
+decide Assignment(day, member) if Day day, Member member.
+constrain(day) cardinality to only 1 Assignment(day, member).
+
+Day 0. Day 1.
+Member Walter. Member Donny.
+";
+
+/// Finds the optimal cost of a planner and the messages of its violations.
+fn check(soft: &str) -> (u64, Vec<String>) {
+    let program = common::load(&format!("{PLANNER}{soft}"));
+    let (problem, diagnostics) = program.ground();
+    assert!(diagnostics.is_empty());
+
+    let mut solver = Solver::from_formula(&problem.formula());
+    let optimum = maxsat::minimize(&mut solver, &problem.objective()).unwrap();
+    assert_eq!(problem.penalty(&optimum.model), optimum.cost);
+
+    let violations = problem.violations(&optimum.model);
+    assert!(violations.iter().all(|d| d.kind == DiagnosticKind::Warning));
+    let messages = violations.into_iter().map(|d| d.message).collect();
+    (optimum.cost, messages)
+}
+
+#[test]
+fn violates_the_lightest_constraint() {
+    let (cost, messages) = check(
+        "
+        constrain soft(3) cardinality to at most 0 Assignment(day, Walter).
+        constrain soft(1) cardinality to at most 0 Assignment(day, Donny).
+        ",
+    );
+
+    assert_eq!(cost, 1);
+    assert_eq!(messages, ["Soft constraint violated at a cost of 1"]);
+}
+
+#[test]
+fn weighs_each_captured_instance() {
+    // each day that Walter works costs 2, which outweighs Donny's one-off 3
+    let (cost, messages) = check(
+        "
+        constrain soft(2) (day) cardinality to at most 0 Assignment(day, Walter).
+        constrain soft(3) cardinality to at most 1 Assignment(day, Donny).
+        ",
+    );
+
+    assert_eq!(cost, 2);
+    assert_eq!(messages, ["Soft constraint violated at a cost of 2"]);
+}
+
+#[test]
+fn satisfies_compatible_constraints() {
+    let (cost, messages) = check(
+        "
+        constrain soft(5) cardinality to at least 1 Assignment(day, Walter).
+        constrain soft(5) cardinality to at least 1 Assignment(day, Donny).
+        ",
+    );
+
+    assert_eq!(cost, 0);
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn rejects_overly_heavy_constraints() {
+    // the weights together do not fit in the solver's penalties
+    let program = common::load(&format!(
+        "{PLANNER}
+        constrain soft(9223372036854775807) cardinality to at most 0 Assignment(day, Walter).
+        constrain soft(9223372036854775807) cardinality to at most 0 Assignment(day, Donny).
+        constrain soft(9223372036854775807) cardinality to at least 2 Assignment(0, member).
+        "
+    ));
+
+    let (_problem, diagnostics) = program.ground();
+    let messages: Vec<_> = diagnostics.into_iter().map(|d| d.message).collect();
+    assert_eq!(messages, ["Soft constraints are too heavy to optimize"]);
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2024 Marceline Cramer.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// This file is part of Archimedes.
+//
+// Archimedes is free software: you can redistribute it and/or modify it under
+// the terms of the GNU Affero General Public License as published by the Free
+// Software Foundation, either version 3 of the License, or (at your option) any
+// later version.
+//
+// Archimedes is distributed in the hope that it will be useful, but WITHOUT
+// ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or FITNESS
+// FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more
+// details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Archimedes. If not, see <https://www.gnu.org/licenses/>.
+
+//! Drives the subprocess backend with the bundled stand-in solver.
+
+use std::process::Command;
+
+use archimedes::solver::{
+    backend::{Backend, BackendError, Native},
+    enumerate::Enumerator,
+    subprocess::{Format, Subprocess},
+    types::*,
+};
+
+const STAND_IN: &str = env!("CARGO_BIN_EXE_stand-in-solver");
+
+/// Three variables of which exactly two hold, with a clause that excludes
+/// the first and third together.
+fn formula() -> Formula {
+    let [a, b, c] = [0, 1, 2].map(Lit::positive);
+
+    Formula {
+        num_vars: 3,
+        clauses: vec![vec![!a, !c]],
+        cardinalities: vec![Cardinality {
+            guard: None,
+            lits: vec![a, b, c],
+            min: 2,
+            max: 2,
+        }],
+    }
+}
+
+/// Penalizes the second variable heavily and the first lightly.
+fn objective() -> Vec<(Lit, u64)> {
+    vec![(Lit::positive(1), 5), (Lit::positive(0), 1)]
+}
+
+#[test]
+fn dimacs_satisfies_hard_constraints() {
+    let formula = formula();
+    let mut backend = Subprocess::new(STAND_IN, Format::Dimacs);
+    let optimum = backend.minimize(&formula, &objective()).unwrap().unwrap();
+    assert!(formula.is_satisfied(&optimum.model));
+}
+
+#[test]
+fn wcnf_matches_native_optimum() {
+    let formula = formula();
+    let objective = objective();
+
+    let native = Native.minimize(&formula, &objective).unwrap().unwrap();
+    let mut backend = Subprocess::new(STAND_IN, Format::Wcnf);
+    let optimum = backend.minimize(&formula, &objective).unwrap().unwrap();
+
+    assert_eq!(optimum.cost, native.cost);
+    assert_eq!(optimum.model, vec![false, true, true]);
+}
+
+#[test]
+fn model_strings_are_parsed() {
+    let formula = formula();
+    let mut backend = Subprocess::new(STAND_IN, Format::Wcnf);
+    backend.args.push("--model-string".to_string());
+    let optimum = backend.minimize(&formula, &objective()).unwrap().unwrap();
+    assert_eq!(optimum.model, vec![false, true, true]);
+    assert_eq!(optimum.cost, 5);
+}
+
+#[test]
+fn unsatisfiable_formulas_have_no_optimum() {
+    let mut formula = formula();
+    formula.clauses.push(vec![Lit::negative(1)]);
+
+    for format in [Format::Dimacs, Format::Wcnf] {
+        let mut backend = Subprocess::new(STAND_IN, format);
+        assert_eq!(backend.minimize(&formula, &[]).unwrap(), None);
+    }
+}
+
+#[test]
+fn enumeration_blocks_found_models() {
+    let mut backend = Subprocess::new(STAND_IN, Format::Wcnf);
+    let models: Vec<_> = Enumerator::new(&mut backend, formula(), objective(), vec![0, 1, 2])
+        .map(|optimum| optimum.unwrap())
+        .map(|optimum| (optimum.model, optimum.cost))
+        .collect();
+
+    assert_eq!(
+        models,
+        vec![(vec![false, true, true], 5), (vec![true, true, false], 6)]
+    );
+
+    // models only differing outside of the projection are the same
+    let mut backend = Subprocess::new(STAND_IN, Format::Wcnf);
+    let models = Enumerator::new(&mut backend, formula(), objective(), vec![1]);
+    assert_eq!(models.count(), 1);
+}
+
+#[test]
+fn missing_solvers_are_reported() {
+    let mut backend = Subprocess::new("/nonexistent/solver", Format::Wcnf);
+    let result = backend.minimize(&formula(), &objective());
+    assert!(matches!(result, Err(BackendError::Io(_))));
+}
+
+#[test]
+fn run_maps_models_onto_decisions() {
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_archimedes"))
+            .arg("run")
+            .arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/chores.ful"
+            ))
+            .args(["--relation", "Assignment"])
+            .args(args)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let native = run(&[]);
+    let external = run(&["--solver", STAND_IN]);
+
+    // equally good decisions may differ, but not their penalty
+    let penalty = |output: &str| output.lines().last().unwrap().to_string();
+    assert_eq!(penalty(&external), penalty(&native));
+    assert!(external.contains("Assignment (6 tuples):"));
+}
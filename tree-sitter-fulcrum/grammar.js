@@ -26,8 +26,19 @@ module.exports = grammar({
 
   extras: $ => [$._whitespace, $.comment],
 
+  // `n = count ...` is an aggregate, but `n = m` is a comparison
+  conflicts: $ => [[$.aggregate, $._operand]],
+
   rules: {
-    file: $ => repeat(choice($.import, $.rule, $.decision, $.constraint)),
+    file: $ => repeat(choice(
+      $.import,
+      $.type_declaration,
+      $.enum_declaration,
+      $.rule,
+      $.decision,
+      $.constraint,
+      $.objective,
+    )),
 
     _whitespace: _ => /[ \n\r\t]/,
     comment: _ => /;.*\n/,
@@ -40,15 +51,46 @@ module.exports = grammar({
 
     import: $ => seq(
       "import",
-      $.symbol,
-      repeat(seq(".", $.symbol)),
+      field("module", $.symbol),
+      repeat(seq(".", field("module", $.symbol))),
       ".",
-      paren_list($.symbol),
+      paren_list(field("relation", $.symbol)),
+    ),
+
+    type_declaration: $ => seq(
+      "type",
+      field("relation", $.symbol),
+      field("type", $.pattern),
+      "."
+    ),
+
+    enum_declaration: $ => seq(
+      "type",
+      field("name", $.symbol),
+      "=",
+      field("variant", $.symbol),
+      repeat(seq("|", field("variant", $.symbol))),
+      "."
     ),
 
     decision: $ => seq("decide", $.rule),
     rule: $ => seq(field("head", $.atom), field("body", optional(seq("if", $._rule_body))), "."),
-    _rule_body: $ => list($.atom),
+    _rule_body: $ => list($._literal),
+
+    _literal: $ => choice($.atom, $.negation, $.aggregate, $.comparison),
+    negation: $ => seq("not", field("atom", $.atom)),
+
+    aggregate: $ => seq(
+      field("result", $.variable),
+      "=",
+      field("kind", choice($.count, $.sum, $.min, $.max)),
+      field("atom", $.atom),
+    ),
+
+    count: _ => "count",
+    sum: $ => seq("sum", "(", field("target", $.variable), ")"),
+    min: $ => seq("min", "(", field("target", $.variable), ")"),
+    max: $ => seq("max", "(", field("target", $.variable), ")"),
 
     constraint: $ => seq(
       "constrain",
@@ -61,6 +103,17 @@ module.exports = grammar({
 
     captures: $ => paren_list($._ident),
 
+    objective: $ => seq(
+      field("sense", choice($.minimize, $.maximize)),
+      field("priority", optional(seq("priority", "(", $.integer, ")"))),
+      field("kind", choice($.count, $.sum, $.min, $.max)),
+      field("body", $._rule_body),
+      "."
+    ),
+
+    minimize: _ => "minimize",
+    maximize: _ => "maximize",
+
     constraint_kind: $ => choice($.uniform, $.cardinality),
 
     uniform: _ => "uniform",
@@ -76,9 +129,29 @@ module.exports = grammar({
 
     atom: $ => seq(field("relation", $.symbol), $.pattern),
 
-    pattern: $ => choice( $.tuple, $._ident, $.integer),
+    comparison: $ => seq(
+      field("lhs", $._operand),
+      field("op", choice("=", "!=", "<", "<=", ">", ">=")),
+      field("rhs", $._operand),
+    ),
+
+    pattern: $ => choice($.tuple, $.record, $._ident, $.integer, $.expression),
     tuple: $ => paren_list($.pattern),
     record: $ => seq("{", list($.field), "}"),
     field: $ => seq(field("key", $.symbol), ":", field("value", $.pattern)),
+
+    _operand: $ => choice($.tuple, $.variable, $.symbol, $.integer, $.expression),
+    expression: $ => choice(
+      prec.left(1, seq(
+        field("lhs", $._operand),
+        field("op", choice("+", "-")),
+        field("rhs", $._operand),
+      )),
+      prec.left(2, seq(
+        field("lhs", $._operand),
+        field("op", choice("*", "/", "%")),
+        field("rhs", $._operand),
+      )),
+    ),
   }
 });
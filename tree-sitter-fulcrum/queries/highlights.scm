@@ -2,15 +2,23 @@
 (integer) @constant.numeric
 (variable) @variable
 (pattern (symbol) @constant)
+(field key: (symbol) @property)
 (import (symbol) @module)
+(type_declaration relation: (symbol) @type)
+(enum_declaration name: (symbol) @type)
+(enum_declaration variant: (symbol) @constant)
 (uniform) @keyword
 
 (rule head: (atom relation: (symbol) @function))
 (rule (_) (atom relation: (symbol) @constant))
 (constraint (_) (atom relation: (symbol) @constant))
+(objective (_) (atom relation: (symbol) @constant))
+(negation atom: (atom relation: (symbol) @constant))
+(aggregate atom: (atom relation: (symbol) @constant))
 
-[ "," "." ] @punctuation.delimiter
-[ "(" ")" ] @punctuation.bracket
+[ "," "." ":" ] @punctuation.delimiter
+[ "(" ")" "{" "}" ] @punctuation.bracket
+[ "=" "|" "+" "-" "*" "/" "%" "!=" "<" "<=" ">" ">=" ] @operator
 
 [
   "constrain"
@@ -18,7 +26,16 @@
   "if"
   "cardinality"
   "soft"
+  "priority"
   "import"
+  "type"
+  "not"
+  "count"
+  "sum"
+  "min"
+  "max"
 ] @keyword
 
 (constraint_kind) @keyword.control
+(minimize) @keyword.control
+(maximize) @keyword.control
@@ -5,15 +5,15 @@
 #endif
 
 #define LANGUAGE_VERSION 14
-#define STATE_COUNT 84
+#define STATE_COUNT 149
 #define LARGE_STATE_COUNT 2
-#define SYMBOL_COUNT 45
+#define SYMBOL_COUNT 85
 #define ALIAS_COUNT 0
-#define TOKEN_COUNT 23
+#define TOKEN_COUNT 47
 #define EXTERNAL_TOKEN_COUNT 0
-#define FIELD_COUNT 6
+#define FIELD_COUNT 20
 #define MAX_ALIAS_SEQUENCE_LENGTH 9
-#define PRODUCTION_ID_COUNT 8
+#define PRODUCTION_ID_COUNT 28
 
 enum ts_symbol_identifiers {
   sym__whitespace = 1,
@@ -27,39 +27,79 @@ enum ts_symbol_identifiers {
   anon_sym_LPAREN = 9,
   anon_sym_COMMA = 10,
   anon_sym_RPAREN = 11,
-  anon_sym_decide = 12,
-  anon_sym_if = 13,
-  anon_sym_constrain = 14,
-  anon_sym_soft = 15,
-  sym_uniform = 16,
-  anon_sym_cardinality = 17,
-  anon_sym_to = 18,
-  sym_only = 19,
-  anon_sym_at = 20,
-  anon_sym_most = 21,
-  anon_sym_least = 22,
-  sym_file = 23,
-  sym__ident = 24,
-  sym_integer = 25,
-  sym_import = 26,
-  sym_decision = 27,
-  sym_rule = 28,
-  sym__rule_body = 29,
-  sym_constraint = 30,
-  sym_captures = 31,
-  sym_constraint_kind = 32,
-  sym_cardinality = 33,
-  sym_at_most = 34,
-  sym_at_least = 35,
-  sym_atom = 36,
-  sym_pattern = 37,
-  sym_tuple = 38,
-  aux_sym_file_repeat1 = 39,
-  aux_sym_import_repeat1 = 40,
-  aux_sym_import_repeat2 = 41,
-  aux_sym__rule_body_repeat1 = 42,
-  aux_sym_captures_repeat1 = 43,
-  aux_sym_tuple_repeat1 = 44,
+  anon_sym_type = 12,
+  anon_sym_EQ = 13,
+  anon_sym_PIPE = 14,
+  anon_sym_decide = 15,
+  anon_sym_if = 16,
+  anon_sym_not = 17,
+  sym_count = 18,
+  anon_sym_sum = 19,
+  anon_sym_min = 20,
+  anon_sym_max = 21,
+  anon_sym_constrain = 22,
+  anon_sym_soft = 23,
+  anon_sym_priority = 24,
+  sym_minimize = 25,
+  sym_maximize = 26,
+  sym_uniform = 27,
+  anon_sym_cardinality = 28,
+  anon_sym_to = 29,
+  sym_only = 30,
+  anon_sym_at = 31,
+  anon_sym_most = 32,
+  anon_sym_least = 33,
+  anon_sym_BANG_EQ = 34,
+  anon_sym_LT = 35,
+  anon_sym_LT_EQ = 36,
+  anon_sym_GT = 37,
+  anon_sym_GT_EQ = 38,
+  anon_sym_LBRACE = 39,
+  anon_sym_RBRACE = 40,
+  anon_sym_COLON = 41,
+  anon_sym_PLUS = 42,
+  anon_sym_DASH = 43,
+  anon_sym_STAR = 44,
+  anon_sym_SLASH = 45,
+  anon_sym_PERCENT = 46,
+  sym_file = 47,
+  sym__ident = 48,
+  sym_integer = 49,
+  sym_import = 50,
+  sym_type_declaration = 51,
+  sym_enum_declaration = 52,
+  sym_decision = 53,
+  sym_rule = 54,
+  sym__rule_body = 55,
+  sym__literal = 56,
+  sym_negation = 57,
+  sym_aggregate = 58,
+  sym_sum = 59,
+  sym_min = 60,
+  sym_max = 61,
+  sym_constraint = 62,
+  sym_captures = 63,
+  sym_objective = 64,
+  sym_constraint_kind = 65,
+  sym_cardinality = 66,
+  sym_at_most = 67,
+  sym_at_least = 68,
+  sym_atom = 69,
+  sym_comparison = 70,
+  sym_pattern = 71,
+  sym_tuple = 72,
+  sym_record = 73,
+  sym_field = 74,
+  sym__operand = 75,
+  sym_expression = 76,
+  aux_sym_file_repeat1 = 77,
+  aux_sym_import_repeat1 = 78,
+  aux_sym_import_repeat2 = 79,
+  aux_sym_enum_declaration_repeat1 = 80,
+  aux_sym__rule_body_repeat1 = 81,
+  aux_sym_captures_repeat1 = 82,
+  aux_sym_tuple_repeat1 = 83,
+  aux_sym_record_repeat1 = 84,
 };
 
 static const char * const ts_symbol_names[] = {
@@ -75,10 +115,21 @@ static const char * const ts_symbol_names[] = {
   [anon_sym_LPAREN] = "(",
   [anon_sym_COMMA] = ",",
   [anon_sym_RPAREN] = ")",
+  [anon_sym_type] = "type",
+  [anon_sym_EQ] = "=",
+  [anon_sym_PIPE] = "|",
   [anon_sym_decide] = "decide",
   [anon_sym_if] = "if",
+  [anon_sym_not] = "not",
+  [sym_count] = "count",
+  [anon_sym_sum] = "sum",
+  [anon_sym_min] = "min",
+  [anon_sym_max] = "max",
   [anon_sym_constrain] = "constrain",
   [anon_sym_soft] = "soft",
+  [anon_sym_priority] = "priority",
+  [sym_minimize] = "minimize",
+  [sym_maximize] = "maximize",
   [sym_uniform] = "uniform",
   [anon_sym_cardinality] = "cardinality",
   [anon_sym_to] = "to",
@@ -86,28 +137,57 @@ static const char * const ts_symbol_names[] = {
   [anon_sym_at] = "at",
   [anon_sym_most] = "most",
   [anon_sym_least] = "least",
+  [anon_sym_BANG_EQ] = "!=",
+  [anon_sym_LT] = "<",
+  [anon_sym_LT_EQ] = "<=",
+  [anon_sym_GT] = ">",
+  [anon_sym_GT_EQ] = ">=",
+  [anon_sym_LBRACE] = "{",
+  [anon_sym_RBRACE] = "}",
+  [anon_sym_COLON] = ":",
+  [anon_sym_PLUS] = "+",
+  [anon_sym_DASH] = "-",
+  [anon_sym_STAR] = "*",
+  [anon_sym_SLASH] = "/",
+  [anon_sym_PERCENT] = "%",
   [sym_file] = "file",
   [sym__ident] = "_ident",
   [sym_integer] = "integer",
   [sym_import] = "import",
+  [sym_type_declaration] = "type_declaration",
+  [sym_enum_declaration] = "enum_declaration",
   [sym_decision] = "decision",
   [sym_rule] = "rule",
   [sym__rule_body] = "_rule_body",
+  [sym__literal] = "_literal",
+  [sym_negation] = "negation",
+  [sym_aggregate] = "aggregate",
+  [sym_sum] = "sum",
+  [sym_min] = "min",
+  [sym_max] = "max",
   [sym_constraint] = "constraint",
   [sym_captures] = "captures",
+  [sym_objective] = "objective",
   [sym_constraint_kind] = "constraint_kind",
   [sym_cardinality] = "cardinality",
   [sym_at_most] = "at_most",
   [sym_at_least] = "at_least",
   [sym_atom] = "atom",
+  [sym_comparison] = "comparison",
   [sym_pattern] = "pattern",
   [sym_tuple] = "tuple",
+  [sym_record] = "record",
+  [sym_field] = "field",
+  [sym__operand] = "_operand",
+  [sym_expression] = "expression",
   [aux_sym_file_repeat1] = "file_repeat1",
   [aux_sym_import_repeat1] = "import_repeat1",
   [aux_sym_import_repeat2] = "import_repeat2",
+  [aux_sym_enum_declaration_repeat1] = "enum_declaration_repeat1",
   [aux_sym__rule_body_repeat1] = "_rule_body_repeat1",
   [aux_sym_captures_repeat1] = "captures_repeat1",
   [aux_sym_tuple_repeat1] = "tuple_repeat1",
+  [aux_sym_record_repeat1] = "record_repeat1",
 };
 
 static const TSSymbol ts_symbol_map[] = {
@@ -123,10 +203,21 @@ static const TSSymbol ts_symbol_map[] = {
   [anon_sym_LPAREN] = anon_sym_LPAREN,
   [anon_sym_COMMA] = anon_sym_COMMA,
   [anon_sym_RPAREN] = anon_sym_RPAREN,
+  [anon_sym_type] = anon_sym_type,
+  [anon_sym_EQ] = anon_sym_EQ,
+  [anon_sym_PIPE] = anon_sym_PIPE,
   [anon_sym_decide] = anon_sym_decide,
   [anon_sym_if] = anon_sym_if,
+  [anon_sym_not] = anon_sym_not,
+  [sym_count] = sym_count,
+  [anon_sym_sum] = anon_sym_sum,
+  [anon_sym_min] = anon_sym_min,
+  [anon_sym_max] = anon_sym_max,
   [anon_sym_constrain] = anon_sym_constrain,
   [anon_sym_soft] = anon_sym_soft,
+  [anon_sym_priority] = anon_sym_priority,
+  [sym_minimize] = sym_minimize,
+  [sym_maximize] = sym_maximize,
   [sym_uniform] = sym_uniform,
   [anon_sym_cardinality] = anon_sym_cardinality,
   [anon_sym_to] = anon_sym_to,
@@ -134,28 +225,57 @@ static const TSSymbol ts_symbol_map[] = {
   [anon_sym_at] = anon_sym_at,
   [anon_sym_most] = anon_sym_most,
   [anon_sym_least] = anon_sym_least,
+  [anon_sym_BANG_EQ] = anon_sym_BANG_EQ,
+  [anon_sym_LT] = anon_sym_LT,
+  [anon_sym_LT_EQ] = anon_sym_LT_EQ,
+  [anon_sym_GT] = anon_sym_GT,
+  [anon_sym_GT_EQ] = anon_sym_GT_EQ,
+  [anon_sym_LBRACE] = anon_sym_LBRACE,
+  [anon_sym_RBRACE] = anon_sym_RBRACE,
+  [anon_sym_COLON] = anon_sym_COLON,
+  [anon_sym_PLUS] = anon_sym_PLUS,
+  [anon_sym_DASH] = anon_sym_DASH,
+  [anon_sym_STAR] = anon_sym_STAR,
+  [anon_sym_SLASH] = anon_sym_SLASH,
+  [anon_sym_PERCENT] = anon_sym_PERCENT,
   [sym_file] = sym_file,
   [sym__ident] = sym__ident,
   [sym_integer] = sym_integer,
   [sym_import] = sym_import,
+  [sym_type_declaration] = sym_type_declaration,
+  [sym_enum_declaration] = sym_enum_declaration,
   [sym_decision] = sym_decision,
   [sym_rule] = sym_rule,
   [sym__rule_body] = sym__rule_body,
+  [sym__literal] = sym__literal,
+  [sym_negation] = sym_negation,
+  [sym_aggregate] = sym_aggregate,
+  [sym_sum] = sym_sum,
+  [sym_min] = sym_min,
+  [sym_max] = sym_max,
   [sym_constraint] = sym_constraint,
   [sym_captures] = sym_captures,
+  [sym_objective] = sym_objective,
   [sym_constraint_kind] = sym_constraint_kind,
   [sym_cardinality] = sym_cardinality,
   [sym_at_most] = sym_at_most,
   [sym_at_least] = sym_at_least,
   [sym_atom] = sym_atom,
+  [sym_comparison] = sym_comparison,
   [sym_pattern] = sym_pattern,
   [sym_tuple] = sym_tuple,
+  [sym_record] = sym_record,
+  [sym_field] = sym_field,
+  [sym__operand] = sym__operand,
+  [sym_expression] = sym_expression,
   [aux_sym_file_repeat1] = aux_sym_file_repeat1,
   [aux_sym_import_repeat1] = aux_sym_import_repeat1,
   [aux_sym_import_repeat2] = aux_sym_import_repeat2,
+  [aux_sym_enum_declaration_repeat1] = aux_sym_enum_declaration_repeat1,
   [aux_sym__rule_body_repeat1] = aux_sym__rule_body_repeat1,
   [aux_sym_captures_repeat1] = aux_sym_captures_repeat1,
   [aux_sym_tuple_repeat1] = aux_sym_tuple_repeat1,
+  [aux_sym_record_repeat1] = aux_sym_record_repeat1,
 };
 
 static const TSSymbolMetadata ts_symbol_metadata[] = {
@@ -207,6 +327,18 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_type] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_PIPE] = {
+    .visible = true,
+    .named = false,
+  },
   [anon_sym_decide] = {
     .visible = true,
     .named = false,
@@ -215,6 +347,26 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_not] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_count] = {
+    .visible = true,
+    .named = true,
+  },
+  [anon_sym_sum] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_min] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_max] = {
+    .visible = true,
+    .named = false,
+  },
   [anon_sym_constrain] = {
     .visible = true,
     .named = false,
@@ -223,6 +375,18 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_priority] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_minimize] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_maximize] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_uniform] = {
     .visible = true,
     .named = true,
@@ -251,6 +415,58 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
+  [anon_sym_BANG_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_LT] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_LT_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_GT] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_GT_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_LBRACE] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_RBRACE] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_COLON] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_PLUS] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_DASH] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_STAR] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_SLASH] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_PERCENT] = {
+    .visible = true,
+    .named = false,
+  },
   [sym_file] = {
     .visible = true,
     .named = true,
@@ -267,6 +483,14 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
+  [sym_type_declaration] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_enum_declaration] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_decision] = {
     .visible = true,
     .named = true,
@@ -279,6 +503,30 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = false,
     .named = true,
   },
+  [sym__literal] = {
+    .visible = false,
+    .named = true,
+  },
+  [sym_negation] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_aggregate] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_sum] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_min] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_max] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_constraint] = {
     .visible = true,
     .named = true,
@@ -287,6 +535,10 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
+  [sym_objective] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_constraint_kind] = {
     .visible = true,
     .named = true,
@@ -307,6 +559,10 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
+  [sym_comparison] = {
+    .visible = true,
+    .named = true,
+  },
   [sym_pattern] = {
     .visible = true,
     .named = true,
@@ -315,6 +571,22 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
+  [sym_record] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_field] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym__operand] = {
+    .visible = false,
+    .named = true,
+  },
+  [sym_expression] = {
+    .visible = true,
+    .named = true,
+  },
   [aux_sym_file_repeat1] = {
     .visible = false,
     .named = false,
@@ -327,6 +599,10 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = false,
     .named = false,
   },
+  [aux_sym_enum_declaration_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
   [aux_sym__rule_body_repeat1] = {
     .visible = false,
     .named = false,
@@ -339,35 +615,87 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = false,
     .named = false,
   },
+  [aux_sym_record_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
 };
 
 enum ts_field_identifiers {
-  field_body = 1,
-  field_captures = 2,
-  field_head = 3,
-  field_kind = 4,
-  field_relation = 5,
-  field_soft = 6,
+  field_atom = 1,
+  field_body = 2,
+  field_captures = 3,
+  field_head = 4,
+  field_key = 5,
+  field_kind = 6,
+  field_lhs = 7,
+  field_module = 8,
+  field_name = 9,
+  field_op = 10,
+  field_priority = 11,
+  field_relation = 12,
+  field_result = 13,
+  field_rhs = 14,
+  field_sense = 15,
+  field_soft = 16,
+  field_target = 17,
+  field_type = 18,
+  field_value = 19,
+  field_variant = 20,
 };
 
 static const char * const ts_field_names[] = {
   [0] = NULL,
+  [field_atom] = "atom",
   [field_body] = "body",
   [field_captures] = "captures",
   [field_head] = "head",
+  [field_key] = "key",
   [field_kind] = "kind",
+  [field_lhs] = "lhs",
+  [field_module] = "module",
+  [field_name] = "name",
+  [field_op] = "op",
+  [field_priority] = "priority",
   [field_relation] = "relation",
+  [field_result] = "result",
+  [field_rhs] = "rhs",
+  [field_sense] = "sense",
   [field_soft] = "soft",
+  [field_target] = "target",
+  [field_type] = "type",
+  [field_value] = "value",
+  [field_variant] = "variant",
 };
 
 static const TSFieldMapSlice ts_field_map_slices[PRODUCTION_ID_COUNT] = {
   [1] = {.index = 0, .length = 1},
   [2] = {.index = 1, .length = 1},
-  [3] = {.index = 2, .length = 2},
-  [4] = {.index = 4, .length = 3},
-  [5] = {.index = 7, .length = 3},
-  [6] = {.index = 10, .length = 6},
-  [7] = {.index = 16, .length = 7},
+  [3] = {.index = 2, .length = 3},
+  [4] = {.index = 5, .length = 1},
+  [5] = {.index = 6, .length = 2},
+  [6] = {.index = 8, .length = 2},
+  [7] = {.index = 10, .length = 1},
+  [8] = {.index = 11, .length = 2},
+  [9] = {.index = 13, .length = 3},
+  [10] = {.index = 16, .length = 3},
+  [11] = {.index = 19, .length = 2},
+  [12] = {.index = 21, .length = 2},
+  [13] = {.index = 23, .length = 3},
+  [14] = {.index = 26, .length = 1},
+  [15] = {.index = 27, .length = 2},
+  [16] = {.index = 29, .length = 1},
+  [17] = {.index = 30, .length = 3},
+  [18] = {.index = 33, .length = 2},
+  [19] = {.index = 35, .length = 3},
+  [20] = {.index = 38, .length = 1},
+  [21] = {.index = 39, .length = 3},
+  [22] = {.index = 42, .length = 2},
+  [23] = {.index = 44, .length = 3},
+  [24] = {.index = 47, .length = 4},
+  [25] = {.index = 51, .length = 6},
+  [26] = {.index = 57, .length = 7},
+  [27] = {.index = 64, .length = 7},
 };
 
 static const TSFieldMapEntry ts_field_map_entries[] = {
@@ -376,24 +704,92 @@ static const TSFieldMapEntry ts_field_map_entries[] = {
   [1] =
     {field_head, 0},
   [2] =
+    {field_lhs, 0},
+    {field_op, 1},
+    {field_rhs, 2},
+  [5] =
+    {field_module, 1},
+  [6] =
+    {field_module, 0, .inherited = true},
+    {field_module, 1, .inherited = true},
+  [8] =
+    {field_relation, 1},
+    {field_type, 2},
+  [10] =
+    {field_atom, 1},
+  [11] =
+    {field_body, 2},
+    {field_kind, 1},
+  [13] =
     {field_body, 2},
     {field_kind, 1},
-  [4] =
+    {field_sense, 0},
+  [16] =
     {field_body, 1},
     {field_body, 2},
     {field_head, 0},
-  [7] =
+  [19] =
+    {field_key, 0},
+    {field_value, 2},
+  [21] =
+    {field_name, 1},
+    {field_variant, 3},
+  [23] =
     {field_body, 3},
     {field_captures, 1},
     {field_kind, 2},
-  [10] =
+  [26] =
+    {field_target, 2},
+  [27] =
+    {field_module, 1},
+    {field_relation, 4},
+  [29] =
+    {field_variant, 1},
+  [30] =
+    {field_name, 1},
+    {field_variant, 3},
+    {field_variant, 4, .inherited = true},
+  [33] =
+    {field_variant, 0, .inherited = true},
+    {field_variant, 1, .inherited = true},
+  [35] =
+    {field_atom, 3},
+    {field_kind, 2},
+    {field_result, 0},
+  [38] =
+    {field_relation, 1},
+  [39] =
+    {field_module, 1},
+    {field_relation, 4},
+    {field_relation, 5, .inherited = true},
+  [42] =
+    {field_relation, 0, .inherited = true},
+    {field_relation, 1, .inherited = true},
+  [44] =
+    {field_module, 1},
+    {field_module, 2, .inherited = true},
+    {field_relation, 5},
+  [47] =
+    {field_module, 1},
+    {field_module, 2, .inherited = true},
+    {field_relation, 5},
+    {field_relation, 6, .inherited = true},
+  [51] =
     {field_body, 6},
     {field_kind, 5},
     {field_soft, 1},
     {field_soft, 2},
     {field_soft, 3},
     {field_soft, 4},
-  [16] =
+  [57] =
+    {field_body, 6},
+    {field_kind, 5},
+    {field_priority, 1},
+    {field_priority, 2},
+    {field_priority, 3},
+    {field_priority, 4},
+    {field_sense, 0},
+  [64] =
     {field_body, 7},
     {field_captures, 5},
     {field_kind, 6},
@@ -463,7 +859,7 @@ static const TSStateId ts_primary_state_ids[STATE_COUNT] = {
   [48] = 48,
   [49] = 49,
   [50] = 50,
-  [51] = 51,
+  [51] = 5,
   [52] = 52,
   [53] = 53,
   [54] = 54,
@@ -496,6 +892,71 @@ static const TSStateId ts_primary_state_ids[STATE_COUNT] = {
   [81] = 81,
   [82] = 82,
   [83] = 83,
+  [84] = 84,
+  [85] = 85,
+  [86] = 86,
+  [87] = 87,
+  [88] = 88,
+  [89] = 89,
+  [90] = 90,
+  [91] = 91,
+  [92] = 92,
+  [93] = 93,
+  [94] = 94,
+  [95] = 95,
+  [96] = 96,
+  [97] = 97,
+  [98] = 98,
+  [99] = 99,
+  [100] = 100,
+  [101] = 101,
+  [102] = 102,
+  [103] = 103,
+  [104] = 104,
+  [105] = 105,
+  [106] = 106,
+  [107] = 107,
+  [108] = 108,
+  [109] = 109,
+  [110] = 110,
+  [111] = 111,
+  [112] = 112,
+  [113] = 113,
+  [114] = 114,
+  [115] = 115,
+  [116] = 116,
+  [117] = 117,
+  [118] = 118,
+  [119] = 119,
+  [120] = 120,
+  [121] = 121,
+  [122] = 122,
+  [123] = 123,
+  [124] = 124,
+  [125] = 125,
+  [126] = 126,
+  [127] = 127,
+  [128] = 128,
+  [129] = 129,
+  [130] = 130,
+  [131] = 131,
+  [132] = 132,
+  [133] = 133,
+  [134] = 134,
+  [135] = 135,
+  [136] = 136,
+  [137] = 137,
+  [138] = 138,
+  [139] = 139,
+  [140] = 140,
+  [141] = 141,
+  [142] = 142,
+  [143] = 143,
+  [144] = 144,
+  [145] = 145,
+  [146] = 146,
+  [147] = 147,
+  [148] = 148,
 };
 
 static bool ts_lex(TSLexer *lexer, TSStateId state) {
@@ -503,275 +964,597 @@ static bool ts_lex(TSLexer *lexer, TSStateId state) {
   eof = lexer->eof(lexer);
   switch (state) {
     case 0:
-      if (eof) ADVANCE(52);
+      if (eof) ADVANCE(95);
       ADVANCE_MAP(
-        '(', 61,
-        ')', 63,
-        ',', 62,
-        '-', 51,
-        '.', 60,
-        '0', 57,
+        '!', 5,
+        '%', 146,
+        '(', 106,
+        ')', 108,
+        '*', 144,
+        '+', 142,
+        ',', 107,
+        '-', 143,
+        '.', 105,
+        '/', 145,
+        '0', 102,
+        ':', 141,
         ';', 1,
-        'a', 42,
-        'c', 3,
-        'd', 10,
-        'i', 13,
-        'l', 11,
-        'm', 29,
-        'o', 24,
-        's', 30,
-        't', 31,
-        'u', 25,
-        '\t', 53,
-        '\n', 53,
-        '\r', 53,
-        ' ', 53,
+        '<', 135,
+        '=', 110,
+        '>', 137,
+        'a', 72,
+        'c', 7,
+        'd', 16,
+        'i', 22,
+        'l', 17,
+        'm', 6,
+        'n', 55,
+        'o', 43,
+        'p', 63,
+        's', 53,
+        't', 54,
+        'u', 48,
+        '{', 139,
+        '|', 111,
+        '}', 140,
+        '\t', 96,
+        '\n', 96,
+        '\r', 96,
+        ' ', 96,
       );
-      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(58);
-      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(56);
+      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(101);
       END_STATE();
     case 1:
-      if (lookahead == '\n') ADVANCE(54);
+      if (lookahead == '\n') ADVANCE(97);
       if (lookahead != 0) ADVANCE(1);
       END_STATE();
     case 2:
-      if (lookahead == '(') ADVANCE(61);
-      if (lookahead == '-') ADVANCE(51);
-      if (lookahead == '0') ADVANCE(57);
-      if (lookahead == ';') ADVANCE(1);
-      if (lookahead == '\t' ||
-          lookahead == '\n' ||
-          lookahead == '\r' ||
-          lookahead == ' ') ADVANCE(53);
-      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(58);
-      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(56);
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(55);
+      ADVANCE_MAP(
+        '(', 106,
+        '-', 93,
+        '0', 102,
+        ';', 1,
+        '=', 110,
+        '{', 139,
+        '\t', 96,
+        '\n', 96,
+        '\r', 96,
+        ' ', 96,
+      );
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(103);
+      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(101);
+      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
       END_STATE();
     case 3:
-      if (lookahead == 'a') ADVANCE(35);
-      if (lookahead == 'o') ADVANCE(28);
+      ADVANCE_MAP(
+        '(', 106,
+        '-', 93,
+        '0', 102,
+        ';', 1,
+        'n', 98,
+        '\t', 96,
+        '\n', 96,
+        '\r', 96,
+        ' ', 96,
+      );
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(103);
+      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
+      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(101);
       END_STATE();
     case 4:
-      if (lookahead == 'a') ADVANCE(22);
+      ADVANCE_MAP(
+        ';', 1,
+        'c', 56,
+        'm', 10,
+        'p', 63,
+        's', 82,
+        '\t', 96,
+        '\n', 96,
+        '\r', 96,
+        ' ', 96,
+      );
       END_STATE();
     case 5:
-      if (lookahead == 'a') ADVANCE(19);
+      if (lookahead == '=') ADVANCE(134);
       END_STATE();
     case 6:
-      if (lookahead == 'a') ADVANCE(41);
+      if (lookahead == 'a') ADVANCE(84);
+      if (lookahead == 'i') ADVANCE(44);
+      if (lookahead == 'o') ADVANCE(69);
       END_STATE();
     case 7:
-      if (lookahead == 'c') ADVANCE(17);
+      if (lookahead == 'a') ADVANCE(64);
+      if (lookahead == 'o') ADVANCE(51);
       END_STATE();
     case 8:
-      if (lookahead == 'd') ADVANCE(12);
+      if (lookahead == 'a') ADVANCE(86);
+      if (lookahead == 'i') ADVANCE(52);
       END_STATE();
     case 9:
-      if (lookahead == 'd') ADVANCE(18);
+      if (lookahead == 'a') ADVANCE(37);
       END_STATE();
     case 10:
-      if (lookahead == 'e') ADVANCE(7);
+      if (lookahead == 'a') ADVANCE(85);
+      if (lookahead == 'i') ADVANCE(46);
       END_STATE();
     case 11:
-      if (lookahead == 'e') ADVANCE(6);
+      if (lookahead == 'a') ADVANCE(31);
       END_STATE();
     case 12:
-      if (lookahead == 'e') ADVANCE(64);
+      if (lookahead == 'a') ADVANCE(71);
       END_STATE();
     case 13:
-      if (lookahead == 'f') ADVANCE(65);
-      if (lookahead == 'm') ADVANCE(34);
+      if (lookahead == 'c') ADVANCE(28);
       END_STATE();
     case 14:
-      if (lookahead == 'f') ADVANCE(44);
+      if (lookahead == 'd') ADVANCE(19);
       END_STATE();
     case 15:
-      if (lookahead == 'f') ADVANCE(33);
+      if (lookahead == 'd') ADVANCE(30);
       END_STATE();
     case 16:
-      if (lookahead == 'i') ADVANCE(15);
+      if (lookahead == 'e') ADVANCE(13);
       END_STATE();
     case 17:
-      if (lookahead == 'i') ADVANCE(8);
+      if (lookahead == 'e') ADVANCE(12);
       END_STATE();
     case 18:
-      if (lookahead == 'i') ADVANCE(27);
+      if (lookahead == 'e') ADVANCE(109);
       END_STATE();
     case 19:
-      if (lookahead == 'i') ADVANCE(26);
+      if (lookahead == 'e') ADVANCE(112);
       END_STATE();
     case 20:
-      if (lookahead == 'i') ADVANCE(47);
+      if (lookahead == 'e') ADVANCE(126);
       END_STATE();
     case 21:
-      if (lookahead == 'l') ADVANCE(49);
+      if (lookahead == 'e') ADVANCE(125);
       END_STATE();
     case 22:
-      if (lookahead == 'l') ADVANCE(20);
+      if (lookahead == 'f') ADVANCE(113);
+      if (lookahead == 'm') ADVANCE(61);
       END_STATE();
     case 23:
-      if (lookahead == 'm') ADVANCE(68);
+      if (lookahead == 'f') ADVANCE(75);
       END_STATE();
     case 24:
-      if (lookahead == 'n') ADVANCE(21);
+      if (lookahead == 'f') ADVANCE(58);
       END_STATE();
     case 25:
-      if (lookahead == 'n') ADVANCE(16);
+      if (lookahead == 'i') ADVANCE(91);
       END_STATE();
     case 26:
-      if (lookahead == 'n') ADVANCE(66);
+      if (lookahead == 'i') ADVANCE(24);
       END_STATE();
     case 27:
-      if (lookahead == 'n') ADVANCE(4);
+      if (lookahead == 'i') ADVANCE(41);
       END_STATE();
     case 28:
-      if (lookahead == 'n') ADVANCE(40);
+      if (lookahead == 'i') ADVANCE(14);
       END_STATE();
     case 29:
-      if (lookahead == 'o') ADVANCE(39);
+      if (lookahead == 'i') ADVANCE(60);
       END_STATE();
     case 30:
-      if (lookahead == 'o') ADVANCE(14);
+      if (lookahead == 'i') ADVANCE(47);
       END_STATE();
     case 31:
-      if (lookahead == 'o') ADVANCE(70);
+      if (lookahead == 'i') ADVANCE(45);
       END_STATE();
     case 32:
-      if (lookahead == 'o') ADVANCE(38);
+      if (lookahead == 'i') ADVANCE(79);
       END_STATE();
     case 33:
-      if (lookahead == 'o') ADVANCE(36);
+      if (lookahead == 'i') ADVANCE(80);
       END_STATE();
     case 34:
-      if (lookahead == 'p') ADVANCE(32);
+      if (lookahead == 'i') ADVANCE(92);
       END_STATE();
     case 35:
-      if (lookahead == 'r') ADVANCE(9);
+      if (lookahead == 'i') ADVANCE(42);
       END_STATE();
     case 36:
-      if (lookahead == 'r') ADVANCE(23);
+      if (lookahead == 'l') ADVANCE(87);
       END_STATE();
     case 37:
-      if (lookahead == 'r') ADVANCE(5);
+      if (lookahead == 'l') ADVANCE(33);
       END_STATE();
     case 38:
-      if (lookahead == 'r') ADVANCE(46);
+      if (lookahead == 'm') ADVANCE(61);
       END_STATE();
     case 39:
-      if (lookahead == 's') ADVANCE(43);
+      if (lookahead == 'm') ADVANCE(117);
       END_STATE();
     case 40:
-      if (lookahead == 's') ADVANCE(48);
+      if (lookahead == 'm') ADVANCE(127);
       END_STATE();
     case 41:
-      if (lookahead == 's') ADVANCE(45);
+      if (lookahead == 'm') ADVANCE(25);
       END_STATE();
     case 42:
-      if (lookahead == 't') ADVANCE(72);
+      if (lookahead == 'm') ADVANCE(34);
       END_STATE();
     case 43:
-      if (lookahead == 't') ADVANCE(73);
+      if (lookahead == 'n') ADVANCE(36);
       END_STATE();
     case 44:
-      if (lookahead == 't') ADVANCE(67);
+      if (lookahead == 'n') ADVANCE(119);
       END_STATE();
     case 45:
-      if (lookahead == 't') ADVANCE(74);
+      if (lookahead == 'n') ADVANCE(122);
       END_STATE();
     case 46:
-      if (lookahead == 't') ADVANCE(59);
+      if (lookahead == 'n') ADVANCE(118);
       END_STATE();
     case 47:
-      if (lookahead == 't') ADVANCE(50);
+      if (lookahead == 'n') ADVANCE(9);
       END_STATE();
     case 48:
-      if (lookahead == 't') ADVANCE(37);
+      if (lookahead == 'n') ADVANCE(26);
       END_STATE();
     case 49:
-      if (lookahead == 'y') ADVANCE(71);
+      if (lookahead == 'n') ADVANCE(76);
       END_STATE();
     case 50:
-      if (lookahead == 'y') ADVANCE(69);
+      if (lookahead == 'n') ADVANCE(70);
       END_STATE();
     case 51:
-      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(58);
+      if (lookahead == 'n') ADVANCE(70);
+      if (lookahead == 'u') ADVANCE(49);
       END_STATE();
     case 52:
-      ACCEPT_TOKEN(ts_builtin_sym_end);
+      if (lookahead == 'n') ADVANCE(35);
       END_STATE();
     case 53:
-      ACCEPT_TOKEN(sym__whitespace);
+      if (lookahead == 'o') ADVANCE(23);
+      if (lookahead == 'u') ADVANCE(39);
       END_STATE();
     case 54:
-      ACCEPT_TOKEN(sym_comment);
+      if (lookahead == 'o') ADVANCE(129);
+      if (lookahead == 'y') ADVANCE(62);
       END_STATE();
     case 55:
+      if (lookahead == 'o') ADVANCE(73);
+      END_STATE();
+    case 56:
+      if (lookahead == 'o') ADVANCE(83);
+      END_STATE();
+    case 57:
+      if (lookahead == 'o') ADVANCE(68);
+      END_STATE();
+    case 58:
+      if (lookahead == 'o') ADVANCE(66);
+      END_STATE();
+    case 59:
+      if (lookahead == 'o') ADVANCE(50);
+      END_STATE();
+    case 60:
+      if (lookahead == 'o') ADVANCE(67);
+      END_STATE();
+    case 61:
+      if (lookahead == 'p') ADVANCE(57);
+      END_STATE();
+    case 62:
+      if (lookahead == 'p') ADVANCE(18);
+      END_STATE();
+    case 63:
+      if (lookahead == 'r') ADVANCE(29);
+      END_STATE();
+    case 64:
+      if (lookahead == 'r') ADVANCE(15);
+      END_STATE();
+    case 65:
+      if (lookahead == 'r') ADVANCE(11);
+      END_STATE();
+    case 66:
+      if (lookahead == 'r') ADVANCE(40);
+      END_STATE();
+    case 67:
+      if (lookahead == 'r') ADVANCE(32);
+      END_STATE();
+    case 68:
+      if (lookahead == 'r') ADVANCE(78);
+      END_STATE();
+    case 69:
+      if (lookahead == 's') ADVANCE(74);
+      END_STATE();
+    case 70:
+      if (lookahead == 's') ADVANCE(81);
+      END_STATE();
+    case 71:
+      if (lookahead == 's') ADVANCE(77);
+      END_STATE();
+    case 72:
+      if (lookahead == 't') ADVANCE(131);
+      END_STATE();
+    case 73:
+      if (lookahead == 't') ADVANCE(114);
+      END_STATE();
+    case 74:
+      if (lookahead == 't') ADVANCE(132);
+      END_STATE();
+    case 75:
+      if (lookahead == 't') ADVANCE(123);
+      END_STATE();
+    case 76:
+      if (lookahead == 't') ADVANCE(116);
+      END_STATE();
+    case 77:
+      if (lookahead == 't') ADVANCE(133);
+      END_STATE();
+    case 78:
+      if (lookahead == 't') ADVANCE(104);
+      END_STATE();
+    case 79:
+      if (lookahead == 't') ADVANCE(88);
+      END_STATE();
+    case 80:
+      if (lookahead == 't') ADVANCE(89);
+      END_STATE();
+    case 81:
+      if (lookahead == 't') ADVANCE(65);
+      END_STATE();
+    case 82:
+      if (lookahead == 'u') ADVANCE(39);
+      END_STATE();
+    case 83:
+      if (lookahead == 'u') ADVANCE(49);
+      END_STATE();
+    case 84:
+      if (lookahead == 'x') ADVANCE(121);
+      END_STATE();
+    case 85:
+      if (lookahead == 'x') ADVANCE(120);
+      END_STATE();
+    case 86:
+      if (lookahead == 'x') ADVANCE(27);
+      END_STATE();
+    case 87:
+      if (lookahead == 'y') ADVANCE(130);
+      END_STATE();
+    case 88:
+      if (lookahead == 'y') ADVANCE(124);
+      END_STATE();
+    case 89:
+      if (lookahead == 'y') ADVANCE(128);
+      END_STATE();
+    case 90:
+      if (lookahead == 'y') ADVANCE(62);
+      END_STATE();
+    case 91:
+      if (lookahead == 'z') ADVANCE(20);
+      END_STATE();
+    case 92:
+      if (lookahead == 'z') ADVANCE(21);
+      END_STATE();
+    case 93:
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(103);
+      END_STATE();
+    case 94:
+      if (eof) ADVANCE(95);
+      ADVANCE_MAP(
+        '-', 93,
+        '0', 102,
+        ';', 1,
+        'c', 59,
+        'd', 16,
+        'i', 38,
+        'm', 8,
+        't', 90,
+        '\t', 96,
+        '\n', 96,
+        '\r', 96,
+        ' ', 96,
+      );
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(103);
+      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(101);
+      END_STATE();
+    case 95:
+      ACCEPT_TOKEN(ts_builtin_sym_end);
+      END_STATE();
+    case 96:
+      ACCEPT_TOKEN(sym__whitespace);
+      END_STATE();
+    case 97:
+      ACCEPT_TOKEN(sym_comment);
+      END_STATE();
+    case 98:
       ACCEPT_TOKEN(sym_variable);
+      if (lookahead == 'o') ADVANCE(99);
       if (('0' <= lookahead && lookahead <= '9') ||
           lookahead == '_' ||
-          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(55);
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
       END_STATE();
-    case 56:
+    case 99:
+      ACCEPT_TOKEN(sym_variable);
+      if (lookahead == 't') ADVANCE(115);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_' ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
+      END_STATE();
+    case 100:
+      ACCEPT_TOKEN(sym_variable);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_' ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
+      END_STATE();
+    case 101:
       ACCEPT_TOKEN(sym_symbol);
       if (('0' <= lookahead && lookahead <= '9') ||
           ('A' <= lookahead && lookahead <= 'Z') ||
-          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(56);
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(101);
       END_STATE();
-    case 57:
+    case 102:
       ACCEPT_TOKEN(anon_sym_0);
       END_STATE();
-    case 58:
+    case 103:
       ACCEPT_TOKEN(aux_sym_integer_token1);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(58);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(103);
       END_STATE();
-    case 59:
+    case 104:
       ACCEPT_TOKEN(anon_sym_import);
       END_STATE();
-    case 60:
+    case 105:
       ACCEPT_TOKEN(anon_sym_DOT);
       END_STATE();
-    case 61:
+    case 106:
       ACCEPT_TOKEN(anon_sym_LPAREN);
       END_STATE();
-    case 62:
+    case 107:
       ACCEPT_TOKEN(anon_sym_COMMA);
       END_STATE();
-    case 63:
+    case 108:
       ACCEPT_TOKEN(anon_sym_RPAREN);
       END_STATE();
-    case 64:
+    case 109:
+      ACCEPT_TOKEN(anon_sym_type);
+      END_STATE();
+    case 110:
+      ACCEPT_TOKEN(anon_sym_EQ);
+      END_STATE();
+    case 111:
+      ACCEPT_TOKEN(anon_sym_PIPE);
+      END_STATE();
+    case 112:
       ACCEPT_TOKEN(anon_sym_decide);
       END_STATE();
-    case 65:
+    case 113:
       ACCEPT_TOKEN(anon_sym_if);
       END_STATE();
-    case 66:
+    case 114:
+      ACCEPT_TOKEN(anon_sym_not);
+      END_STATE();
+    case 115:
+      ACCEPT_TOKEN(anon_sym_not);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_' ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
+      END_STATE();
+    case 116:
+      ACCEPT_TOKEN(sym_count);
+      END_STATE();
+    case 117:
+      ACCEPT_TOKEN(anon_sym_sum);
+      END_STATE();
+    case 118:
+      ACCEPT_TOKEN(anon_sym_min);
+      END_STATE();
+    case 119:
+      ACCEPT_TOKEN(anon_sym_min);
+      if (lookahead == 'i') ADVANCE(42);
+      END_STATE();
+    case 120:
+      ACCEPT_TOKEN(anon_sym_max);
+      END_STATE();
+    case 121:
+      ACCEPT_TOKEN(anon_sym_max);
+      if (lookahead == 'i') ADVANCE(41);
+      END_STATE();
+    case 122:
       ACCEPT_TOKEN(anon_sym_constrain);
       END_STATE();
-    case 67:
+    case 123:
       ACCEPT_TOKEN(anon_sym_soft);
       END_STATE();
-    case 68:
+    case 124:
+      ACCEPT_TOKEN(anon_sym_priority);
+      END_STATE();
+    case 125:
+      ACCEPT_TOKEN(sym_minimize);
+      END_STATE();
+    case 126:
+      ACCEPT_TOKEN(sym_maximize);
+      END_STATE();
+    case 127:
       ACCEPT_TOKEN(sym_uniform);
       END_STATE();
-    case 69:
+    case 128:
       ACCEPT_TOKEN(anon_sym_cardinality);
       END_STATE();
-    case 70:
+    case 129:
       ACCEPT_TOKEN(anon_sym_to);
       END_STATE();
-    case 71:
+    case 130:
       ACCEPT_TOKEN(sym_only);
       END_STATE();
-    case 72:
+    case 131:
       ACCEPT_TOKEN(anon_sym_at);
       END_STATE();
-    case 73:
+    case 132:
       ACCEPT_TOKEN(anon_sym_most);
       END_STATE();
-    case 74:
+    case 133:
       ACCEPT_TOKEN(anon_sym_least);
       END_STATE();
+    case 134:
+      ACCEPT_TOKEN(anon_sym_BANG_EQ);
+      END_STATE();
+    case 135:
+      ACCEPT_TOKEN(anon_sym_LT);
+      if (lookahead == '=') ADVANCE(136);
+      END_STATE();
+    case 136:
+      ACCEPT_TOKEN(anon_sym_LT_EQ);
+      END_STATE();
+    case 137:
+      ACCEPT_TOKEN(anon_sym_GT);
+      if (lookahead == '=') ADVANCE(138);
+      END_STATE();
+    case 138:
+      ACCEPT_TOKEN(anon_sym_GT_EQ);
+      END_STATE();
+    case 139:
+      ACCEPT_TOKEN(anon_sym_LBRACE);
+      END_STATE();
+    case 140:
+      ACCEPT_TOKEN(anon_sym_RBRACE);
+      END_STATE();
+    case 141:
+      ACCEPT_TOKEN(anon_sym_COLON);
+      END_STATE();
+    case 142:
+      ACCEPT_TOKEN(anon_sym_PLUS);
+      END_STATE();
+    case 143:
+      ACCEPT_TOKEN(anon_sym_DASH);
+      END_STATE();
+    case 144:
+      ACCEPT_TOKEN(anon_sym_STAR);
+      END_STATE();
+    case 145:
+      ACCEPT_TOKEN(anon_sym_SLASH);
+      END_STATE();
+    case 146:
+      ACCEPT_TOKEN(anon_sym_PERCENT);
+      END_STATE();
+    case 147:
+      ADVANCE_MAP(
+        '!', 5,
+        '%', 146,
+        '(', 106,
+        '*', 144,
+        '+', 142,
+        '-', 148,
+        '/', 145,
+        '0', 102,
+        ';', 1,
+        '<', 135,
+        '=', 110,
+        '>', 137,
+        '{', 139,
+        '\t', 96,
+        '\n', 96,
+        '\r', 96,
+        ' ', 96,
+      );
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(103);
+      if (('A' <= lookahead && lookahead <= 'Z')) ADVANCE(101);
+      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(100);
+      END_STATE();
+    case 148:
+      ACCEPT_TOKEN(anon_sym_DASH);
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(103);
+      END_STATE();
     default:
       return false;
   }
@@ -779,62 +1562,62 @@ static bool ts_lex(TSLexer *lexer, TSStateId state) {
 
 static const TSLexMode ts_lex_modes[STATE_COUNT] = {
   [0] = {.lex_state = 0},
-  [1] = {.lex_state = 0},
-  [2] = {.lex_state = 0},
-  [3] = {.lex_state = 0},
-  [4] = {.lex_state = 2},
-  [5] = {.lex_state = 2},
-  [6] = {.lex_state = 2},
-  [7] = {.lex_state = 0},
+  [1] = {.lex_state = 94},
+  [2] = {.lex_state = 94},
+  [3] = {.lex_state = 94},
+  [4] = {.lex_state = 3},
+  [5] = {.lex_state = 0},
+  [6] = {.lex_state = 3},
+  [7] = {.lex_state = 3},
   [8] = {.lex_state = 0},
   [9] = {.lex_state = 0},
   [10] = {.lex_state = 0},
-  [11] = {.lex_state = 0},
-  [12] = {.lex_state = 0},
+  [11] = {.lex_state = 3},
+  [12] = {.lex_state = 3},
   [13] = {.lex_state = 0},
-  [14] = {.lex_state = 0},
-  [15] = {.lex_state = 0},
-  [16] = {.lex_state = 0},
-  [17] = {.lex_state = 0},
-  [18] = {.lex_state = 0},
-  [19] = {.lex_state = 0},
-  [20] = {.lex_state = 0},
-  [21] = {.lex_state = 0},
+  [14] = {.lex_state = 3},
+  [15] = {.lex_state = 3},
+  [16] = {.lex_state = 3},
+  [17] = {.lex_state = 2},
+  [18] = {.lex_state = 2},
+  [19] = {.lex_state = 2},
+  [20] = {.lex_state = 2},
+  [21] = {.lex_state = 2},
   [22] = {.lex_state = 0},
   [23] = {.lex_state = 0},
   [24] = {.lex_state = 0},
   [25] = {.lex_state = 0},
-  [26] = {.lex_state = 0},
-  [27] = {.lex_state = 0},
-  [28] = {.lex_state = 0},
-  [29] = {.lex_state = 0},
-  [30] = {.lex_state = 2},
-  [31] = {.lex_state = 0},
-  [32] = {.lex_state = 2},
-  [33] = {.lex_state = 0},
-  [34] = {.lex_state = 0},
-  [35] = {.lex_state = 0},
-  [36] = {.lex_state = 0},
-  [37] = {.lex_state = 0},
-  [38] = {.lex_state = 0},
-  [39] = {.lex_state = 0},
-  [40] = {.lex_state = 0},
-  [41] = {.lex_state = 0},
-  [42] = {.lex_state = 0},
-  [43] = {.lex_state = 0},
-  [44] = {.lex_state = 0},
-  [45] = {.lex_state = 0},
+  [26] = {.lex_state = 2},
+  [27] = {.lex_state = 94},
+  [28] = {.lex_state = 2},
+  [29] = {.lex_state = 94},
+  [30] = {.lex_state = 94},
+  [31] = {.lex_state = 94},
+  [32] = {.lex_state = 4},
+  [33] = {.lex_state = 94},
+  [34] = {.lex_state = 94},
+  [35] = {.lex_state = 94},
+  [36] = {.lex_state = 94},
+  [37] = {.lex_state = 94},
+  [38] = {.lex_state = 2},
+  [39] = {.lex_state = 94},
+  [40] = {.lex_state = 94},
+  [41] = {.lex_state = 94},
+  [42] = {.lex_state = 94},
+  [43] = {.lex_state = 94},
+  [44] = {.lex_state = 94},
+  [45] = {.lex_state = 94},
   [46] = {.lex_state = 0},
-  [47] = {.lex_state = 0},
-  [48] = {.lex_state = 0},
+  [47] = {.lex_state = 4},
+  [48] = {.lex_state = 4},
   [49] = {.lex_state = 0},
-  [50] = {.lex_state = 0},
-  [51] = {.lex_state = 0},
-  [52] = {.lex_state = 0},
+  [50] = {.lex_state = 3},
+  [51] = {.lex_state = 3},
+  [52] = {.lex_state = 3},
   [53] = {.lex_state = 0},
-  [54] = {.lex_state = 0},
-  [55] = {.lex_state = 0},
-  [56] = {.lex_state = 0},
+  [54] = {.lex_state = 3},
+  [55] = {.lex_state = 3},
+  [56] = {.lex_state = 3},
   [57] = {.lex_state = 0},
   [58] = {.lex_state = 0},
   [59] = {.lex_state = 0},
@@ -842,7 +1625,7 @@ static const TSLexMode ts_lex_modes[STATE_COUNT] = {
   [61] = {.lex_state = 0},
   [62] = {.lex_state = 0},
   [63] = {.lex_state = 0},
-  [64] = {.lex_state = 0},
+  [64] = {.lex_state = 94},
   [65] = {.lex_state = 0},
   [66] = {.lex_state = 0},
   [67] = {.lex_state = 0},
@@ -850,18 +1633,83 @@ static const TSLexMode ts_lex_modes[STATE_COUNT] = {
   [69] = {.lex_state = 0},
   [70] = {.lex_state = 0},
   [71] = {.lex_state = 0},
-  [72] = {.lex_state = 0},
+  [72] = {.lex_state = 94},
   [73] = {.lex_state = 0},
   [74] = {.lex_state = 0},
   [75] = {.lex_state = 0},
   [76] = {.lex_state = 0},
   [77] = {.lex_state = 0},
   [78] = {.lex_state = 0},
-  [79] = {.lex_state = 0},
+  [79] = {.lex_state = 2},
   [80] = {.lex_state = 0},
-  [81] = {.lex_state = 0},
+  [81] = {.lex_state = 2},
   [82] = {.lex_state = 0},
   [83] = {.lex_state = 0},
+  [84] = {.lex_state = 0},
+  [85] = {.lex_state = 0},
+  [86] = {.lex_state = 0},
+  [87] = {.lex_state = 94},
+  [88] = {.lex_state = 0},
+  [89] = {.lex_state = 0},
+  [90] = {.lex_state = 0},
+  [91] = {.lex_state = 0},
+  [92] = {.lex_state = 0},
+  [93] = {.lex_state = 0},
+  [94] = {.lex_state = 0},
+  [95] = {.lex_state = 0},
+  [96] = {.lex_state = 0},
+  [97] = {.lex_state = 0},
+  [98] = {.lex_state = 0},
+  [99] = {.lex_state = 0},
+  [100] = {.lex_state = 0},
+  [101] = {.lex_state = 0},
+  [102] = {.lex_state = 94},
+  [103] = {.lex_state = 94},
+  [104] = {.lex_state = 0},
+  [105] = {.lex_state = 0},
+  [106] = {.lex_state = 0},
+  [107] = {.lex_state = 0},
+  [108] = {.lex_state = 0},
+  [109] = {.lex_state = 0},
+  [110] = {.lex_state = 0},
+  [111] = {.lex_state = 0},
+  [112] = {.lex_state = 0},
+  [113] = {.lex_state = 0},
+  [114] = {.lex_state = 0},
+  [115] = {.lex_state = 0},
+  [116] = {.lex_state = 2},
+  [117] = {.lex_state = 0},
+  [118] = {.lex_state = 0},
+  [119] = {.lex_state = 0},
+  [120] = {.lex_state = 0},
+  [121] = {.lex_state = 0},
+  [122] = {.lex_state = 0},
+  [123] = {.lex_state = 0},
+  [124] = {.lex_state = 0},
+  [125] = {.lex_state = 0},
+  [126] = {.lex_state = 0},
+  [127] = {.lex_state = 0},
+  [128] = {.lex_state = 0},
+  [129] = {.lex_state = 0},
+  [130] = {.lex_state = 0},
+  [131] = {.lex_state = 0},
+  [132] = {.lex_state = 0},
+  [133] = {.lex_state = 0},
+  [134] = {.lex_state = 0},
+  [135] = {.lex_state = 2},
+  [136] = {.lex_state = 0},
+  [137] = {.lex_state = 0},
+  [138] = {.lex_state = 0},
+  [139] = {.lex_state = 0},
+  [140] = {.lex_state = 0},
+  [141] = {.lex_state = 0},
+  [142] = {.lex_state = 0},
+  [143] = {.lex_state = 0},
+  [144] = {.lex_state = 2},
+  [145] = {.lex_state = 0},
+  [146] = {.lex_state = 0},
+  [147] = {.lex_state = 0},
+  [148] = {.lex_state = 147},
 };
 
 static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
@@ -871,16 +1719,26 @@ static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
     [sym_comment] = ACTIONS(3),
     [sym_symbol] = ACTIONS(1),
     [anon_sym_0] = ACTIONS(1),
-    [aux_sym_integer_token1] = ACTIONS(1),
     [anon_sym_import] = ACTIONS(1),
     [anon_sym_DOT] = ACTIONS(1),
     [anon_sym_LPAREN] = ACTIONS(1),
     [anon_sym_COMMA] = ACTIONS(1),
     [anon_sym_RPAREN] = ACTIONS(1),
+    [anon_sym_type] = ACTIONS(1),
+    [anon_sym_EQ] = ACTIONS(1),
+    [anon_sym_PIPE] = ACTIONS(1),
     [anon_sym_decide] = ACTIONS(1),
     [anon_sym_if] = ACTIONS(1),
+    [anon_sym_not] = ACTIONS(1),
+    [sym_count] = ACTIONS(1),
+    [anon_sym_sum] = ACTIONS(1),
+    [anon_sym_min] = ACTIONS(1),
+    [anon_sym_max] = ACTIONS(1),
     [anon_sym_constrain] = ACTIONS(1),
     [anon_sym_soft] = ACTIONS(1),
+    [anon_sym_priority] = ACTIONS(1),
+    [sym_minimize] = ACTIONS(1),
+    [sym_maximize] = ACTIONS(1),
     [sym_uniform] = ACTIONS(1),
     [anon_sym_cardinality] = ACTIONS(1),
     [anon_sym_to] = ACTIONS(1),
@@ -888,881 +1746,1967 @@ static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
     [anon_sym_at] = ACTIONS(1),
     [anon_sym_most] = ACTIONS(1),
     [anon_sym_least] = ACTIONS(1),
+    [anon_sym_BANG_EQ] = ACTIONS(1),
+    [anon_sym_LT] = ACTIONS(1),
+    [anon_sym_LT_EQ] = ACTIONS(1),
+    [anon_sym_GT] = ACTIONS(1),
+    [anon_sym_GT_EQ] = ACTIONS(1),
+    [anon_sym_LBRACE] = ACTIONS(1),
+    [anon_sym_RBRACE] = ACTIONS(1),
+    [anon_sym_COLON] = ACTIONS(1),
+    [anon_sym_PLUS] = ACTIONS(1),
+    [anon_sym_DASH] = ACTIONS(1),
+    [anon_sym_STAR] = ACTIONS(1),
+    [anon_sym_SLASH] = ACTIONS(1),
+    [anon_sym_PERCENT] = ACTIONS(1),
   },
   [1] = {
-    [sym_file] = STATE(71),
-    [sym_import] = STATE(3),
-    [sym_decision] = STATE(3),
-    [sym_rule] = STATE(3),
-    [sym_constraint] = STATE(3),
-    [sym_atom] = STATE(55),
-    [aux_sym_file_repeat1] = STATE(3),
+    [sym_file] = STATE(121),
+    [sym_import] = STATE(2),
+    [sym_type_declaration] = STATE(2),
+    [sym_enum_declaration] = STATE(2),
+    [sym_decision] = STATE(2),
+    [sym_rule] = STATE(2),
+    [sym_constraint] = STATE(2),
+    [sym_objective] = STATE(2),
+    [sym_atom] = STATE(96),
+    [aux_sym_file_repeat1] = STATE(2),
     [ts_builtin_sym_end] = ACTIONS(5),
     [sym__whitespace] = ACTIONS(3),
     [sym_comment] = ACTIONS(3),
     [sym_symbol] = ACTIONS(7),
     [anon_sym_import] = ACTIONS(9),
-    [anon_sym_decide] = ACTIONS(11),
-    [anon_sym_constrain] = ACTIONS(13),
+    [anon_sym_type] = ACTIONS(11),
+    [anon_sym_decide] = ACTIONS(13),
+    [anon_sym_constrain] = ACTIONS(15),
+    [sym_minimize] = ACTIONS(17),
+    [sym_maximize] = ACTIONS(17),
   },
 };
 
 static const uint16_t ts_small_parse_table[] = {
-  [0] = 8,
-    ACTIONS(15), 1,
-      ts_builtin_sym_end,
-    ACTIONS(17), 1,
+  [0] = 10,
+    ACTIONS(7), 1,
       sym_symbol,
-    ACTIONS(20), 1,
+    ACTIONS(9), 1,
       anon_sym_import,
-    ACTIONS(23), 1,
+    ACTIONS(11), 1,
+      anon_sym_type,
+    ACTIONS(13), 1,
       anon_sym_decide,
-    ACTIONS(26), 1,
+    ACTIONS(15), 1,
       anon_sym_constrain,
-    STATE(55), 1,
+    ACTIONS(19), 1,
+      ts_builtin_sym_end,
+    STATE(96), 1,
       sym_atom,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    STATE(2), 5,
+    ACTIONS(17), 2,
+      sym_minimize,
+      sym_maximize,
+    STATE(3), 8,
       sym_import,
+      sym_type_declaration,
+      sym_enum_declaration,
       sym_decision,
       sym_rule,
       sym_constraint,
+      sym_objective,
       aux_sym_file_repeat1,
-  [30] = 8,
-    ACTIONS(7), 1,
+  [40] = 10,
+    ACTIONS(21), 1,
+      ts_builtin_sym_end,
+    ACTIONS(23), 1,
       sym_symbol,
-    ACTIONS(9), 1,
+    ACTIONS(26), 1,
       anon_sym_import,
-    ACTIONS(11), 1,
+    ACTIONS(29), 1,
+      anon_sym_type,
+    ACTIONS(32), 1,
       anon_sym_decide,
-    ACTIONS(13), 1,
+    ACTIONS(35), 1,
       anon_sym_constrain,
-    ACTIONS(29), 1,
-      ts_builtin_sym_end,
-    STATE(55), 1,
+    STATE(96), 1,
       sym_atom,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    STATE(2), 5,
+    ACTIONS(38), 2,
+      sym_minimize,
+      sym_maximize,
+    STATE(3), 8,
       sym_import,
+      sym_type_declaration,
+      sym_enum_declaration,
       sym_decision,
       sym_rule,
       sym_constraint,
+      sym_objective,
       aux_sym_file_repeat1,
-  [60] = 6,
-    ACTIONS(35), 1,
+  [80] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
       anon_sym_LPAREN,
-    STATE(49), 1,
-      sym_pattern,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(142), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [117] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(51), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(49), 14,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_if,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+      anon_sym_RBRACE,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [142] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(137), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [179] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(133), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [216] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(55), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(53), 14,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_if,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+      anon_sym_RBRACE,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [241] = 4,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(59), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(61), 3,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+    ACTIONS(57), 11,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_if,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+      anon_sym_RBRACE,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+  [268] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(59), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(57), 14,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_if,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+      anon_sym_RBRACE,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [293] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(120), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [330] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(143), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [367] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(65), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(63), 14,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_if,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+      anon_sym_RBRACE,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [392] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(140), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [429] = 9,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
+    STATE(115), 1,
+      sym__rule_body,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(89), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [466] = 8,
+    ACTIONS(365), 1,
+      sym_symbol,
+    ACTIONS(41), 1,
+      sym_variable,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(47), 1,
+      anon_sym_not,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(31), 2,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(23), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+    STATE(106), 5,
+      sym__literal,
+      sym_negation,
+      sym_aggregate,
+      sym_atom,
+      sym_comparison,
+  [500] = 10,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(67), 2,
       sym_variable,
       sym_symbol,
-    ACTIONS(33), 2,
+    ACTIONS(71), 1,
+      anon_sym_EQ,
+    ACTIONS(73), 1,
+      anon_sym_LBRACE,
+    STATE(60), 1,
+      sym__operand,
+    STATE(122), 1,
+      sym_pattern,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
       anon_sym_0,
       aux_sym_integer_token1,
-    STATE(21), 3,
+    STATE(57), 2,
       sym__ident,
+      sym_record,
+    STATE(25), 3,
       sym_integer,
       sym_tuple,
-  [84] = 6,
-    ACTIONS(35), 1,
+      sym_expression,
+  [537] = 9,
+    ACTIONS(45), 1,
       anon_sym_LPAREN,
-    STATE(45), 1,
+    ACTIONS(67), 2,
+      sym_variable,
+      sym_symbol,
+    ACTIONS(73), 1,
+      anon_sym_LBRACE,
+    STATE(60), 1,
+      sym__operand,
+    STATE(86), 1,
       sym_pattern,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(31), 2,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(57), 2,
+      sym__ident,
+      sym_record,
+    STATE(25), 3,
+      sym_integer,
+      sym_tuple,
+      sym_expression,
+  [571] = 9,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(67), 2,
       sym_variable,
       sym_symbol,
-    ACTIONS(33), 2,
+    ACTIONS(73), 1,
+      anon_sym_LBRACE,
+    STATE(60), 1,
+      sym__operand,
+    STATE(73), 1,
+      sym_pattern,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
       anon_sym_0,
       aux_sym_integer_token1,
-    STATE(21), 3,
+    STATE(57), 2,
       sym__ident,
+      sym_record,
+    STATE(25), 3,
       sym_integer,
       sym_tuple,
-  [108] = 6,
-    ACTIONS(35), 1,
+      sym_expression,
+  [605] = 9,
+    ACTIONS(45), 1,
       anon_sym_LPAREN,
-    STATE(51), 1,
+    ACTIONS(67), 2,
+      sym_variable,
+      sym_symbol,
+    ACTIONS(73), 1,
+      anon_sym_LBRACE,
+    STATE(60), 1,
+      sym__operand,
+    STATE(94), 1,
       sym_pattern,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(31), 2,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(57), 2,
+      sym__ident,
+      sym_record,
+    STATE(25), 3,
+      sym_integer,
+      sym_tuple,
+      sym_expression,
+  [639] = 9,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(67), 2,
       sym_variable,
       sym_symbol,
-    ACTIONS(33), 2,
+    ACTIONS(73), 1,
+      anon_sym_LBRACE,
+    STATE(60), 1,
+      sym__operand,
+    STATE(93), 1,
+      sym_pattern,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
       anon_sym_0,
       aux_sym_integer_token1,
-    STATE(21), 3,
+    STATE(57), 2,
       sym__ident,
+      sym_record,
+    STATE(25), 3,
       sym_integer,
       sym_tuple,
-  [132] = 8,
-    ACTIONS(37), 1,
+      sym_expression,
+  [673] = 4,
+    ACTIONS(75), 1,
+      anon_sym_EQ,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(80), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(78), 8,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [695] = 5,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(84), 2,
+      anon_sym_LT,
+      anon_sym_GT,
+    ACTIONS(86), 2,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+    ACTIONS(61), 3,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+    ACTIONS(82), 4,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT_EQ,
+      anon_sym_GT_EQ,
+  [719] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(78), 5,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+    ACTIONS(88), 5,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_if,
+      anon_sym_RBRACE,
+  [738] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(78), 5,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+    ACTIONS(90), 5,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_if,
+      anon_sym_RBRACE,
+  [757] = 5,
+    ACTIONS(45), 1,
       anon_sym_LPAREN,
-    ACTIONS(39), 1,
-      anon_sym_soft,
-    ACTIONS(41), 1,
-      sym_uniform,
-    ACTIONS(43), 1,
-      anon_sym_cardinality,
-    STATE(20), 1,
-      sym_captures,
-    STATE(39), 1,
-      sym_constraint_kind,
-    STATE(83), 1,
-      sym_cardinality,
+    ACTIONS(92), 2,
+      sym_variable,
+      sym_symbol,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(49), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+  [779] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [158] = 7,
-    ACTIONS(37), 1,
+    ACTIONS(94), 8,
+      ts_builtin_sym_end,
+      sym_symbol,
+      anon_sym_import,
+      anon_sym_type,
+      anon_sym_decide,
+      anon_sym_constrain,
+      sym_minimize,
+      sym_maximize,
+  [794] = 5,
+    ACTIONS(45), 1,
       anon_sym_LPAREN,
-    ACTIONS(41), 1,
-      sym_uniform,
-    ACTIONS(43), 1,
-      anon_sym_cardinality,
-    STATE(24), 1,
-      sym_captures,
-    STATE(44), 1,
-      sym_constraint_kind,
-    STATE(83), 1,
-      sym_cardinality,
+    ACTIONS(96), 2,
+      sym_variable,
+      sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [181] = 2,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(10), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+  [816] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(45), 5,
+    ACTIONS(98), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [193] = 2,
+      sym_minimize,
+      sym_maximize,
+  [831] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(47), 5,
+    ACTIONS(100), 8,
+      ts_builtin_sym_end,
       sym_symbol,
-      anon_sym_DOT,
-      anon_sym_COMMA,
-      anon_sym_RPAREN,
-      anon_sym_if,
-  [205] = 2,
+      anon_sym_import,
+      anon_sym_type,
+      anon_sym_decide,
+      anon_sym_constrain,
+      sym_minimize,
+      sym_maximize,
+  [846] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(49), 5,
+    ACTIONS(102), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [217] = 2,
+      sym_minimize,
+      sym_maximize,
+  [861] = 7,
+    ACTIONS(104), 1,
+      sym_count,
+    ACTIONS(106), 1,
+      anon_sym_sum,
+    ACTIONS(108), 1,
+      anon_sym_min,
+    ACTIONS(110), 1,
+      anon_sym_max,
+    ACTIONS(112), 1,
+      anon_sym_priority,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(51), 5,
+    STATE(4), 3,
+      sym_sum,
+      sym_min,
+      sym_max,
+  [886] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(114), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [229] = 2,
+      sym_minimize,
+      sym_maximize,
+  [901] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(53), 5,
+    ACTIONS(116), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [241] = 2,
+      sym_minimize,
+      sym_maximize,
+  [916] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(55), 5,
+    ACTIONS(118), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [253] = 2,
+      sym_minimize,
+      sym_maximize,
+  [931] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(57), 5,
+    ACTIONS(120), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [265] = 2,
+      sym_minimize,
+      sym_maximize,
+  [946] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(59), 5,
+    ACTIONS(122), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [277] = 2,
+      sym_minimize,
+      sym_maximize,
+  [961] = 5,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(124), 2,
+      sym_variable,
+      sym_symbol,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(9), 4,
+      sym_integer,
+      sym_tuple,
+      sym__operand,
+      sym_expression,
+  [983] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(61), 5,
+    ACTIONS(126), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [289] = 2,
+      sym_minimize,
+      sym_maximize,
+  [998] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(63), 5,
+    ACTIONS(128), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [301] = 2,
+      sym_minimize,
+      sym_maximize,
+  [1013] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(65), 5,
+    ACTIONS(130), 8,
       ts_builtin_sym_end,
       sym_symbol,
       anon_sym_import,
+      anon_sym_type,
       anon_sym_decide,
       anon_sym_constrain,
-  [313] = 5,
-    ACTIONS(41), 1,
+      sym_minimize,
+      sym_maximize,
+  [1028] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(132), 8,
+      ts_builtin_sym_end,
+      sym_symbol,
+      anon_sym_import,
+      anon_sym_type,
+      anon_sym_decide,
+      anon_sym_constrain,
+      sym_minimize,
+      sym_maximize,
+  [1043] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(134), 8,
+      ts_builtin_sym_end,
+      sym_symbol,
+      anon_sym_import,
+      anon_sym_type,
+      anon_sym_decide,
+      anon_sym_constrain,
+      sym_minimize,
+      sym_maximize,
+  [1058] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(136), 8,
+      ts_builtin_sym_end,
+      sym_symbol,
+      anon_sym_import,
+      anon_sym_type,
+      anon_sym_decide,
+      anon_sym_constrain,
+      sym_minimize,
+      sym_maximize,
+  [1073] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(138), 8,
+      ts_builtin_sym_end,
+      sym_symbol,
+      anon_sym_import,
+      anon_sym_type,
+      anon_sym_decide,
+      anon_sym_constrain,
+      sym_minimize,
+      sym_maximize,
+  [1088] = 8,
+    ACTIONS(140), 1,
+      anon_sym_LPAREN,
+    ACTIONS(142), 1,
+      anon_sym_soft,
+    ACTIONS(144), 1,
       sym_uniform,
-    ACTIONS(43), 1,
+    ACTIONS(146), 1,
       anon_sym_cardinality,
-    STATE(28), 1,
+    STATE(6), 1,
       sym_constraint_kind,
-    STATE(83), 1,
+    STATE(50), 1,
       sym_cardinality,
+    STATE(63), 1,
+      sym_captures,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1114] = 6,
+    ACTIONS(106), 1,
+      anon_sym_sum,
+    ACTIONS(108), 1,
+      anon_sym_min,
+    ACTIONS(110), 1,
+      anon_sym_max,
+    ACTIONS(148), 1,
+      sym_count,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    STATE(105), 3,
+      sym_sum,
+      sym_min,
+      sym_max,
+  [1136] = 6,
+    ACTIONS(106), 1,
+      anon_sym_sum,
+    ACTIONS(108), 1,
+      anon_sym_min,
+    ACTIONS(110), 1,
+      anon_sym_max,
+    ACTIONS(150), 1,
+      sym_count,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [330] = 2,
+    STATE(15), 3,
+      sym_sum,
+      sym_min,
+      sym_max,
+  [1158] = 4,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(67), 4,
+    ACTIONS(86), 2,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+    ACTIONS(152), 2,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+    ACTIONS(61), 3,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [1176] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(154), 2,
+      sym_variable,
+      anon_sym_not,
+    ACTIONS(156), 4,
+      sym_symbol,
+      anon_sym_0,
+      aux_sym_integer_token1,
+      anon_sym_LPAREN,
+  [1191] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(51), 2,
+      sym_variable,
+      anon_sym_not,
+    ACTIONS(49), 4,
+      sym_symbol,
+      anon_sym_0,
+      aux_sym_integer_token1,
+      anon_sym_LPAREN,
+  [1206] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(158), 2,
+      sym_variable,
+      anon_sym_not,
+    ACTIONS(160), 4,
+      sym_symbol,
+      anon_sym_0,
+      aux_sym_integer_token1,
+      anon_sym_LPAREN,
+  [1221] = 7,
+    ACTIONS(140), 1,
+      anon_sym_LPAREN,
+    ACTIONS(144), 1,
+      sym_uniform,
+    ACTIONS(146), 1,
+      anon_sym_cardinality,
+    STATE(14), 1,
+      sym_constraint_kind,
+    STATE(50), 1,
+      sym_cardinality,
+    STATE(62), 1,
+      sym_captures,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1244] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(162), 2,
+      sym_variable,
+      anon_sym_not,
+    ACTIONS(164), 4,
+      sym_symbol,
+      anon_sym_0,
+      aux_sym_integer_token1,
+      anon_sym_LPAREN,
+  [1259] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(166), 2,
+      sym_variable,
+      anon_sym_not,
+    ACTIONS(168), 4,
+      sym_symbol,
+      anon_sym_0,
+      aux_sym_integer_token1,
+      anon_sym_LPAREN,
+  [1274] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(170), 2,
+      sym_variable,
+      anon_sym_not,
+    ACTIONS(172), 4,
+      sym_symbol,
+      anon_sym_0,
+      aux_sym_integer_token1,
+      anon_sym_LPAREN,
+  [1289] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(90), 5,
       anon_sym_DOT,
       anon_sym_COMMA,
       anon_sym_RPAREN,
       anon_sym_if,
-  [341] = 4,
-    ACTIONS(69), 1,
+      anon_sym_RBRACE,
+  [1301] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(174), 5,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_if,
+      anon_sym_RBRACE,
+  [1313] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(176), 5,
+      anon_sym_DOT,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+      anon_sym_if,
+      anon_sym_RBRACE,
+  [1325] = 3,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(86), 2,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+    ACTIONS(61), 3,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+  [1339] = 4,
+    ACTIONS(178), 1,
       sym_only,
-    ACTIONS(71), 1,
+    ACTIONS(180), 1,
       anon_sym_at,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    STATE(34), 2,
-      sym_at_most,
-      sym_at_least,
-  [356] = 2,
+    STATE(64), 2,
+      sym_at_most,
+      sym_at_least,
+  [1354] = 5,
+    ACTIONS(144), 1,
+      sym_uniform,
+    ACTIONS(146), 1,
+      anon_sym_cardinality,
+    STATE(12), 1,
+      sym_constraint_kind,
+    STATE(50), 1,
+      sym_cardinality,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1371] = 5,
+    ACTIONS(144), 1,
+      sym_uniform,
+    ACTIONS(146), 1,
+      anon_sym_cardinality,
+    STATE(7), 1,
+      sym_constraint_kind,
+    STATE(50), 1,
+      sym_cardinality,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1388] = 3,
+    STATE(55), 1,
+      sym_integer,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(182), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+  [1400] = 4,
+    ACTIONS(184), 1,
+      anon_sym_DOT,
+    ACTIONS(186), 1,
+      anon_sym_COMMA,
+    STATE(67), 1,
+      aux_sym__rule_body_repeat1,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1414] = 4,
+    ACTIONS(188), 1,
+      anon_sym_COMMA,
+    ACTIONS(191), 1,
+      anon_sym_RPAREN,
+    STATE(66), 1,
+      aux_sym_tuple_repeat1,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1428] = 4,
+    ACTIONS(193), 1,
+      anon_sym_DOT,
+    ACTIONS(195), 1,
+      anon_sym_COMMA,
+    STATE(67), 1,
+      aux_sym__rule_body_repeat1,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1442] = 4,
+    ACTIONS(198), 1,
+      anon_sym_COMMA,
+    ACTIONS(200), 1,
+      anon_sym_RBRACE,
+    STATE(70), 1,
+      aux_sym_record_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(73), 4,
-      anon_sym_DOT,
+  [1456] = 4,
+    ACTIONS(202), 1,
       anon_sym_COMMA,
+    ACTIONS(204), 1,
       anon_sym_RPAREN,
-      anon_sym_if,
-  [367] = 5,
-    ACTIONS(41), 1,
-      sym_uniform,
-    ACTIONS(43), 1,
-      anon_sym_cardinality,
-    STATE(48), 1,
-      sym_constraint_kind,
     STATE(83), 1,
-      sym_cardinality,
+      aux_sym_captures_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [384] = 2,
+  [1470] = 4,
+    ACTIONS(206), 1,
+      anon_sym_COMMA,
+    ACTIONS(209), 1,
+      anon_sym_RBRACE,
+    STATE(70), 1,
+      aux_sym_record_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(75), 4,
-      anon_sym_DOT,
+  [1484] = 4,
+    ACTIONS(211), 1,
       anon_sym_COMMA,
+    ACTIONS(213), 1,
       anon_sym_RPAREN,
-      anon_sym_if,
-  [395] = 4,
-    ACTIONS(7), 1,
-      sym_symbol,
-    STATE(9), 1,
-      sym_rule,
-    STATE(55), 1,
-      sym_atom,
+    STATE(74), 1,
+      aux_sym_import_repeat2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [409] = 3,
-    STATE(72), 1,
+  [1498] = 3,
+    STATE(129), 1,
       sym_integer,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(33), 2,
+    ACTIONS(43), 2,
       anon_sym_0,
       aux_sym_integer_token1,
-  [421] = 4,
-    ACTIONS(7), 1,
-      sym_symbol,
-    STATE(29), 1,
-      sym_atom,
-    STATE(76), 1,
-      sym__rule_body,
+  [1510] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [435] = 4,
-    ACTIONS(77), 1,
+    ACTIONS(215), 3,
       anon_sym_DOT,
-    ACTIONS(79), 1,
       anon_sym_COMMA,
-    STATE(35), 1,
-      aux_sym__rule_body_repeat1,
+      anon_sym_if,
+  [1520] = 4,
+    ACTIONS(211), 1,
+      anon_sym_COMMA,
+    ACTIONS(217), 1,
+      anon_sym_RPAREN,
+    STATE(82), 1,
+      aux_sym_import_repeat2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [449] = 3,
-    STATE(37), 1,
-      sym__ident,
+  [1534] = 4,
+    ACTIONS(211), 1,
+      anon_sym_COMMA,
+    ACTIONS(219), 1,
+      anon_sym_RPAREN,
+    STATE(84), 1,
+      aux_sym_import_repeat2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(81), 2,
-      sym_variable,
-      sym_symbol,
-  [461] = 4,
-    ACTIONS(83), 1,
-      anon_sym_COMMA,
-    ACTIONS(85), 1,
-      anon_sym_RPAREN,
-    STATE(36), 1,
-      aux_sym_tuple_repeat1,
+  [1548] = 4,
+    ACTIONS(221), 1,
+      anon_sym_DOT,
+    ACTIONS(223), 1,
+      anon_sym_PIPE,
+    STATE(78), 1,
+      aux_sym_enum_declaration_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [475] = 3,
-    STATE(52), 1,
-      sym__ident,
+  [1562] = 4,
+    ACTIONS(225), 1,
+      anon_sym_DOT,
+    ACTIONS(227), 1,
+      anon_sym_PIPE,
+    STATE(77), 1,
+      aux_sym_enum_declaration_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(87), 2,
-      sym_variable,
-      sym_symbol,
-  [487] = 4,
-    ACTIONS(89), 1,
-      anon_sym_COMMA,
-    ACTIONS(91), 1,
-      anon_sym_RPAREN,
-    STATE(38), 1,
-      aux_sym_captures_repeat1,
+  [1576] = 4,
+    ACTIONS(223), 1,
+      anon_sym_PIPE,
+    ACTIONS(230), 1,
+      anon_sym_DOT,
+    STATE(77), 1,
+      aux_sym_enum_declaration_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [501] = 3,
-    STATE(74), 1,
-      sym_integer,
+  [1590] = 3,
+    STATE(69), 1,
+      sym__ident,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(33), 2,
-      anon_sym_0,
-      aux_sym_integer_token1,
-  [513] = 4,
-    ACTIONS(79), 1,
+    ACTIONS(232), 2,
+      sym_variable,
+      sym_symbol,
+  [1602] = 4,
+    ACTIONS(198), 1,
       anon_sym_COMMA,
-    ACTIONS(93), 1,
-      anon_sym_DOT,
-    STATE(40), 1,
-      aux_sym__rule_body_repeat1,
+    ACTIONS(234), 1,
+      anon_sym_RBRACE,
+    STATE(68), 1,
+      aux_sym_record_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [527] = 4,
-    ACTIONS(95), 1,
-      anon_sym_COMMA,
-    ACTIONS(98), 1,
-      anon_sym_RPAREN,
-    STATE(36), 1,
-      aux_sym_tuple_repeat1,
+  [1616] = 3,
+    STATE(97), 1,
+      sym__ident,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [541] = 4,
-    ACTIONS(89), 1,
+    ACTIONS(236), 2,
+      sym_variable,
+      sym_symbol,
+  [1628] = 4,
+    ACTIONS(238), 1,
       anon_sym_COMMA,
-    ACTIONS(100), 1,
+    ACTIONS(241), 1,
       anon_sym_RPAREN,
-    STATE(33), 1,
-      aux_sym_captures_repeat1,
+    STATE(82), 1,
+      aux_sym_import_repeat2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [555] = 4,
-    ACTIONS(102), 1,
+  [1642] = 4,
+    ACTIONS(202), 1,
       anon_sym_COMMA,
-    ACTIONS(105), 1,
+    ACTIONS(243), 1,
       anon_sym_RPAREN,
-    STATE(38), 1,
+    STATE(85), 1,
       aux_sym_captures_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [569] = 4,
-    ACTIONS(7), 1,
-      sym_symbol,
-    STATE(29), 1,
-      sym_atom,
-    STATE(73), 1,
-      sym__rule_body,
-    ACTIONS(3), 2,
-      sym__whitespace,
-      sym_comment,
-  [583] = 4,
-    ACTIONS(107), 1,
-      anon_sym_DOT,
-    ACTIONS(109), 1,
+  [1656] = 4,
+    ACTIONS(211), 1,
       anon_sym_COMMA,
-    STATE(40), 1,
-      aux_sym__rule_body_repeat1,
+    ACTIONS(245), 1,
+      anon_sym_RPAREN,
+    STATE(82), 1,
+      aux_sym_import_repeat2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [597] = 4,
-    ACTIONS(112), 1,
+  [1670] = 4,
+    ACTIONS(247), 1,
       anon_sym_COMMA,
-    ACTIONS(114), 1,
+    ACTIONS(250), 1,
       anon_sym_RPAREN,
-    STATE(46), 1,
-      aux_sym_import_repeat2,
+    STATE(85), 1,
+      aux_sym_captures_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [611] = 4,
-    ACTIONS(112), 1,
+  [1684] = 4,
+    ACTIONS(252), 1,
       anon_sym_COMMA,
-    ACTIONS(114), 1,
+    ACTIONS(254), 1,
       anon_sym_RPAREN,
-    STATE(47), 1,
-      aux_sym_import_repeat2,
+    STATE(90), 1,
+      aux_sym_tuple_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [625] = 4,
-    ACTIONS(7), 1,
-      sym_symbol,
-    STATE(29), 1,
-      sym_atom,
-    STATE(67), 1,
-      sym__rule_body,
+  [1698] = 3,
+    STATE(141), 1,
+      sym_integer,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [639] = 4,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+  [1710] = 4,
     ACTIONS(7), 1,
       sym_symbol,
-    STATE(29), 1,
+    STATE(34), 1,
+      sym_rule,
+    STATE(96), 1,
       sym_atom,
-    STATE(79), 1,
-      sym__rule_body,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [653] = 2,
+  [1724] = 4,
+    ACTIONS(186), 1,
+      anon_sym_COMMA,
+    ACTIONS(256), 1,
+      anon_sym_DOT,
+    STATE(65), 1,
+      aux_sym__rule_body_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(116), 3,
-      anon_sym_DOT,
-      anon_sym_COMMA,
-      anon_sym_if,
-  [663] = 4,
-    ACTIONS(118), 1,
+  [1738] = 4,
+    ACTIONS(252), 1,
       anon_sym_COMMA,
-    ACTIONS(121), 1,
+    ACTIONS(258), 1,
       anon_sym_RPAREN,
-    STATE(46), 1,
-      aux_sym_import_repeat2,
+    STATE(66), 1,
+      aux_sym_tuple_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [677] = 4,
-    ACTIONS(112), 1,
-      anon_sym_COMMA,
-    ACTIONS(123), 1,
-      anon_sym_RPAREN,
-    STATE(46), 1,
-      aux_sym_import_repeat2,
+  [1752] = 3,
+    ACTIONS(260), 1,
+      anon_sym_most,
+    ACTIONS(262), 1,
+      anon_sym_least,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [691] = 4,
-    ACTIONS(7), 1,
-      sym_symbol,
-    STATE(29), 1,
-      sym_atom,
-    STATE(81), 1,
-      sym__rule_body,
+  [1763] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [705] = 4,
-    ACTIONS(83), 1,
+    ACTIONS(264), 2,
+      anon_sym_DOT,
       anon_sym_COMMA,
-    ACTIONS(125), 1,
-      anon_sym_RPAREN,
-    STATE(31), 1,
-      aux_sym_tuple_repeat1,
+  [1772] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [719] = 4,
-    ACTIONS(112), 1,
+    ACTIONS(191), 2,
       anon_sym_COMMA,
-    ACTIONS(127), 1,
       anon_sym_RPAREN,
-    STATE(41), 1,
-      aux_sym_import_repeat2,
+  [1781] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [733] = 2,
+    ACTIONS(266), 2,
+      anon_sym_COMMA,
+      anon_sym_RBRACE,
+  [1790] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(98), 2,
+    ACTIONS(209), 2,
       anon_sym_COMMA,
-      anon_sym_RPAREN,
-  [742] = 2,
+      anon_sym_RBRACE,
+  [1799] = 3,
+    ACTIONS(268), 1,
+      anon_sym_DOT,
+    ACTIONS(270), 1,
+      anon_sym_if,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1810] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(105), 2,
+    ACTIONS(250), 2,
       anon_sym_COMMA,
       anon_sym_RPAREN,
-  [751] = 2,
+  [1819] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(129), 2,
+    ACTIONS(272), 2,
       sym_uniform,
       anon_sym_cardinality,
-  [760] = 3,
-    ACTIONS(131), 1,
+  [1828] = 3,
+    ACTIONS(274), 1,
       sym_symbol,
-    ACTIONS(133), 1,
-      anon_sym_LPAREN,
+    STATE(95), 1,
+      sym_field,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [771] = 3,
-    ACTIONS(135), 1,
+  [1839] = 3,
+    ACTIONS(276), 1,
       anon_sym_DOT,
-    ACTIONS(137), 1,
-      anon_sym_if,
+    STATE(104), 1,
+      aux_sym_import_repeat1,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1850] = 3,
+    ACTIONS(278), 1,
+      sym_symbol,
+    ACTIONS(280), 1,
+      anon_sym_LPAREN,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [782] = 2,
+  [1861] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(139), 2,
+    ACTIONS(282), 2,
       anon_sym_0,
       aux_sym_integer_token1,
-  [791] = 2,
+  [1870] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(141), 2,
+    ACTIONS(284), 2,
       anon_sym_0,
       aux_sym_integer_token1,
-  [800] = 3,
-    ACTIONS(143), 1,
+  [1879] = 3,
+    ACTIONS(286), 1,
       anon_sym_DOT,
-    STATE(58), 1,
+    STATE(108), 1,
       aux_sym_import_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [811] = 2,
+  [1890] = 3,
+    ACTIONS(7), 1,
+      sym_symbol,
+    STATE(112), 1,
+      sym_atom,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1901] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(107), 2,
+    ACTIONS(193), 2,
       anon_sym_DOT,
       anon_sym_COMMA,
-  [820] = 2,
+  [1910] = 3,
+    ACTIONS(278), 1,
+      sym_symbol,
+    ACTIONS(288), 1,
+      anon_sym_LPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1921] = 3,
+    ACTIONS(290), 1,
+      anon_sym_DOT,
+    STATE(108), 1,
+      aux_sym_import_repeat1,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1932] = 3,
+    ACTIONS(274), 1,
+      sym_symbol,
+    STATE(80), 1,
+      sym_field,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [1943] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(146), 2,
+    ACTIONS(293), 2,
       sym_uniform,
       anon_sym_cardinality,
-  [829] = 2,
+  [1952] = 2,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+    ACTIONS(295), 2,
+      anon_sym_DOT,
+      anon_sym_PIPE,
+  [1961] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-    ACTIONS(121), 2,
+    ACTIONS(297), 2,
+      anon_sym_DOT,
       anon_sym_COMMA,
-      anon_sym_RPAREN,
-  [838] = 3,
-    ACTIONS(148), 1,
-      anon_sym_most,
-    ACTIONS(150), 1,
-      anon_sym_least,
+  [1970] = 2,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [849] = 3,
+    ACTIONS(299), 2,
+      anon_sym_COMMA,
+      anon_sym_RPAREN,
+  [1979] = 3,
     ACTIONS(7), 1,
       sym_symbol,
-    STATE(59), 1,
+    STATE(92), 1,
       sym_atom,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [860] = 3,
-    ACTIONS(152), 1,
+  [1990] = 2,
+    ACTIONS(301), 1,
       anon_sym_DOT,
-    STATE(66), 1,
-      aux_sym_import_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [871] = 3,
-    ACTIONS(131), 1,
+  [1998] = 2,
+    ACTIONS(303), 1,
+      sym_variable,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2006] = 2,
+    ACTIONS(305), 1,
       sym_symbol,
-    ACTIONS(154), 1,
-      anon_sym_LPAREN,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [882] = 3,
-    ACTIONS(156), 1,
+  [2014] = 2,
+    ACTIONS(307), 1,
       anon_sym_DOT,
-    STATE(58), 1,
-      aux_sym_import_repeat1,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [893] = 2,
-    ACTIONS(158), 1,
+  [2022] = 2,
+    ACTIONS(309), 1,
+      sym_symbol,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2030] = 2,
+    ACTIONS(311), 1,
       anon_sym_DOT,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [901] = 2,
-    ACTIONS(160), 1,
+  [2038] = 2,
+    ACTIONS(313), 1,
+      ts_builtin_sym_end,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2046] = 2,
+    ACTIONS(315), 1,
+      anon_sym_DOT,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2054] = 2,
+    ACTIONS(317), 1,
       sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [909] = 2,
-    ACTIONS(162), 1,
+  [2062] = 2,
+    ACTIONS(319), 1,
+      anon_sym_COLON,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2070] = 2,
+    ACTIONS(321), 1,
+      anon_sym_LPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2078] = 2,
+    ACTIONS(323), 1,
       sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [917] = 2,
-    ACTIONS(164), 1,
+  [2086] = 2,
+    ACTIONS(278), 1,
       sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [925] = 2,
-    ACTIONS(166), 1,
-      ts_builtin_sym_end,
+  [2094] = 2,
+    ACTIONS(325), 1,
+      anon_sym_LPAREN,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [933] = 2,
-    ACTIONS(168), 1,
+  [2102] = 2,
+    ACTIONS(327), 1,
       anon_sym_RPAREN,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [941] = 2,
-    ACTIONS(170), 1,
+  [2110] = 2,
+    ACTIONS(329), 1,
+      anon_sym_LPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2118] = 2,
+    ACTIONS(331), 1,
+      anon_sym_LPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2126] = 2,
+    ACTIONS(333), 1,
+      anon_sym_to,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2134] = 2,
+    ACTIONS(335), 1,
       anon_sym_DOT,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [949] = 2,
-    ACTIONS(172), 1,
+  [2142] = 2,
+    ACTIONS(337), 1,
       sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [957] = 2,
-    ACTIONS(131), 1,
-      sym_symbol,
+  [2150] = 2,
+    ACTIONS(339), 1,
+      sym_variable,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2158] = 2,
+    ACTIONS(341), 1,
+      anon_sym_LPAREN,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [965] = 2,
-    ACTIONS(174), 1,
+  [2166] = 2,
+    ACTIONS(343), 1,
       anon_sym_DOT,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [973] = 2,
-    ACTIONS(176), 1,
-      anon_sym_LPAREN,
+  [2174] = 2,
+    ACTIONS(345), 1,
+      sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [981] = 2,
-    ACTIONS(178), 1,
-      anon_sym_to,
+  [2182] = 2,
+    ACTIONS(347), 1,
+      sym_symbol,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [989] = 2,
-    ACTIONS(180), 1,
+  [2190] = 2,
+    ACTIONS(349), 1,
       anon_sym_DOT,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [997] = 2,
-    ACTIONS(182), 1,
+  [2198] = 2,
+    ACTIONS(351), 1,
+      anon_sym_RPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2206] = 2,
+    ACTIONS(353), 1,
       anon_sym_DOT,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [1005] = 2,
-    ACTIONS(184), 1,
+  [2214] = 2,
+    ACTIONS(355), 1,
       anon_sym_DOT,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [1013] = 2,
-    ACTIONS(186), 1,
-      sym_symbol,
+  [2222] = 2,
+    ACTIONS(357), 1,
+      sym_variable,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
-  [1021] = 2,
-    ACTIONS(188), 1,
+  [2230] = 2,
+    ACTIONS(359), 1,
+      anon_sym_RPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2238] = 2,
+    ACTIONS(361), 1,
+      anon_sym_RPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2246] = 2,
+    ACTIONS(363), 1,
+      anon_sym_RPAREN,
+    ACTIONS(3), 2,
+      sym__whitespace,
+      sym_comment,
+  [2254] = 11,
+    ACTIONS(78), 11,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_PLUS,
+      anon_sym_DASH,
+      anon_sym_STAR,
+      anon_sym_SLASH,
+      anon_sym_PERCENT,
+    ACTIONS(45), 1,
+      anon_sym_LPAREN,
+    ACTIONS(67), 1,
+      sym_variable,
+    ACTIONS(69), 1,
       sym_symbol,
+    ACTIONS(73), 1,
+      anon_sym_LBRACE,
+    STATE(60), 1,
+      sym__operand,
+    STATE(73), 1,
+      sym_pattern,
     ACTIONS(3), 2,
       sym__whitespace,
       sym_comment,
+    ACTIONS(43), 2,
+      anon_sym_0,
+      aux_sym_integer_token1,
+    STATE(57), 2,
+      sym__ident,
+      sym_record,
+    STATE(25), 3,
+      sym_integer,
+      sym_tuple,
+      sym_expression,
 };
 
 static const uint32_t ts_small_parse_table_map[] = {
   [SMALL_STATE(2)] = 0,
-  [SMALL_STATE(3)] = 30,
-  [SMALL_STATE(4)] = 60,
-  [SMALL_STATE(5)] = 84,
-  [SMALL_STATE(6)] = 108,
-  [SMALL_STATE(7)] = 132,
-  [SMALL_STATE(8)] = 158,
-  [SMALL_STATE(9)] = 181,
-  [SMALL_STATE(10)] = 193,
-  [SMALL_STATE(11)] = 205,
-  [SMALL_STATE(12)] = 217,
-  [SMALL_STATE(13)] = 229,
-  [SMALL_STATE(14)] = 241,
-  [SMALL_STATE(15)] = 253,
-  [SMALL_STATE(16)] = 265,
-  [SMALL_STATE(17)] = 277,
-  [SMALL_STATE(18)] = 289,
-  [SMALL_STATE(19)] = 301,
-  [SMALL_STATE(20)] = 313,
-  [SMALL_STATE(21)] = 330,
-  [SMALL_STATE(22)] = 341,
-  [SMALL_STATE(23)] = 356,
-  [SMALL_STATE(24)] = 367,
-  [SMALL_STATE(25)] = 384,
-  [SMALL_STATE(26)] = 395,
-  [SMALL_STATE(27)] = 409,
-  [SMALL_STATE(28)] = 421,
-  [SMALL_STATE(29)] = 435,
-  [SMALL_STATE(30)] = 449,
-  [SMALL_STATE(31)] = 461,
-  [SMALL_STATE(32)] = 475,
-  [SMALL_STATE(33)] = 487,
-  [SMALL_STATE(34)] = 501,
-  [SMALL_STATE(35)] = 513,
-  [SMALL_STATE(36)] = 527,
-  [SMALL_STATE(37)] = 541,
-  [SMALL_STATE(38)] = 555,
-  [SMALL_STATE(39)] = 569,
-  [SMALL_STATE(40)] = 583,
-  [SMALL_STATE(41)] = 597,
-  [SMALL_STATE(42)] = 611,
-  [SMALL_STATE(43)] = 625,
-  [SMALL_STATE(44)] = 639,
-  [SMALL_STATE(45)] = 653,
-  [SMALL_STATE(46)] = 663,
-  [SMALL_STATE(47)] = 677,
-  [SMALL_STATE(48)] = 691,
-  [SMALL_STATE(49)] = 705,
-  [SMALL_STATE(50)] = 719,
-  [SMALL_STATE(51)] = 733,
-  [SMALL_STATE(52)] = 742,
-  [SMALL_STATE(53)] = 751,
-  [SMALL_STATE(54)] = 760,
-  [SMALL_STATE(55)] = 771,
-  [SMALL_STATE(56)] = 782,
-  [SMALL_STATE(57)] = 791,
-  [SMALL_STATE(58)] = 800,
-  [SMALL_STATE(59)] = 811,
-  [SMALL_STATE(60)] = 820,
-  [SMALL_STATE(61)] = 829,
-  [SMALL_STATE(62)] = 838,
-  [SMALL_STATE(63)] = 849,
-  [SMALL_STATE(64)] = 860,
-  [SMALL_STATE(65)] = 871,
-  [SMALL_STATE(66)] = 882,
-  [SMALL_STATE(67)] = 893,
-  [SMALL_STATE(68)] = 901,
-  [SMALL_STATE(69)] = 909,
-  [SMALL_STATE(70)] = 917,
-  [SMALL_STATE(71)] = 925,
-  [SMALL_STATE(72)] = 933,
-  [SMALL_STATE(73)] = 941,
-  [SMALL_STATE(74)] = 949,
-  [SMALL_STATE(75)] = 957,
-  [SMALL_STATE(76)] = 965,
-  [SMALL_STATE(77)] = 973,
-  [SMALL_STATE(78)] = 981,
-  [SMALL_STATE(79)] = 989,
-  [SMALL_STATE(80)] = 997,
-  [SMALL_STATE(81)] = 1005,
-  [SMALL_STATE(82)] = 1013,
-  [SMALL_STATE(83)] = 1021,
+  [SMALL_STATE(3)] = 40,
+  [SMALL_STATE(4)] = 80,
+  [SMALL_STATE(5)] = 117,
+  [SMALL_STATE(6)] = 142,
+  [SMALL_STATE(7)] = 179,
+  [SMALL_STATE(8)] = 216,
+  [SMALL_STATE(9)] = 241,
+  [SMALL_STATE(10)] = 268,
+  [SMALL_STATE(11)] = 293,
+  [SMALL_STATE(12)] = 330,
+  [SMALL_STATE(13)] = 367,
+  [SMALL_STATE(14)] = 392,
+  [SMALL_STATE(15)] = 429,
+  [SMALL_STATE(16)] = 466,
+  [SMALL_STATE(17)] = 500,
+  [SMALL_STATE(18)] = 537,
+  [SMALL_STATE(19)] = 571,
+  [SMALL_STATE(20)] = 605,
+  [SMALL_STATE(21)] = 639,
+  [SMALL_STATE(22)] = 673,
+  [SMALL_STATE(23)] = 695,
+  [SMALL_STATE(24)] = 719,
+  [SMALL_STATE(25)] = 738,
+  [SMALL_STATE(26)] = 757,
+  [SMALL_STATE(27)] = 779,
+  [SMALL_STATE(28)] = 794,
+  [SMALL_STATE(29)] = 816,
+  [SMALL_STATE(30)] = 831,
+  [SMALL_STATE(31)] = 846,
+  [SMALL_STATE(32)] = 861,
+  [SMALL_STATE(33)] = 886,
+  [SMALL_STATE(34)] = 901,
+  [SMALL_STATE(35)] = 916,
+  [SMALL_STATE(36)] = 931,
+  [SMALL_STATE(37)] = 946,
+  [SMALL_STATE(38)] = 961,
+  [SMALL_STATE(39)] = 983,
+  [SMALL_STATE(40)] = 998,
+  [SMALL_STATE(41)] = 1013,
+  [SMALL_STATE(42)] = 1028,
+  [SMALL_STATE(43)] = 1043,
+  [SMALL_STATE(44)] = 1058,
+  [SMALL_STATE(45)] = 1073,
+  [SMALL_STATE(46)] = 1088,
+  [SMALL_STATE(47)] = 1114,
+  [SMALL_STATE(48)] = 1136,
+  [SMALL_STATE(49)] = 1158,
+  [SMALL_STATE(50)] = 1176,
+  [SMALL_STATE(51)] = 1191,
+  [SMALL_STATE(52)] = 1206,
+  [SMALL_STATE(53)] = 1221,
+  [SMALL_STATE(54)] = 1244,
+  [SMALL_STATE(55)] = 1259,
+  [SMALL_STATE(56)] = 1274,
+  [SMALL_STATE(57)] = 1289,
+  [SMALL_STATE(58)] = 1301,
+  [SMALL_STATE(59)] = 1313,
+  [SMALL_STATE(60)] = 1325,
+  [SMALL_STATE(61)] = 1339,
+  [SMALL_STATE(62)] = 1354,
+  [SMALL_STATE(63)] = 1371,
+  [SMALL_STATE(64)] = 1388,
+  [SMALL_STATE(65)] = 1400,
+  [SMALL_STATE(66)] = 1414,
+  [SMALL_STATE(67)] = 1428,
+  [SMALL_STATE(68)] = 1442,
+  [SMALL_STATE(69)] = 1456,
+  [SMALL_STATE(70)] = 1470,
+  [SMALL_STATE(71)] = 1484,
+  [SMALL_STATE(72)] = 1498,
+  [SMALL_STATE(73)] = 1510,
+  [SMALL_STATE(74)] = 1520,
+  [SMALL_STATE(75)] = 1534,
+  [SMALL_STATE(76)] = 1548,
+  [SMALL_STATE(77)] = 1562,
+  [SMALL_STATE(78)] = 1576,
+  [SMALL_STATE(79)] = 1590,
+  [SMALL_STATE(80)] = 1602,
+  [SMALL_STATE(81)] = 1616,
+  [SMALL_STATE(82)] = 1628,
+  [SMALL_STATE(83)] = 1642,
+  [SMALL_STATE(84)] = 1656,
+  [SMALL_STATE(85)] = 1670,
+  [SMALL_STATE(86)] = 1684,
+  [SMALL_STATE(87)] = 1698,
+  [SMALL_STATE(88)] = 1710,
+  [SMALL_STATE(89)] = 1724,
+  [SMALL_STATE(90)] = 1738,
+  [SMALL_STATE(91)] = 1752,
+  [SMALL_STATE(92)] = 1763,
+  [SMALL_STATE(93)] = 1772,
+  [SMALL_STATE(94)] = 1781,
+  [SMALL_STATE(95)] = 1790,
+  [SMALL_STATE(96)] = 1799,
+  [SMALL_STATE(97)] = 1810,
+  [SMALL_STATE(98)] = 1819,
+  [SMALL_STATE(99)] = 1828,
+  [SMALL_STATE(100)] = 1839,
+  [SMALL_STATE(101)] = 1850,
+  [SMALL_STATE(102)] = 1861,
+  [SMALL_STATE(103)] = 1870,
+  [SMALL_STATE(104)] = 1879,
+  [SMALL_STATE(105)] = 1890,
+  [SMALL_STATE(106)] = 1901,
+  [SMALL_STATE(107)] = 1910,
+  [SMALL_STATE(108)] = 1921,
+  [SMALL_STATE(109)] = 1932,
+  [SMALL_STATE(110)] = 1943,
+  [SMALL_STATE(111)] = 1952,
+  [SMALL_STATE(112)] = 1961,
+  [SMALL_STATE(113)] = 1970,
+  [SMALL_STATE(114)] = 1979,
+  [SMALL_STATE(115)] = 1990,
+  [SMALL_STATE(116)] = 1998,
+  [SMALL_STATE(117)] = 2006,
+  [SMALL_STATE(118)] = 2014,
+  [SMALL_STATE(119)] = 2022,
+  [SMALL_STATE(120)] = 2030,
+  [SMALL_STATE(121)] = 2038,
+  [SMALL_STATE(122)] = 2046,
+  [SMALL_STATE(123)] = 2054,
+  [SMALL_STATE(124)] = 2062,
+  [SMALL_STATE(125)] = 2070,
+  [SMALL_STATE(126)] = 2078,
+  [SMALL_STATE(127)] = 2086,
+  [SMALL_STATE(128)] = 2094,
+  [SMALL_STATE(129)] = 2102,
+  [SMALL_STATE(130)] = 2110,
+  [SMALL_STATE(131)] = 2118,
+  [SMALL_STATE(132)] = 2126,
+  [SMALL_STATE(133)] = 2134,
+  [SMALL_STATE(134)] = 2142,
+  [SMALL_STATE(135)] = 2150,
+  [SMALL_STATE(136)] = 2158,
+  [SMALL_STATE(137)] = 2166,
+  [SMALL_STATE(138)] = 2174,
+  [SMALL_STATE(139)] = 2182,
+  [SMALL_STATE(140)] = 2190,
+  [SMALL_STATE(141)] = 2198,
+  [SMALL_STATE(142)] = 2206,
+  [SMALL_STATE(143)] = 2214,
+  [SMALL_STATE(144)] = 2222,
+  [SMALL_STATE(145)] = 2230,
+  [SMALL_STATE(146)] = 2238,
+  [SMALL_STATE(147)] = 2246,
+  [SMALL_STATE(148)] = 2254,
 };
 
 static const TSParseActionEntry ts_parse_actions[] = {
@@ -1770,93 +3714,179 @@ static const TSParseActionEntry ts_parse_actions[] = {
   [1] = {.entry = {.count = 1, .reusable = false}}, RECOVER(),
   [3] = {.entry = {.count = 1, .reusable = true}}, SHIFT_EXTRA(),
   [5] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_file, 0, 0, 0),
-  [7] = {.entry = {.count = 1, .reusable = true}}, SHIFT(5),
-  [9] = {.entry = {.count = 1, .reusable = true}}, SHIFT(69),
-  [11] = {.entry = {.count = 1, .reusable = true}}, SHIFT(26),
-  [13] = {.entry = {.count = 1, .reusable = true}}, SHIFT(7),
-  [15] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0),
-  [17] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(5),
-  [20] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(69),
-  [23] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(26),
-  [26] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(7),
-  [29] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_file, 1, 0, 0),
-  [31] = {.entry = {.count = 1, .reusable = true}}, SHIFT(21),
-  [33] = {.entry = {.count = 1, .reusable = true}}, SHIFT(10),
-  [35] = {.entry = {.count = 1, .reusable = true}}, SHIFT(4),
-  [37] = {.entry = {.count = 1, .reusable = true}}, SHIFT(30),
-  [39] = {.entry = {.count = 1, .reusable = true}}, SHIFT(77),
-  [41] = {.entry = {.count = 1, .reusable = true}}, SHIFT(83),
-  [43] = {.entry = {.count = 1, .reusable = true}}, SHIFT(78),
-  [45] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_decision, 2, 0, 0),
-  [47] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_integer, 1, 0, 0),
-  [49] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_rule, 2, 0, 2),
-  [51] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 8, 0, 0),
-  [53] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_rule, 4, 0, 4),
-  [55] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 8, 0, 6),
-  [57] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 4, 0, 3),
-  [59] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 5, 0, 5),
-  [61] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 6, 0, 0),
-  [63] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 9, 0, 7),
-  [65] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 7, 0, 0),
-  [67] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_pattern, 1, 0, 0),
-  [69] = {.entry = {.count = 1, .reusable = true}}, SHIFT(34),
-  [71] = {.entry = {.count = 1, .reusable = true}}, SHIFT(62),
-  [73] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_tuple, 3, 0, 0),
-  [75] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_tuple, 4, 0, 0),
-  [77] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__rule_body, 1, 0, 0),
-  [79] = {.entry = {.count = 1, .reusable = true}}, SHIFT(63),
-  [81] = {.entry = {.count = 1, .reusable = true}}, SHIFT(37),
-  [83] = {.entry = {.count = 1, .reusable = true}}, SHIFT(6),
-  [85] = {.entry = {.count = 1, .reusable = true}}, SHIFT(25),
-  [87] = {.entry = {.count = 1, .reusable = true}}, SHIFT(52),
-  [89] = {.entry = {.count = 1, .reusable = true}}, SHIFT(32),
-  [91] = {.entry = {.count = 1, .reusable = true}}, SHIFT(53),
-  [93] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__rule_body, 2, 0, 0),
-  [95] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_tuple_repeat1, 2, 0, 0), SHIFT_REPEAT(6),
-  [98] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_tuple_repeat1, 2, 0, 0),
-  [100] = {.entry = {.count = 1, .reusable = true}}, SHIFT(60),
-  [102] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_captures_repeat1, 2, 0, 0), SHIFT_REPEAT(32),
-  [105] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_captures_repeat1, 2, 0, 0),
-  [107] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym__rule_body_repeat1, 2, 0, 0),
-  [109] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym__rule_body_repeat1, 2, 0, 0), SHIFT_REPEAT(63),
-  [112] = {.entry = {.count = 1, .reusable = true}}, SHIFT(68),
-  [114] = {.entry = {.count = 1, .reusable = true}}, SHIFT(19),
-  [116] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_atom, 2, 0, 1),
-  [118] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_import_repeat2, 2, 0, 0), SHIFT_REPEAT(68),
-  [121] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_import_repeat2, 2, 0, 0),
-  [123] = {.entry = {.count = 1, .reusable = true}}, SHIFT(12),
-  [125] = {.entry = {.count = 1, .reusable = true}}, SHIFT(23),
-  [127] = {.entry = {.count = 1, .reusable = true}}, SHIFT(17),
-  [129] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_captures, 4, 0, 0),
-  [131] = {.entry = {.count = 1, .reusable = true}}, SHIFT(80),
-  [133] = {.entry = {.count = 1, .reusable = true}}, SHIFT(70),
-  [135] = {.entry = {.count = 1, .reusable = true}}, SHIFT(11),
-  [137] = {.entry = {.count = 1, .reusable = true}}, SHIFT(43),
-  [139] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_at_most, 2, 0, 0),
-  [141] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_at_least, 2, 0, 0),
-  [143] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_import_repeat1, 2, 0, 0), SHIFT_REPEAT(75),
-  [146] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_captures, 3, 0, 0),
-  [148] = {.entry = {.count = 1, .reusable = true}}, SHIFT(56),
-  [150] = {.entry = {.count = 1, .reusable = true}}, SHIFT(57),
-  [152] = {.entry = {.count = 1, .reusable = true}}, SHIFT(65),
-  [154] = {.entry = {.count = 1, .reusable = true}}, SHIFT(82),
-  [156] = {.entry = {.count = 1, .reusable = true}}, SHIFT(54),
-  [158] = {.entry = {.count = 1, .reusable = true}}, SHIFT(13),
-  [160] = {.entry = {.count = 1, .reusable = true}}, SHIFT(61),
-  [162] = {.entry = {.count = 1, .reusable = true}}, SHIFT(64),
-  [164] = {.entry = {.count = 1, .reusable = true}}, SHIFT(42),
-  [166] = {.entry = {.count = 1, .reusable = true}},  ACCEPT_INPUT(),
-  [168] = {.entry = {.count = 1, .reusable = true}}, SHIFT(8),
-  [170] = {.entry = {.count = 1, .reusable = true}}, SHIFT(15),
-  [172] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cardinality, 4, 0, 0),
-  [174] = {.entry = {.count = 1, .reusable = true}}, SHIFT(16),
-  [176] = {.entry = {.count = 1, .reusable = true}}, SHIFT(27),
-  [178] = {.entry = {.count = 1, .reusable = true}}, SHIFT(22),
-  [180] = {.entry = {.count = 1, .reusable = true}}, SHIFT(14),
-  [182] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_import_repeat1, 2, 0, 0),
-  [184] = {.entry = {.count = 1, .reusable = true}}, SHIFT(18),
-  [186] = {.entry = {.count = 1, .reusable = true}}, SHIFT(50),
-  [188] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint_kind, 1, 0, 0),
+  [7] = {.entry = {.count = 1, .reusable = true}}, SHIFT(19),
+  [9] = {.entry = {.count = 1, .reusable = true}}, SHIFT(139),
+  [11] = {.entry = {.count = 1, .reusable = true}}, SHIFT(138),
+  [13] = {.entry = {.count = 1, .reusable = true}}, SHIFT(88),
+  [15] = {.entry = {.count = 1, .reusable = true}}, SHIFT(46),
+  [17] = {.entry = {.count = 1, .reusable = true}}, SHIFT(32),
+  [19] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_file, 1, 0, 0),
+  [21] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0),
+  [23] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(19),
+  [26] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(139),
+  [29] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(138),
+  [32] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(88),
+  [35] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(46),
+  [38] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_file_repeat1, 2, 0, 0), SHIFT_REPEAT(32),
+  [41] = {.entry = {.count = 1, .reusable = false}}, SHIFT(22),
+  [43] = {.entry = {.count = 1, .reusable = true}}, SHIFT(5),
+  [45] = {.entry = {.count = 1, .reusable = true}}, SHIFT(18),
+  [47] = {.entry = {.count = 1, .reusable = false}}, SHIFT(114),
+  [49] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_integer, 1, 0, 0),
+  [51] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_integer, 1, 0, 0),
+  [53] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_tuple, 3, 0, 0),
+  [55] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_tuple, 3, 0, 0),
+  [57] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_expression, 3, 0, 3),
+  [59] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_expression, 3, 0, 3),
+  [61] = {.entry = {.count = 1, .reusable = true}}, SHIFT(28),
+  [63] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_tuple, 4, 0, 0),
+  [65] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_tuple, 4, 0, 0),
+  [67] = {.entry = {.count = 1, .reusable = true}}, SHIFT(24),
+  [69] = {.entry = {.count = 1, .reusable = true}}, SHIFT(57),
+  [71] = {.entry = {.count = 1, .reusable = true}}, SHIFT(117),
+  [73] = {.entry = {.count = 1, .reusable = true}}, SHIFT(109),
+  [75] = {.entry = {.count = 2, .reusable = true}}, REDUCE(sym__operand, 1, 0, 0), SHIFT(47),
+  [78] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__operand, 1, 0, 0),
+  [80] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym__operand, 1, 0, 0),
+  [82] = {.entry = {.count = 1, .reusable = true}}, SHIFT(26),
+  [84] = {.entry = {.count = 1, .reusable = false}}, SHIFT(26),
+  [86] = {.entry = {.count = 1, .reusable = true}}, SHIFT(38),
+  [88] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__ident, 1, 0, 0),
+  [90] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_pattern, 1, 0, 0),
+  [92] = {.entry = {.count = 1, .reusable = true}}, SHIFT(49),
+  [94] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_rule, 2, 0, 2),
+  [96] = {.entry = {.count = 1, .reusable = true}}, SHIFT(10),
+  [98] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_type_declaration, 4, 0, 6),
+  [100] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_rule, 4, 0, 10),
+  [102] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 4, 0, 8),
+  [104] = {.entry = {.count = 1, .reusable = true}}, SHIFT(4),
+  [106] = {.entry = {.count = 1, .reusable = true}}, SHIFT(125),
+  [108] = {.entry = {.count = 1, .reusable = true}}, SHIFT(128),
+  [110] = {.entry = {.count = 1, .reusable = true}}, SHIFT(130),
+  [112] = {.entry = {.count = 1, .reusable = true}}, SHIFT(131),
+  [114] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_objective, 4, 0, 9),
+  [116] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_decision, 2, 0, 0),
+  [118] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_enum_declaration, 5, 0, 12),
+  [120] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 5, 0, 13),
+  [122] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 7, 0, 23),
+  [124] = {.entry = {.count = 1, .reusable = true}}, SHIFT(9),
+  [126] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 6, 0, 15),
+  [128] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_enum_declaration, 6, 0, 17),
+  [130] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 7, 0, 21),
+  [132] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_import, 8, 0, 24),
+  [134] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 8, 0, 25),
+  [136] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_objective, 8, 0, 26),
+  [138] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint, 9, 0, 27),
+  [140] = {.entry = {.count = 1, .reusable = true}}, SHIFT(79),
+  [142] = {.entry = {.count = 1, .reusable = true}}, SHIFT(136),
+  [144] = {.entry = {.count = 1, .reusable = true}}, SHIFT(50),
+  [146] = {.entry = {.count = 1, .reusable = true}}, SHIFT(132),
+  [148] = {.entry = {.count = 1, .reusable = true}}, SHIFT(105),
+  [150] = {.entry = {.count = 1, .reusable = true}}, SHIFT(15),
+  [152] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_comparison, 3, 0, 3),
+  [154] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_constraint_kind, 1, 0, 0),
+  [156] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_constraint_kind, 1, 0, 0),
+  [158] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_sum, 4, 0, 14),
+  [160] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_sum, 4, 0, 14),
+  [162] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_max, 4, 0, 14),
+  [164] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_max, 4, 0, 14),
+  [166] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_cardinality, 4, 0, 0),
+  [168] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_cardinality, 4, 0, 0),
+  [170] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_min, 4, 0, 14),
+  [172] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_min, 4, 0, 14),
+  [174] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_record, 3, 0, 0),
+  [176] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_record, 4, 0, 0),
+  [178] = {.entry = {.count = 1, .reusable = true}}, SHIFT(64),
+  [180] = {.entry = {.count = 1, .reusable = true}}, SHIFT(91),
+  [182] = {.entry = {.count = 1, .reusable = true}}, SHIFT(51),
+  [184] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__rule_body, 2, 0, 0),
+  [186] = {.entry = {.count = 1, .reusable = true}}, SHIFT(16),
+  [188] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_tuple_repeat1, 2, 0, 0), SHIFT_REPEAT(21),
+  [191] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_tuple_repeat1, 2, 0, 0),
+  [193] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym__rule_body_repeat1, 2, 0, 0),
+  [195] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym__rule_body_repeat1, 2, 0, 0), SHIFT_REPEAT(16),
+  [198] = {.entry = {.count = 1, .reusable = true}}, SHIFT(99),
+  [200] = {.entry = {.count = 1, .reusable = true}}, SHIFT(59),
+  [202] = {.entry = {.count = 1, .reusable = true}}, SHIFT(81),
+  [204] = {.entry = {.count = 1, .reusable = true}}, SHIFT(110),
+  [206] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_record_repeat1, 2, 0, 0), SHIFT_REPEAT(99),
+  [209] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_record_repeat1, 2, 0, 0),
+  [211] = {.entry = {.count = 1, .reusable = true}}, SHIFT(123),
+  [213] = {.entry = {.count = 1, .reusable = true}}, SHIFT(39),
+  [215] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_atom, 2, 0, 1),
+  [217] = {.entry = {.count = 1, .reusable = true}}, SHIFT(41),
+  [219] = {.entry = {.count = 1, .reusable = true}}, SHIFT(37),
+  [221] = {.entry = {.count = 1, .reusable = true}}, SHIFT(35),
+  [223] = {.entry = {.count = 1, .reusable = true}}, SHIFT(134),
+  [225] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_enum_declaration_repeat1, 2, 0, 18),
+  [227] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_enum_declaration_repeat1, 2, 0, 18), SHIFT_REPEAT(134),
+  [230] = {.entry = {.count = 1, .reusable = true}}, SHIFT(40),
+  [232] = {.entry = {.count = 1, .reusable = true}}, SHIFT(69),
+  [234] = {.entry = {.count = 1, .reusable = true}}, SHIFT(58),
+  [236] = {.entry = {.count = 1, .reusable = true}}, SHIFT(97),
+  [238] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_import_repeat2, 2, 0, 22), SHIFT_REPEAT(123),
+  [241] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_import_repeat2, 2, 0, 22),
+  [243] = {.entry = {.count = 1, .reusable = true}}, SHIFT(98),
+  [245] = {.entry = {.count = 1, .reusable = true}}, SHIFT(42),
+  [247] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_captures_repeat1, 2, 0, 0), SHIFT_REPEAT(81),
+  [250] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_captures_repeat1, 2, 0, 0),
+  [252] = {.entry = {.count = 1, .reusable = true}}, SHIFT(21),
+  [254] = {.entry = {.count = 1, .reusable = true}}, SHIFT(8),
+  [256] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__rule_body, 1, 0, 0),
+  [258] = {.entry = {.count = 1, .reusable = true}}, SHIFT(13),
+  [260] = {.entry = {.count = 1, .reusable = true}}, SHIFT(102),
+  [262] = {.entry = {.count = 1, .reusable = true}}, SHIFT(103),
+  [264] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_negation, 2, 0, 7),
+  [266] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_field, 3, 0, 11),
+  [268] = {.entry = {.count = 1, .reusable = true}}, SHIFT(27),
+  [270] = {.entry = {.count = 1, .reusable = true}}, SHIFT(11),
+  [272] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_captures, 4, 0, 0),
+  [274] = {.entry = {.count = 1, .reusable = true}}, SHIFT(124),
+  [276] = {.entry = {.count = 1, .reusable = true}}, SHIFT(101),
+  [278] = {.entry = {.count = 1, .reusable = true}}, SHIFT(118),
+  [280] = {.entry = {.count = 1, .reusable = true}}, SHIFT(119),
+  [282] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_at_most, 2, 0, 0),
+  [284] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_at_least, 2, 0, 0),
+  [286] = {.entry = {.count = 1, .reusable = true}}, SHIFT(107),
+  [288] = {.entry = {.count = 1, .reusable = true}}, SHIFT(126),
+  [290] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_import_repeat1, 2, 0, 5), SHIFT_REPEAT(127),
+  [293] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_captures, 3, 0, 0),
+  [295] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_enum_declaration_repeat1, 2, 0, 16),
+  [297] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_aggregate, 4, 0, 19),
+  [299] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_import_repeat2, 2, 0, 20),
+  [301] = {.entry = {.count = 1, .reusable = true}}, SHIFT(44),
+  [303] = {.entry = {.count = 1, .reusable = true}}, SHIFT(146),
+  [305] = {.entry = {.count = 1, .reusable = true}}, SHIFT(76),
+  [307] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_import_repeat1, 2, 0, 4),
+  [309] = {.entry = {.count = 1, .reusable = true}}, SHIFT(71),
+  [311] = {.entry = {.count = 1, .reusable = true}}, SHIFT(30),
+  [313] = {.entry = {.count = 1, .reusable = true}},  ACCEPT_INPUT(),
+  [315] = {.entry = {.count = 1, .reusable = true}}, SHIFT(29),
+  [317] = {.entry = {.count = 1, .reusable = true}}, SHIFT(113),
+  [319] = {.entry = {.count = 1, .reusable = true}}, SHIFT(20),
+  [321] = {.entry = {.count = 1, .reusable = true}}, SHIFT(144),
+  [323] = {.entry = {.count = 1, .reusable = true}}, SHIFT(75),
+  [325] = {.entry = {.count = 1, .reusable = true}}, SHIFT(116),
+  [327] = {.entry = {.count = 1, .reusable = true}}, SHIFT(53),
+  [329] = {.entry = {.count = 1, .reusable = true}}, SHIFT(135),
+  [331] = {.entry = {.count = 1, .reusable = true}}, SHIFT(87),
+  [333] = {.entry = {.count = 1, .reusable = true}}, SHIFT(61),
+  [335] = {.entry = {.count = 1, .reusable = true}}, SHIFT(36),
+  [337] = {.entry = {.count = 1, .reusable = true}}, SHIFT(111),
+  [339] = {.entry = {.count = 1, .reusable = true}}, SHIFT(147),
+  [341] = {.entry = {.count = 1, .reusable = true}}, SHIFT(72),
+  [343] = {.entry = {.count = 1, .reusable = true}}, SHIFT(31),
+  [345] = {.entry = {.count = 1, .reusable = true}}, SHIFT(17),
+  [347] = {.entry = {.count = 1, .reusable = true}}, SHIFT(100),
+  [349] = {.entry = {.count = 1, .reusable = true}}, SHIFT(43),
+  [351] = {.entry = {.count = 1, .reusable = true}}, SHIFT(48),
+  [353] = {.entry = {.count = 1, .reusable = true}}, SHIFT(33),
+  [355] = {.entry = {.count = 1, .reusable = true}}, SHIFT(45),
+  [357] = {.entry = {.count = 1, .reusable = true}}, SHIFT(145),
+  [359] = {.entry = {.count = 1, .reusable = true}}, SHIFT(52),
+  [361] = {.entry = {.count = 1, .reusable = true}}, SHIFT(56),
+  [363] = {.entry = {.count = 1, .reusable = true}}, SHIFT(54),
+  [365] = {.entry = {.count = 1, .reusable = true}}, SHIFT(148),
 };
 
 #ifdef __cplusplus